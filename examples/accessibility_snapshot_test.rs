@@ -0,0 +1,48 @@
+//! Example demonstrating Page::accessibility_snapshot()
+//!
+//! Builds a small login form and looks up its controls in the AX tree.
+
+use sparkle::prelude::*;
+
+fn count_nodes(node: &AxNode) -> usize {
+    1 + node.children.iter().map(count_nodes).sum::<usize>()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <form>
+                <div><label for="email">Email</label><input id="email" value="me@example.com"></div>
+                <div><button type="submit">Sign in</button></div>
+            </form>`;
+    "#).await?;
+
+    println!("\n=== Interesting nodes only ===");
+    let snapshot = page.accessibility_snapshot(true).await?;
+    println!("{}", serde_json::to_string_pretty(&snapshot).unwrap());
+
+    let button = snapshot.find("button", "Sign in").expect("button should be in the snapshot");
+    assert!(button.children.is_empty());
+    let email = snapshot.find("textbox", "Email").expect("textbox should be in the snapshot");
+    assert_eq!(email.value.as_deref(), Some("me@example.com"));
+
+    println!("\n=== Full tree ===");
+    let full = page.accessibility_snapshot(false).await?;
+    assert!(full.find("button", "Sign in").is_some());
+    println!("nodes: {} interesting, {} total", count_nodes(&snapshot), count_nodes(&full));
+    assert!(count_nodes(&full) > count_nodes(&snapshot), "pruning should drop uninteresting nodes");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
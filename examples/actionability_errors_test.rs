@@ -0,0 +1,89 @@
+//! Example checking that failing actions return structured errors
+//!
+//! Clicks hidden, disabled, moving and covered buttons, fills a read-only
+//! input and navigates to an unreachable host, matching on the returned
+//! `Error` variants and the actionability check each one names.
+
+use sparkle::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <button id="hidden" style="display: none">Hidden</button>
+            <button id="disabled" disabled>Disabled</button>
+            <input id="readonly" readonly value="fixed">
+            <style>
+                @keyframes slide { from { transform: translateX(0); } to { transform: translateX(200px); } }
+                #moving { animation: slide 1s linear infinite; }
+                #overlay { position: fixed; inset: 0; background: rgba(0, 0, 0, 0.3); }
+            </style>
+            <button id="moving">Moving</button>
+            <button id="covered">Covered</button>`;
+    "#).await?;
+
+    let short = Duration::from_millis(500);
+    let click_options = ClickOptionsBuilder::default().timeout(short).build().unwrap();
+
+    println!("\n=== Clicking a hidden button ===");
+    let err = page.locator("#hidden").click(click_options.clone()).await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(
+        &err,
+        Error::NotActionable { selector, reason: NotActionableReason::NotVisible } if selector == "#hidden"
+    ));
+
+    println!("\n=== Clicking a disabled button ===");
+    let err = page.locator("#disabled").click(click_options.clone()).await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(
+        &err,
+        Error::NotActionable { selector, reason: NotActionableReason::NotEnabled } if selector == "#disabled"
+    ));
+
+    println!("\n=== Clicking an animated button ===");
+    let err = page.locator("#moving").click(click_options.clone()).await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(&err, Error::NotActionable { reason: NotActionableReason::NotStable, .. }));
+
+    println!("\n=== Clicking a button under an overlay ===");
+    page.evaluate(r#"
+        document.getElementById('moving').remove();
+        const overlay = document.createElement('div');
+        overlay.id = 'overlay';
+        document.body.appendChild(overlay);
+    "#).await?;
+    let err = page.locator("#covered").click(click_options).await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(&err, Error::NotActionable { reason: NotActionableReason::Intercepted, .. }));
+
+    println!("\n=== Filling a read-only input ===");
+    let err = page.locator("#readonly").timeout(short).fill("new").await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(
+        &err,
+        Error::NotActionable { selector, reason: NotActionableReason::NotEditable } if selector == "#readonly"
+    ));
+
+    println!("\n=== Navigating to an unreachable host ===");
+    let err = page
+        .goto("http://sparkle-unreachable.invalid/", Default::default())
+        .await
+        .unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(&err, Error::Navigation { url, .. } if url == "http://sparkle-unreachable.invalid/"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Example demonstrating page.add_style_tag() and page.add_script_tag()
+//!
+//! Injects CSS and checks the computed style changes, then injects scripts
+//! inline, from a URL and from a file and checks the globals they define.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Testing add_style_tag with inline content ===");
+    page.add_style_tag(AddTagOptionsBuilder::default().content("h1 { color: rgb(255, 0, 0); }").build().unwrap())
+        .await?;
+    let color = page.evaluate("return getComputedStyle(document.querySelector('h1')).color;").await?;
+    println!("h1 color: {}", color);
+    assert_eq!(color, serde_json::json!("rgb(255, 0, 0)"));
+
+    println!("\n=== Testing add_style_tag with a URL ===");
+    page.add_style_tag(
+        AddTagOptionsBuilder::default()
+            .url("data:text/css,h1%20%7B%20font-size%3A%2050px%3B%20%7D")
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    let size = page.evaluate("return getComputedStyle(document.querySelector('h1')).fontSize;").await?;
+    assert_eq!(size, serde_json::json!("50px"));
+
+    println!("\n=== Testing add_script_tag with inline content ===");
+    page.add_script_tag(AddTagOptionsBuilder::default().content("window.inlineGlobal = 'inline';").build().unwrap())
+        .await?;
+    assert_eq!(page.evaluate("return window.inlineGlobal;").await?, serde_json::json!("inline"));
+
+    println!("\n=== Testing add_script_tag with a URL ===");
+    page.add_script_tag(
+        AddTagOptionsBuilder::default()
+            .url("data:text/javascript,window.urlGlobal%20%3D%20'url'%3B")
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    assert_eq!(page.evaluate("return window.urlGlobal;").await?, serde_json::json!("url"));
+
+    println!("\n=== Testing add_script_tag with a file ===");
+    let path = std::env::temp_dir().join("sparkle-add-tag-test.js");
+    std::fs::write(&path, "window.fileGlobal = 'file';")?;
+    page.add_script_tag(AddTagOptionsBuilder::default().path(path.clone()).build().unwrap())
+        .await?;
+    assert_eq!(page.evaluate("return window.fileGlobal;").await?, serde_json::json!("file"));
+    std::fs::remove_file(&path)?;
+
+    println!("\n=== Testing a URL that fails to load ===");
+    let missing = page
+        .add_script_tag(AddTagOptionsBuilder::default().url("https://www.example.com/missing.js").build().unwrap())
+        .await;
+    assert!(missing.is_err(), "a script that fails to load should be an error");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
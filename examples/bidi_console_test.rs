@@ -0,0 +1,55 @@
+//! Example demonstrating console and network events over WebDriver BiDi
+//!
+//! Launches with `Protocol::Bidi`, logs from the page and checks the message
+//! arrives through `log.entryAdded`. Skips when the driver does not support
+//! BiDi.
+
+use std::sync::{Arc, Mutex};
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = match playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).protocol(Protocol::Bidi).build().unwrap())
+        .await
+    {
+        Ok(browser) => browser,
+        Err(Error::NotImplemented(reason)) => {
+            println!("Skipping: {}", reason);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    let page = browser.new_page().await?;
+
+    println!("\n=== Testing on_console over BiDi ===");
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let handler_messages = Arc::clone(&messages);
+    page.on_console(move |message| {
+        handler_messages.lock().unwrap().push((message.r#type().to_string(), message.text().to_string()));
+    })
+    .await?;
+    page.goto_data_html("<script>console.log('hello', 42); console.warn('careful');</script>").await?;
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    let messages = messages.lock().unwrap().clone();
+    println!("messages: {:?}", messages);
+    assert!(messages.contains(&("log".to_string(), "hello 42".to_string())));
+    assert!(messages.contains(&("warning".to_string(), "careful".to_string())));
+
+    println!("\n=== Testing on_response over BiDi ===");
+    let statuses = Arc::new(Mutex::new(Vec::new()));
+    let handler_statuses = Arc::clone(&statuses);
+    page.on_response(move |response| handler_statuses.lock().unwrap().push(response.status()))
+        .await?;
+    page.goto("https://www.example.com", Default::default()).await?;
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert!(statuses.lock().unwrap().contains(&200));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//! Example demonstrating Page::bring_to_front()
+//!
+//! Opens two tabs and checks that the first one becomes visible again.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let context = browser.new_context(Default::default()).await?;
+
+    let first = context.new_page().await?;
+    first.goto("https://www.example.com", Default::default()).await?;
+
+    let second = context.new_page().await?;
+    second.goto("https://www.example.com/?second", Default::default()).await?;
+
+    println!("\n=== Testing pages use separate tabs ===");
+    let first_url = first.url().await?;
+    let second_url = second.url().await?;
+    println!("first: {}, second: {}", first_url, second_url);
+    assert!(!first_url.contains("second"), "first page should keep its own URL");
+    assert!(second_url.contains("second"), "second page should have its own URL");
+
+    println!("\n=== Testing bring_to_front ===");
+    first.bring_to_front().await?;
+    let visibility = first.evaluate("return document.visibilityState;").await?;
+    println!("first page visibilityState: {}", visibility);
+    assert_eq!(visibility, serde_json::json!("visible"), "first page should be visible after bring_to_front");
+
+    println!("\n=== Testing close releases the tab ===");
+    second.close().await?;
+    let title = first.title().await?;
+    println!("first page title after closing second: {}", title);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
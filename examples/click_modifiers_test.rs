@@ -0,0 +1,54 @@
+//! Example demonstrating Locator::click_with_modifiers()
+//!
+//! Performs a Control-click and checks the modifier flags on the event.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        window.lastClick = null;
+        const target = document.createElement('button');
+        target.id = 'target';
+        target.textContent = 'Click me';
+        target.addEventListener('click', e => {
+            window.lastClick = { ctrl: e.ctrlKey, shift: e.shiftKey, alt: e.altKey, meta: e.metaKey };
+        });
+        document.body.appendChild(target);
+    "#).await?;
+
+    println!("\n=== Testing Control click ===");
+    page.locator("#target").click_with_modifiers("Control", Default::default()).await?;
+    let event = page.evaluate("return window.lastClick;").await?;
+    println!("click event: {}", event);
+    assert_eq!(event["ctrl"], serde_json::json!(true), "ctrlKey should be set");
+    assert_eq!(event["shift"], serde_json::json!(false), "shiftKey should not be set");
+
+    println!("\n=== Testing Control+Shift click ===");
+    page.locator("#target").click_with_modifiers("Control+Shift", Default::default()).await?;
+    let event = page.evaluate("return window.lastClick;").await?;
+    assert_eq!(event["ctrl"], serde_json::json!(true));
+    assert_eq!(event["shift"], serde_json::json!(true));
+
+    println!("\n=== Testing plain click releases modifiers ===");
+    page.locator("#target").click(Default::default()).await?;
+    let event = page.evaluate("return window.lastClick;").await?;
+    assert_eq!(event["ctrl"], serde_json::json!(false), "modifiers should be released after the click");
+
+    println!("\n=== Testing an unknown modifier ===");
+    let result = page.locator("#target").click_with_modifiers("Hyper", Default::default()).await;
+    assert!(result.is_err(), "unknown modifier names should be rejected");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! Example demonstrating Page::write_clipboard() and Page::read_clipboard()
+//!
+//! Clicks a "copy link" button and asserts what it put on the clipboard, then
+//! writes the clipboard and clicks a "paste" button that reads it into a div.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <button id="copy">Copy link</button>
+            <button id="paste">Paste</button>
+            <div id="pasted"></div>`;
+        document.getElementById('copy').addEventListener('click', () => {
+            navigator.clipboard.writeText('https://www.example.com/share/42')
+                .then(() => document.body.dataset.copied = 'yes');
+        });
+        document.getElementById('paste').addEventListener('click', () => {
+            navigator.clipboard.readText()
+                .then((text) => document.getElementById('pasted').textContent = text);
+        });
+    "#).await?;
+
+    println!("\n=== Testing write_clipboard / read_clipboard ===");
+    page.write_clipboard("hello from sparkle").await?;
+    let text = page.read_clipboard().await?;
+    println!("clipboard: {}", text);
+    assert_eq!(text, "hello from sparkle");
+
+    println!("\n=== Testing a copy button ===");
+    page.locator("#copy").click(Default::default()).await?;
+    page.locator("body[data-copied='yes']").wait_for().await?;
+    let text = page.read_clipboard().await?;
+    println!("clipboard: {}", text);
+    assert_eq!(text, "https://www.example.com/share/42");
+
+    println!("\n=== Testing a paste button ===");
+    page.write_clipboard("pasted by sparkle").await?;
+    page.locator("#paste").click(Default::default()).await?;
+    page.locator("#pasted:not(:empty)").wait_for().await?;
+    let pasted = page.locator("#pasted").text_content().await?;
+    println!("pasted: {:?}", pasted);
+    assert_eq!(pasted, "pasted by sparkle");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Example demonstrating page.content()
+//!
+//! Checks that the returned HTML keeps the doctype, that an iframe's own
+//! document can be read, and that large documents come back whole.
+
+use sparkle::prelude::*;
+
+const DOCUMENT: &str = "<!DOCTYPE html><html><head><title>Known</title></head><body><p id=\"greeting\">Hello</p></body></html>";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    println!("\n=== Testing content() includes the doctype ===");
+    page.set_content(DOCUMENT, Default::default()).await?;
+    let html = page.content().await?;
+    println!("Content: {}", html);
+    assert_eq!(html, DOCUMENT);
+
+    println!("\n=== Testing content() inside a frame ===");
+    page.evaluate(
+        r#"
+        const frame = document.createElement('iframe');
+        frame.id = 'frame';
+        frame.srcdoc = '<!DOCTYPE html><html><head></head><body><p>Framed</p></body></html>';
+        document.body.appendChild(frame);
+        return new Promise((resolve) => frame.onload = resolve);
+    "#,
+    )
+    .await?;
+    let framed = page.frame_locator("#frame").content().await?;
+    assert_eq!(framed, "<!DOCTYPE html><html><head></head><body><p>Framed</p></body></html>");
+    assert!(page.content().await?.contains("<iframe id=\"frame\""));
+
+    println!("\n=== Testing content() of a large document ===");
+    page.evaluate(
+        r#"
+        const text = 'sparkle ✨ '.repeat(300000);
+        document.body.innerHTML = `<pre id="large">${text}</pre>`;
+    "#,
+    )
+    .await?;
+    let html = page.content().await?;
+    let expected_len = page
+        .evaluate("return document.documentElement.outerHTML.length + '<!DOCTYPE html>'.length;")
+        .await?
+        .as_u64()
+        .unwrap();
+    println!("Content length: {} characters", html.chars().count());
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</pre></body></html>"));
+    // JS lengths count UTF-16 units; '✨' is one unit and one char
+    assert_eq!(html.chars().count() as u64, expected_len);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Example checking the java_script_enabled and bypass_csp context options
+//!
+//! Loads data URLs whose inline scripts are blocked by the context settings
+//! or by a Content-Security-Policy.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    println!("\n=== Testing java_script_enabled(false) ===");
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .java_script_enabled(false)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+    page.goto(
+        "data:text/html,<title>original</title><script>document.title = 'changed';</script>",
+        Default::default(),
+    )
+    .await?;
+    let title = page.title().await?;
+    println!("title with JavaScript disabled: {}", title);
+    assert_eq!(title, "original", "inline scripts should not run");
+    context.close().await?;
+
+    println!("\n=== Testing bypass_csp(true) ===");
+    let csp_page = "data:text/html,<meta http-equiv=\"Content-Security-Policy\" content=\"script-src 'none'\"><p>csp</p>";
+    let inject = r#"
+        const script = document.createElement('script');
+        script.textContent = 'window.__injected = true;';
+        document.head.appendChild(script);
+        return window.__injected === true;
+    "#;
+
+    let strict = browser.new_context(Default::default()).await?;
+    let page = strict.new_page().await?;
+    page.goto(csp_page, Default::default()).await?;
+    let ran = page.evaluate(inject).await?;
+    println!("injected script ran without bypass: {}", ran);
+    assert_eq!(ran, serde_json::json!(false), "CSP should block the inline script");
+    strict.close().await?;
+
+    let bypass = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .bypass_csp(true)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = bypass.new_page().await?;
+    page.goto(csp_page, Default::default()).await?;
+    let ran = page.evaluate(inject).await?;
+    println!("injected script ran with bypass: {}", ran);
+    assert_eq!(ran, serde_json::json!(true), "bypass_csp should allow the inline script");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Example demonstrating URL-scoped cookies
+//!
+//! Restores cookies given a `url` instead of `domain`/`path` and checks the
+//! derived domain, path and secure flag when they are read back.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    let state = StorageState {
+        cookies: vec![
+            CookieState {
+                name: "by_url".to_string(),
+                value: "abc".to_string(),
+                url: Some("https://www.example.com/docs/index.html".to_string()),
+                ..Default::default()
+            },
+            CookieState {
+                name: "by_domain".to_string(),
+                value: "def".to_string(),
+                domain: ".example.com".to_string(),
+                path: "/".to_string(),
+                ..Default::default()
+            },
+        ],
+        origins: vec![],
+    };
+
+    println!("\n=== Restoring cookies scoped by url and by domain ===");
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .storage_state(StorageStateSource::State(state))
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+
+    println!("\n=== Testing the derived scope ===");
+    let saved = context.storage_state(None::<&str>).await?;
+    let cookie = saved
+        .cookies
+        .iter()
+        .find(|c| c.name == "by_url")
+        .expect("url-scoped cookie should be present");
+    println!("cookie: {:?}", cookie);
+    assert_eq!(cookie.domain, "www.example.com", "url cookies should be host-only");
+    assert_eq!(cookie.path, "/docs");
+    assert!(cookie.secure, "https urls should give a secure cookie");
+    assert!(saved.cookies.iter().any(|c| c.name == "by_domain" && c.domain == ".example.com"));
+
+    println!("\n=== Testing the cookie is only sent under its path ===");
+    page.goto("https://www.example.com/docs/", Default::default()).await?;
+    let cookies = page.evaluate("return document.cookie;").await?;
+    assert!(cookies.as_str().unwrap().contains("by_url=abc"));
+    page.goto("https://www.example.com/", Default::default()).await?;
+    let cookies = page.evaluate("return document.cookie;").await?;
+    assert!(!cookies.as_str().unwrap().contains("by_url"));
+
+    println!("\n=== Testing a cookie without url or domain is rejected ===");
+    let invalid = StorageState {
+        cookies: vec![CookieState {
+            name: "unscoped".to_string(),
+            value: "x".to_string(),
+            ..Default::default()
+        }],
+        origins: vec![],
+    };
+    let result = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .storage_state(StorageStateSource::State(invalid))
+                .build()
+                .unwrap(),
+        )
+        .await;
+    assert!(matches!(result, Err(Error::InvalidArgument(_))), "unexpected result: {:?}", result.err());
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Example demonstrating Page::wait_for_download()
+//!
+//! Clicks a link to a small generated file and checks the downloaded copy.
+
+use sparkle::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .accept_downloads(true)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        const blob = new Blob(['id,name\n1,sparkle\n'], { type: 'text/csv' });
+        document.body.innerHTML = `<a id="export" download="export.csv">Export</a>`;
+        document.getElementById('export').href = URL.createObjectURL(blob);
+    "#).await?;
+
+    println!("\n=== Testing a link download ===");
+    page.locator("#export").click(Default::default()).await?;
+    let download = page.wait_for_download(Some(Duration::from_secs(10))).await?;
+    println!("suggested filename: {}", download.suggested_filename());
+    assert_eq!(download.suggested_filename(), "export.csv");
+
+    let path = download.path().await?;
+    println!("downloaded to: {}", path.display());
+    assert_eq!(std::fs::read_to_string(&path)?, "id,name\n1,sparkle\n");
+
+    println!("\n=== Testing save_as ===");
+    let dest = std::env::temp_dir().join("sparkle-download-test").join("export.csv");
+    download.save_as(&dest).await?;
+    assert_eq!(std::fs::read_to_string(&dest)?, "id,name\n1,sparkle\n");
+    std::fs::remove_dir_all(dest.parent().unwrap())?;
+
+    println!("\n=== Testing timeout without a download ===");
+    let result = page.wait_for_download(Some(Duration::from_millis(500))).await;
+    assert!(matches!(result, Err(Error::Timeout { .. })));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
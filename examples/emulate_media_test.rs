@@ -0,0 +1,66 @@
+//! Example demonstrating Page::emulate_media() and the context color_scheme option
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Testing dark mode ===");
+    page.emulate_media(
+        EmulateMediaOptionsBuilder::default()
+            .color_scheme(ColorScheme::Dark)
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    let dark = page.evaluate("return window.matchMedia('(prefers-color-scheme: dark)').matches;").await?;
+    println!("prefers-color-scheme: dark -> {}", dark);
+    assert_eq!(dark, serde_json::json!(true));
+
+    println!("\n=== Testing print media keeps the color scheme ===");
+    page.emulate_media(
+        EmulateMediaOptionsBuilder::default()
+            .media(Media::Print)
+            .reduced_motion(ReducedMotion::Reduce)
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    let state = page.evaluate(r#"
+        return {
+            print: window.matchMedia('print').matches,
+            dark: window.matchMedia('(prefers-color-scheme: dark)').matches,
+            reduce: window.matchMedia('(prefers-reduced-motion: reduce)').matches,
+        };
+    "#).await?;
+    println!("media state: {}", state);
+    assert_eq!(state["print"], serde_json::json!(true));
+    assert_eq!(state["dark"], serde_json::json!(true), "earlier color scheme should be kept");
+    assert_eq!(state["reduce"], serde_json::json!(true));
+
+    println!("\n=== Testing the context color_scheme option ===");
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .color_scheme(ColorScheme::Dark)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let dark_page = context.new_page().await?;
+    dark_page.goto("https://www.example.com", Default::default()).await?;
+    let dark = dark_page.evaluate("return window.matchMedia('(prefers-color-scheme: dark)').matches;").await?;
+    assert_eq!(dark, serde_json::json!(true), "context color_scheme should apply to new pages");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Example demonstrating Page::evaluate_isolated()
+//!
+//! Checks that globals defined in an isolated world stay invisible to the
+//! page and that page globals can't clobber isolated helpers.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Defining a global in an isolated world ===");
+    let value = page.evaluate_isolated("window.__helper = 42; window.__helper").await?;
+    println!("isolated value: {}", value);
+    assert_eq!(value, serde_json::json!(42));
+
+    let visible = page.evaluate("return typeof window.__helper;").await?;
+    println!("typeof __helper in page context: {}", visible);
+    assert_eq!(visible, serde_json::json!("undefined"), "isolated globals must not leak into the page");
+
+    println!("\n=== Page globals don't reach the isolated world ===");
+    page.evaluate("window.JSON = null; window.__pageOnly = true;").await?;
+    let isolated = page
+        .evaluate_isolated("[typeof window.__pageOnly, JSON.stringify({ ok: true })]")
+        .await?;
+    println!("isolated view: {}", isolated);
+    assert_eq!(isolated, serde_json::json!(["undefined", "{\"ok\":true}"]));
+
+    println!("\n=== The DOM is shared ===");
+    let heading = page.evaluate_isolated("document.querySelector('h1').textContent").await?;
+    println!("heading: {}", heading);
+    assert_eq!(heading, serde_json::json!("Example Domain"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
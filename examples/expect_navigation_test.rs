@@ -0,0 +1,83 @@
+//! Example demonstrating page.expect_navigation()
+//!
+//! Clicks links served by a local HTTP server inside the action, including one
+//! that redirects immediately, and checks each navigation is captured.
+
+use sparkle::prelude::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const HOME: &str = r##"<a id="redirect" href="/redirect">Redirect</a> <a id="anchor" href="#details">Details</a>"##;
+
+/// Serve the home page, a redirect to `/target` and the target page
+async fn http_server() -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = vec![0; 4096];
+                let Ok(read) = stream.read(&mut request).await else { return };
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, extra, body) = match path {
+                    "/" => ("200 OK", "", HOME),
+                    "/redirect" => ("302 Found", "Location: /target\r\n", ""),
+                    "/target" => ("200 OK", "", "<title>Target</title><h1>Target</h1>"),
+                    _ => ("404 Not Found", "", ""),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: text/html\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    extra,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(url)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let url = http_server().await?;
+
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+    page.goto(&format!("{}/", url), Default::default()).await?;
+
+    println!("\n=== Testing a click that redirects ===");
+    let response = page
+        .expect_navigation(page.click("#redirect", Default::default()), Default::default())
+        .await?
+        .expect("the navigation should have a response");
+    println!("{} {}", response.status(), response.url());
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.url(), format!("{}/target", url));
+    assert_eq!(page.title().await?, "Target");
+
+    println!("\n=== Testing a navigation within the document ===");
+    page.goto(&format!("{}/", url), Default::default()).await?;
+    let response = page
+        .expect_navigation(page.click("#anchor", Default::default()), Default::default())
+        .await?;
+    assert!(response.is_none());
+    assert!(page.url().await?.ends_with("#details"));
+
+    println!("\n=== Testing a timeout when nothing navigates ===");
+    let options = NavigationOptionsBuilder::default()
+        .timeout(std::time::Duration::from_millis(500))
+        .build()
+        .unwrap();
+    let result = page.expect_navigation(page.evaluate("return 1;"), options).await;
+    assert!(matches!(result, Err(Error::Timeout { .. })));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Example demonstrating Page::wait_for_file_chooser()
+//!
+//! A styled "Upload" button opens the picker of a hidden file input; the
+//! intercepted chooser is fulfilled with temporary files.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <input type="file" id="single" style="display: none">
+            <input type="file" id="multi" multiple style="display: none">
+            <button id="upload" class="fancy">Upload</button>
+            <button id="upload-many" class="fancy">Upload many</button>
+            <p id="status"></p>`;
+        document.getElementById('upload').onclick = () => document.getElementById('single').click();
+        document.getElementById('upload-many').onclick = () => document.getElementById('multi').click();
+        document.getElementById('single').onchange = (e) => {
+            document.getElementById('status').textContent = 'changed: ' + e.target.files[0].name;
+        };
+    "#).await?;
+
+    let dir = std::env::temp_dir().join("sparkle-file-chooser-test");
+    std::fs::create_dir_all(&dir)?;
+    let avatar = dir.join("avatar.txt");
+    let notes = dir.join("notes.txt");
+    std::fs::write(&avatar, "avatar")?;
+    std::fs::write(&notes, "notes")?;
+
+    println!("\n=== Testing a single-file chooser ===");
+    let (chooser, _) = tokio::try_join!(
+        page.wait_for_file_chooser(None),
+        page.click("#upload", Default::default()),
+    )?;
+    assert!(!chooser.is_multiple());
+    chooser.set_files(&[&avatar]).await?;
+
+    let files = page
+        .evaluate("const f = document.getElementById('single').files; return [f.length, f[0].name];")
+        .await?;
+    println!("files: {}", files);
+    assert_eq!(files, serde_json::json!([1, "avatar.txt"]));
+    let status = page.locator("#status").text_content().await?;
+    assert_eq!(status, "changed: avatar.txt");
+
+    println!("\n=== Testing a multi-file chooser ===");
+    let (chooser, _) = tokio::try_join!(
+        page.wait_for_file_chooser(None),
+        page.click("#upload-many", Default::default()),
+    )?;
+    assert!(chooser.is_multiple());
+    chooser.set_files(&[&avatar, &notes]).await?;
+    let count = page.evaluate("return document.getElementById('multi').files.length;").await?;
+    assert_eq!(count, serde_json::json!(2));
+
+    std::fs::remove_dir_all(&dir)?;
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Example demonstrating Page::fill_form()
+//!
+//! Fills a text input, a checkbox and a select in one call.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <form>
+                <input id="name">
+                <input type="checkbox" id="terms">
+                <input type="checkbox" id="newsletter" checked>
+                <select id="country">
+                    <option value="us">United States</option>
+                    <option value="ca">Canada</option>
+                </select>
+            </form>`;
+        window.changes = [];
+        document.getElementById('country').onchange = (e) => window.changes.push(e.target.value);
+    "#).await?;
+
+    println!("\n=== Filling the form ===");
+    page.fill_form(vec![
+        ("#name".to_string(), "Ada Lovelace".into()),
+        ("#terms".to_string(), true.into()),
+        ("#newsletter".to_string(), false.into()),
+        ("#country".to_string(), FormValue::Select("Canada".to_string())),
+    ])
+    .await?;
+
+    let values = page
+        .evaluate(r#"
+            return [
+                document.getElementById('name').value,
+                document.getElementById('terms').checked,
+                document.getElementById('newsletter').checked,
+                document.getElementById('country').value,
+                window.changes,
+            ];
+        "#)
+        .await?;
+    println!("values: {}", values);
+    assert_eq!(
+        values,
+        serde_json::json!(["Ada Lovelace", true, false, "ca", ["ca"]])
+    );
+
+    println!("\n=== Testing an unknown option ===");
+    let err = page
+        .fill_form(vec![("#country".to_string(), FormValue::Select("Mars".to_string()))])
+        .await
+        .unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(err, Error::InvalidArgument(_)));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Example checking that fill() waits for an input to become editable
+//!
+//! The input starts disabled and read-only, as during SPA hydration.
+
+use sparkle::prelude::*;
+use std::time::Instant;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        const input = document.createElement('input');
+        input.id = 'name';
+        input.disabled = true;
+        input.readOnly = true;
+        document.body.appendChild(input);
+        setTimeout(() => { input.disabled = false; }, 100);
+        setTimeout(() => { input.readOnly = false; }, 200);
+    "#).await?;
+
+    println!("\n=== Testing fill waits for hydration ===");
+    let start = Instant::now();
+    page.locator("#name").fill("Ada Lovelace").await?;
+    let waited = start.elapsed();
+    println!("fill completed after {:?}", waited);
+
+    let value = page.evaluate("return document.getElementById('name').value;").await?;
+    assert_eq!(value, serde_json::json!("Ada Lovelace"));
+
+    println!("\n=== Testing fill times out on a read-only input ===");
+    page.evaluate(r#"
+        const locked = document.createElement('input');
+        locked.id = 'locked';
+        locked.readOnly = true;
+        document.body.appendChild(locked);
+    "#).await?;
+    let result = page
+        .locator("#locked")
+        .timeout(std::time::Duration::from_millis(500))
+        .fill("nope")
+        .await;
+    println!("result: {:?}", result);
+    assert!(result.is_err(), "fill should not write into a read-only input");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! Example demonstrating forced clicks and taps
+//!
+//! A button with `pointer-events: none` never receives real pointer input, so
+//! a normal click times out while `force` dispatches the events in-page.
+
+use std::time::Duration;
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(
+        r#"
+        document.body.innerHTML = `
+            <button id="inert" style="pointer-events: none">Inert</button>
+            <div style="position: relative">
+                <button id="covered">Covered</button>
+                <div style="position: absolute; inset: 0; background: rgba(0, 0, 0, 0.2)"></div>
+            </div>
+            <button id="plain">Plain</button>
+        `;
+        window.clicks = { inert: 0, covered: 0, plain: 0 };
+        window.pointerTypes = [];
+        for (const id of Object.keys(window.clicks)) {
+            const button = document.getElementById(id);
+            button.addEventListener('click', () => window.clicks[id]++);
+            button.addEventListener('pointerdown', (e) => window.pointerTypes.push(e.pointerType));
+        }
+    "#,
+    )
+    .await?;
+
+    let clicks = |id: &'static str| {
+        let page = &page;
+        async move {
+            page.evaluate(&format!("return window.clicks['{}'];", id))
+                .await
+                .map(|count| count.as_u64().unwrap_or(0))
+        }
+    };
+
+    println!("\n=== Testing that a normal click is refused ===");
+    let err = page
+        .locator("#inert")
+        .click(ClickOptionsBuilder::default().timeout(Duration::from_secs(1)).build().unwrap())
+        .await
+        .unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(err, Error::NotActionable { .. }));
+    assert_eq!(clicks("inert").await?, 0);
+
+    println!("\n=== Testing a forced click on a pointer-events: none button ===");
+    let force = ClickOptionsBuilder::default().force(true).build().unwrap();
+    page.locator("#inert").click(force.clone()).await?;
+    assert_eq!(clicks("inert").await?, 1, "the click handler should have fired");
+
+    println!("\n=== Testing a forced click under an overlay ===");
+    page.click("#covered", force.clone()).await?;
+    assert_eq!(clicks("covered").await?, 1);
+
+    println!("\n=== Testing a forced click on a reachable button ===");
+    page.locator("#plain").click(force).await?;
+    assert_eq!(clicks("plain").await?, 1);
+
+    println!("\n=== Testing a forced tap ===");
+    page.evaluate("window.pointerTypes = [];").await?;
+    page.tap("#inert", TapOptionsBuilder::default().force(true).build().unwrap())
+        .await?;
+    assert_eq!(clicks("inert").await?, 2);
+    let pointer_types = page.evaluate("return window.pointerTypes;").await?;
+    assert_eq!(pointer_types, serde_json::json!(["touch"]));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! Example demonstrating FrameLocator::evaluate()
+//!
+//! Reads widget-internal state from inside an iframe.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        window.widgetVersion = 'top-level';
+        const frame = document.createElement('iframe');
+        frame.id = 'widget';
+        frame.srcdoc = '<script>window.widgetVersion = "2.4.1";</script><p>Widget</p>';
+        document.body.appendChild(frame);
+    "#).await?;
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+    println!("\n=== Testing evaluate inside the frame ===");
+    let widget = page.frame_locator("#widget");
+    let version = widget.evaluate("return window.widgetVersion;", vec![]).await?;
+    println!("widgetVersion in frame: {}", version);
+    assert_eq!(version, serde_json::json!("2.4.1"));
+
+    println!("\n=== Testing evaluate with arguments ===");
+    let sum = widget
+        .evaluate("return arguments[0] + arguments[1];", vec![serde_json::json!(2), serde_json::json!(3)])
+        .await?;
+    assert_eq!(sum, serde_json::json!(5));
+
+    println!("\n=== Testing the page context is restored ===");
+    let top = page.evaluate("return window.widgetVersion;").await?;
+    println!("widgetVersion at top level: {}", top);
+    assert_eq!(top, serde_json::json!("top-level"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
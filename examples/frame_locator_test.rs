@@ -108,12 +108,8 @@ async fn main() -> Result<()> {
 </html>
     "#;
 
-    // Navigate to data URL with the test HTML
-    page.goto(
-        &format!("data:text/html,{}", urlencoding::encode(html_content)),
-        Default::default(),
-    )
-    .await?;
+    // Load the test HTML
+    page.goto_data_html(html_content).await?;
 
     println!("✓ Test page loaded");
     tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
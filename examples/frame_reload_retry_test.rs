@@ -0,0 +1,58 @@
+//! Example checking that frame switching survives an iframe being replaced
+//!
+//! Replaces a widget iframe on a short timer, the way refreshing captcha
+//! frames do, while repeatedly evaluating inside it.
+
+use sparkle::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        window.frameReloads = 0;
+        const mount = () => {
+            const frame = document.createElement('iframe');
+            frame.id = 'captcha';
+            frame.srcdoc = `<p id="generation">${window.frameReloads}</p>`;
+            const old = document.getElementById('captcha');
+            if (old) old.replaceWith(frame); else document.body.appendChild(frame);
+        };
+        mount();
+        const timer = setInterval(() => {
+            window.frameReloads += 1;
+            mount();
+            if (window.frameReloads >= 20) clearInterval(timer);
+        }, 30);
+    "#).await?;
+
+    println!("\n=== Switching into a frame while it reloads ===");
+    let captcha = page.frame_locator("#captcha");
+    for attempt in 0..10 {
+        let ready = captcha.evaluate("return document.readyState;", vec![]).await?;
+        println!("attempt {}: readyState {}", attempt, ready);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    let reloads = page.evaluate("return window.frameReloads;").await?;
+    println!("frame reloads during the test: {}", reloads);
+    assert!(reloads.as_u64().unwrap_or(0) > 0, "the frame should have been replaced");
+
+    println!("\n=== Switching after the frame settles ===");
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let generation = captcha.locator("#generation").text_content().await?;
+    println!("final frame generation: {:?}", generation);
+    assert_eq!(generation.as_deref(), Some("20"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Example demonstrating get_by_role() with accessible-name filtering
+//!
+//! Distinguishes buttons sharing a role by their accessible name, both on the
+//! page and inside an iframe, and finds a link by an `aria-label` that differs
+//! from its text.
+
+use sparkle::prelude::*;
+
+const BUTTONS: &str = r#"
+    <button id="save">Save</button>
+    <button id="save-draft">Save draft</button>
+    <div role="button" id="close" aria-label="Close dialog">X</div>
+    <label for="submit">Send form</label>
+    <input type="submit" id="submit" value="Go">
+    <a href="/top" id="menu-link" aria-label="Open navigation">Menu</a>
+"#;
+
+fn by_name(name: &str, exact: bool) -> GetByRoleOptions {
+    GetByRoleOptionsBuilder::default().name(name).exact(exact).build().unwrap()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(&format!(
+        r#"
+        document.body.innerHTML = `{}<iframe id="frame"></iframe>`;
+        document.getElementById('frame').srcdoc = `{}`;
+        return new Promise((resolve) => document.getElementById('frame').onload = resolve);
+    "#,
+        BUTTONS, BUTTONS
+    ))
+    .await?;
+
+    println!("\n=== Testing substring name match ===");
+    let save = page.get_by_role("button", by_name("save", false));
+    assert_eq!(save.count().await?, 2, "'save' should match both Save buttons");
+
+    println!("\n=== Testing exact name match ===");
+    let save = page.get_by_role("button", by_name("Save", true));
+    assert_eq!(save.count().await?, 1);
+    assert_eq!(save.get_attribute("id").await?.as_deref(), Some("save"));
+
+    let draft = page.get_by_role("button", by_name("Save draft", true));
+    assert_eq!(draft.get_attribute("id").await?.as_deref(), Some("save-draft"));
+
+    println!("\n=== Testing aria-label and <label> names ===");
+    let close = page.get_by_role("button", by_name("Close dialog", true));
+    assert_eq!(close.get_attribute("id").await?.as_deref(), Some("close"));
+    assert_eq!(
+        page.get_by_role("button", by_name("X", true)).count().await?,
+        0,
+        "aria-label overrides the text content"
+    );
+
+    let submit = page.get_by_role("button", by_name("Send form", true));
+    assert_eq!(submit.get_attribute("id").await?.as_deref(), Some("submit"));
+
+    println!("\n=== Testing link names from the accessibility tree ===");
+    let menu = page.get_by_role("link", by_name("Open navigation", true)).strict(true);
+    assert_eq!(menu.get_attribute("id").await?.as_deref(), Some("menu-link"));
+    assert_eq!(
+        page.get_by_role("link", by_name("Menu", true)).count().await?,
+        0,
+        "the link is named by its aria-label, not its text"
+    );
+
+    println!("\n=== Testing names inside a frame ===");
+    let frame = page.frame_locator("#frame");
+    let draft = frame.get_by_role_with_options("button", by_name("Save draft", true));
+    assert_eq!(draft.get_attribute("id").await?.as_deref(), Some("save-draft"));
+    let save = frame.get_by_role_with_options("button", by_name("Save", true));
+    assert_eq!(save.get_attribute("id").await?.as_deref(), Some("save"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Example demonstrating get_by_role() with ARIA state options
+//!
+//! Selects the expanded menu button among several buttons.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <nav>
+                <button id="file" aria-expanded="false">File</button>
+                <button id="edit" aria-expanded="true">Edit</button>
+                <div role="button" id="view" aria-expanded="false">View</div>
+                <button id="save" disabled>Save</button>
+                <input type="checkbox" id="agree" checked>
+                <input type="checkbox" id="newsletter">
+            </nav>`;
+    "#).await?;
+
+    println!("\n=== Testing role matching ===");
+    let buttons = page.get_by_role("button", Default::default());
+    let count = buttons.count().await?;
+    println!("buttons: {}", count);
+    assert_eq!(count, 4, "three <button>s and one [role=button] should match");
+
+    println!("\n=== Testing expanded ===");
+    let expanded = page.get_by_role(
+        "button",
+        GetByRoleOptionsBuilder::default().expanded(true).build().unwrap(),
+    );
+    assert_eq!(expanded.count().await?, 1);
+    assert_eq!(expanded.get_attribute("id").await?.as_deref(), Some("edit"));
+
+    println!("\n=== Testing disabled ===");
+    let disabled = page.get_by_role(
+        "button",
+        GetByRoleOptionsBuilder::default().disabled(true).build().unwrap(),
+    );
+    assert_eq!(disabled.get_attribute("id").await?.as_deref(), Some("save"));
+
+    println!("\n=== Testing checked ===");
+    let checked = page.get_by_role(
+        "checkbox",
+        GetByRoleOptionsBuilder::default().checked(true).build().unwrap(),
+    );
+    assert_eq!(checked.count().await?, 1);
+    assert_eq!(checked.get_attribute("id").await?.as_deref(), Some("agree"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
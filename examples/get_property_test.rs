@@ -0,0 +1,39 @@
+//! Example demonstrating locator.get_property() against get_attribute()
+//!
+//! Clicks a checkbox and types into an input, then checks that the live
+//! `checked` and `value` properties change while the attributes from the
+//! markup do not.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto_data_html(r#"
+        <input id="terms" type="checkbox">
+        <input id="name" value="initial">
+    "#).await?;
+
+    println!("\n=== Testing the checked property ===");
+    let terms = page.locator("#terms");
+    assert_eq!(terms.get_property("checked").await?, serde_json::json!(false));
+    terms.click(Default::default()).await?;
+    assert_eq!(terms.get_property("checked").await?, serde_json::json!(true));
+    assert_eq!(terms.get_attribute("checked").await?, None, "the attribute should not follow the click");
+
+    println!("\n=== Testing the value property ===");
+    let name = page.locator("#name");
+    name.fill("typed").await?;
+    assert_eq!(name.get_property("value").await?, serde_json::json!("typed"));
+    assert_eq!(name.get_attribute("value").await?.as_deref(), Some("initial"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+//! Example demonstrating page.goto_data_html()
+//!
+//! Loads HTML far bigger than a `data:` URL can carry and checks inline
+//! scripts run.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Testing a 3MB document ===");
+    let filler = "<p>sparkle filler paragraph</p>".repeat(3 * 1024 * 1024 / 31);
+    let html = format!(
+        "<title>Large</title><body>{}<p id=\"last\">The end</p><script>document.title = 'Scripted';</script></body>",
+        filler
+    );
+    println!("HTML length: {} bytes", html.len());
+    assert!(html.len() > 3_000_000);
+    page.goto_data_html(&html).await?;
+
+    let body = page.evaluate("return document.body.innerText.length;").await?;
+    println!("Body text length: {}", body);
+    assert_eq!(page.locator("#last").text_content().await?, "The end");
+    assert_eq!(page.title().await?, "Scripted", "inline scripts should run");
+
+    println!("\n=== Testing the previous page is replaced ===");
+    assert_eq!(page.url().await?, "about:blank");
+    assert_eq!(page.locator("h1").count().await?, 0);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Example demonstrating the response returned by page.goto()
+//!
+//! Starts a local HTTP server with a page, a missing page and a redirect, and
+//! checks the status, URL and headers of each navigation.
+
+use sparkle::prelude::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Answer `/` with 200, `/old` with a redirect to `/` and anything else with 404
+async fn http_server() -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = vec![0; 4096];
+                let Ok(read) = stream.read(&mut request).await else { return };
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, extra, body) = match path {
+                    "/" => ("200 OK", "", "<h1>Home</h1>"),
+                    "/old" => ("301 Moved Permanently", "Location: /\r\n", ""),
+                    _ => ("404 Not Found", "", "<h1>Not found</h1>"),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: text/html\r\nX-Sparkle: test\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    extra,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(url)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let url = http_server().await?;
+
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    println!("\n=== Testing a successful navigation ===");
+    let response = page.goto(&format!("{}/", url), Default::default()).await?.expect("should have a response");
+    println!("{} {}", response.status(), response.url());
+    assert_eq!(response.status(), 200);
+    assert!(response.ok());
+    assert_eq!(response.headers().get("x-sparkle").map(String::as_str), Some("test"));
+
+    println!("\n=== Testing a 404 ===");
+    let response = page.goto(&format!("{}/missing", url), Default::default()).await?.expect("should have a response");
+    println!("{} {}", response.status(), response.url());
+    assert_eq!(response.status(), 404);
+    assert!(!response.ok());
+    // The body is still readable without any on_response handler
+    assert_eq!(response.text().await?, "<h1>Not found</h1>");
+
+    println!("\n=== Testing a redirect reports the final response ===");
+    let response = page.goto(&format!("{}/old", url), Default::default()).await?.expect("should have a response");
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.url(), format!("{}/", url));
+
+    println!("\n=== Testing about:blank has no response ===");
+    assert!(page.goto("about:blank", Default::default()).await?.is_none());
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
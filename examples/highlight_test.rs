@@ -0,0 +1,49 @@
+//! Example demonstrating Locator::highlight() and Page::hide_highlights()
+//!
+//! Highlights a visible button, checks the overlay covers its bounding box,
+//! then clears it.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+    page.evaluate(r#"
+        document.body.innerHTML =
+            '<button id="save" style="position: absolute; left: 40px; top: 60px; width: 120px; height: 30px">Save</button>';
+    "#).await?;
+
+    println!("\n=== Testing highlight ===");
+    page.locator("#save").highlight().await?;
+    let overlay = page.evaluate(r#"
+        const boxes = document.querySelectorAll('[data-sparkle-highlight]');
+        const target = document.getElementById('save').getBoundingClientRect();
+        const rect = boxes[0].getBoundingClientRect();
+        return {
+            count: boxes.length,
+            matches: rect.left === target.left && rect.top === target.top
+                && rect.width === target.width && rect.height === target.height,
+        };
+    "#).await?;
+    println!("Overlay: {}", overlay);
+    assert_eq!(overlay["count"], 1);
+    assert_eq!(overlay["matches"], true, "the overlay should cover the element");
+
+    println!("\n=== Testing highlights don't block clicks ===");
+    page.locator("#save").click(Default::default()).await?;
+
+    println!("\n=== Testing hide_highlights ===");
+    page.hide_highlights().await?;
+    assert_eq!(page.locator("[data-sparkle-highlight]").count().await?, 0);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Example demonstrating add_init_script() deduplication and remove_init_script()
+//!
+//! Registers the same counting script twice and checks it runs once per
+//! document, then removes it and checks it no longer runs.
+
+use sparkle::prelude::*;
+
+const COUNT_SCRIPT: &str = "window.__count = (window.__count || 0) + 1;";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    println!("\n=== Testing the same script is registered once ===");
+    let first = page.add_init_script(COUNT_SCRIPT).await?;
+    let second = page.add_init_script(COUNT_SCRIPT).await?;
+    assert_eq!(first, second, "re-adding a script should return its identifier");
+
+    page.goto("https://www.example.com", Default::default()).await?;
+    let count = page.evaluate("return window.__count;").await?;
+    println!("window.__count after navigation: {}", count);
+    assert_eq!(count, serde_json::json!(1));
+
+    println!("\n=== Testing the identifier survives reload ===");
+    page.reload(Default::default()).await?;
+    let count = page.evaluate("return window.__count;").await?;
+    assert_eq!(count, serde_json::json!(1));
+
+    println!("\n=== Testing remove_init_script ===");
+    page.remove_init_script(&first).await?;
+    page.reload(Default::default()).await?;
+    let count = page.evaluate("return window.__count === undefined;").await?;
+    assert_eq!(count, serde_json::json!(true), "removed script should not run");
+    assert!(page.remove_init_script(&first).await.is_err());
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -68,6 +68,7 @@ async fn main() -> Result<()> {
         .unwrap();
 
     println!("  Note: devtools=true automatically disables headless mode");
+    println!("  Configuration: devtools={:?}, timeout={:?}", options.devtools, options.timeout);
     println!("  (Skipping actual launch to avoid opening browser window)\n");
 
     // Example 3: Launch with slow motion for debugging
@@ -95,9 +96,10 @@ async fn main() -> Result<()> {
     // Example 4: Custom arguments and sandbox configuration
     println!("Example 4: Custom arguments");
     println!("---------------------------");
-    let mut custom_args = Vec::new();
-    custom_args.push("--window-size=1920,1080".to_string());
-    custom_args.push("--start-maximized".to_string());
+    let custom_args = vec![
+        "--window-size=1920,1080".to_string(),
+        "--start-maximized".to_string(),
+    ];
 
     let options = LaunchOptionsBuilder::default()
         .headless(true)
@@ -147,8 +149,10 @@ async fn main() -> Result<()> {
         .unwrap();
 
     println!("  (Skipping actual launch - proxy server doesn't exist)");
-    println!("  Configuration: http://proxy.example.com:8080");
-    println!("  Bypass: localhost,127.0.0.1\n");
+    if let Some(proxy) = &options.proxy {
+        println!("  Configuration: {}", proxy.server);
+        println!("  Bypass: {}\n", proxy.bypass.as_deref().unwrap_or("none"));
+    }
 
     // Example 7: Environment variables
     println!("Example 7: Environment variables");
@@ -173,8 +177,7 @@ async fn main() -> Result<()> {
     // Example 8: Ignore default arguments
     println!("Example 8: Ignore default arguments");
     println!("------------------------------------");
-    let mut ignore_args = Vec::new();
-    ignore_args.push("--disable-dev-shm-usage".to_string());
+    let ignore_args = vec!["--disable-dev-shm-usage".to_string()];
 
     let options = LaunchOptionsBuilder::default()
         .headless(true)
@@ -0,0 +1,55 @@
+//! Example demonstrating Locator::locator() chaining
+//!
+//! Clicks the button inside the second of two cards.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        window.clicked = null;
+        document.body.innerHTML = `
+            <button id="outside">Outside</button>
+            <div class="card"><h2>First</h2><button id="first-button">Buy</button></div>
+            <div class="card"><h2>Second</h2><button id="second-button">Buy</button></div>`;
+        document.querySelectorAll('button').forEach(b => b.addEventListener('click', () => { window.clicked = b.id; }));
+    "#).await?;
+
+    println!("\n=== Testing chained count ===");
+    let buttons = page.locator(".card").locator("button");
+    let count = buttons.count().await?;
+    println!("buttons inside cards: {}", count);
+    assert_eq!(count, 2, "the button outside the cards should not match");
+
+    println!("\n=== Testing nth on the parent ===");
+    page.locator(".card").nth(1).locator("button").click(Default::default()).await?;
+    let clicked = page.evaluate("return window.clicked;").await?;
+    println!("clicked: {}", clicked);
+    assert_eq!(clicked, serde_json::json!("second-button"));
+
+    println!("\n=== Testing first on the parent ===");
+    let id = page.locator(".card").first().locator("button").get_attribute("id").await?;
+    assert_eq!(id.as_deref(), Some("first-button"));
+
+    println!("\n=== Testing filter on the parent ===");
+    let id = page
+        .locator(".card")
+        .filter(FilterOptionsBuilder::default().has_text("Second").build().unwrap())
+        .locator("button")
+        .get_attribute("id")
+        .await?;
+    assert_eq!(id.as_deref(), Some("second-button"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
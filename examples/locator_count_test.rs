@@ -0,0 +1,56 @@
+//! Example benchmarking Locator::count() on a large list
+//!
+//! Counts 500 list items via the in-page fast path and compares it with the
+//! element-by-element path used by filtered locators.
+
+use sparkle::prelude::*;
+use std::time::Instant;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        const list = document.createElement('ul');
+        for (let i = 0; i < 500; i++) {
+            const item = document.createElement('li');
+            item.className = 'item';
+            item.textContent = 'Item ' + i;
+            list.appendChild(item);
+        }
+        document.body.appendChild(list);
+    "#).await?;
+
+    println!("\n=== Testing CSS fast path ===");
+    let start = Instant::now();
+    let count = page.locator("li.item").count().await?;
+    let fast = start.elapsed();
+    println!("count: {} in {:?}", count, fast);
+    assert_eq!(count, 500);
+
+    println!("\n=== Testing XPath fast path ===");
+    let count = page.locator("//li[@class='item']").count().await?;
+    assert_eq!(count, 500);
+
+    println!("\n=== Testing filtered locators still resolve elements ===");
+    let start = Instant::now();
+    let count = page
+        .locator("li.item")
+        .filter(FilterOptionsBuilder::default().has_text("Item 4").build().unwrap())
+        .count()
+        .await?;
+    println!("filtered count: {} in {:?}", count, start.elapsed());
+    // "Item 4", "Item 40".."Item 49" and "Item 400".."Item 499"
+    assert_eq!(count, 111);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
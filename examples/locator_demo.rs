@@ -81,7 +81,7 @@ async fn main() -> Result<()> {
     page.click("button[type='submit']", Default::default()).await?;
     
     // Wait for element to appear
-    page.wait_for_selector(".success-message").await?;
+    page.wait_for_selector(".success-message", Default::default()).await?;
     */
     
     println!("\n=== Cleanup ===");
@@ -0,0 +1,53 @@
+//! Example demonstrating locator.evaluate() and evaluate_all()
+//!
+//! The element is passed to the script as `arguments[0]`; evaluate_all passes
+//! the array of all matches.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(
+        r#"
+        document.body.innerHTML = `
+            <section id="feed" style="height: 50px; overflow: auto"><div style="height: 400px"></div></section>
+            <ul><li>One</li><li>Three</li><li>Fifteen</li></ul>
+        `;
+    "#,
+    )
+    .await?;
+
+    println!("\n=== Testing evaluate on a resolved element ===");
+    let tag = page.locator("#feed").evaluate("return arguments[0].tagName;").await?;
+    assert_eq!(tag.as_str(), Some("SECTION"));
+    let height: u64 = page
+        .locator("#feed")
+        .evaluate_as("return arguments[0].scrollHeight;")
+        .await?;
+    assert_eq!(height, 400);
+    let tag: String = page.locator("li").nth(1).evaluate_as("return arguments[0].textContent;").await?;
+    assert_eq!(tag, "Three");
+
+    println!("\n=== Testing evaluate_all across matches ===");
+    let lengths = page
+        .locator("li")
+        .evaluate_all("return arguments[0].map((el) => el.textContent.length);")
+        .await?;
+    assert_eq!(lengths, serde_json::json!([3, 5, 7]));
+
+    let none = page.locator(".missing").evaluate_all("return arguments[0].length;").await?;
+    assert_eq!(none, serde_json::json!(0));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
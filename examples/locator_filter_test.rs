@@ -0,0 +1,76 @@
+//! Example demonstrating Locator::filter() with has_text and has
+//!
+//! Builds a small list, narrows it down to the row containing "Active"
+//! and clicks the button inside only that row.
+
+use sparkle::prelude::*;
+use thirtyfour::By;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    // Add a list where every row has a button that records its clicks
+    page.evaluate(r#"
+        window.clicked = [];
+        const ul = document.createElement('ul');
+        ul.id = 'rows';
+        ['Pending', 'Active', 'Archived'].forEach((status, i) => {
+            const li = document.createElement('li');
+            li.textContent = `Item ${i + 1} - ${status} `;
+            if (status !== 'Archived') {
+                const button = document.createElement('button');
+                button.textContent = 'Open';
+                button.onclick = () => window.clicked.push(status);
+                li.appendChild(button);
+            }
+            ul.appendChild(li);
+        });
+        document.body.appendChild(ul);
+    "#).await?;
+
+    println!("\n=== Testing has_text ===");
+    let rows = page.locator("#rows li");
+    let active = rows.filter(FilterOptionsBuilder::default().has_text("Active").build().unwrap());
+    assert_eq!(active.count().await?, 1, "exactly one row should contain 'Active'");
+    println!("has_text('Active'): {}", active.text_content().await?);
+
+    let not_active = rows.filter(FilterOptionsBuilder::default().has_not_text("Active").build().unwrap());
+    assert_eq!(not_active.count().await?, 2, "two rows should not contain 'Active'");
+
+    println!("\n=== Testing has / has_not ===");
+    let with_button = rows.filter(
+        FilterOptionsBuilder::default()
+            .has(page.locator("button"))
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(with_button.count().await?, 2, "two rows should have a button");
+
+    let without_button = rows.filter(
+        FilterOptionsBuilder::default()
+            .has_not(page.locator("button"))
+            .build()
+            .unwrap(),
+    );
+    assert_eq!(without_button.count().await?, 1, "one row should have no button");
+
+    println!("\n=== Clicking the button inside the Active row ===");
+    let row = active.element().await?;
+    row.find(By::Css("button")).await?.click().await?;
+
+    let clicked = page.evaluate("return window.clicked;").await?;
+    println!("clicked: {}", clicked);
+    assert_eq!(clicked, serde_json::json!(["Active"]), "only the Active row's button should be clicked");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Example demonstrating locator.or() and locator.and()
+//!
+//! Finds whichever of two buttons is on the page, combines XPath with CSS,
+//! and narrows buttons down to the one that also has a given title.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto_data_html(r#"
+        <button id="agree">Agree</button>
+        <button class="primary" title="Save">Save</button>
+        <button class="primary" title="Publish">Publish</button>
+        <a class="primary" title="Save">Save link</a>
+    "#).await?;
+
+    println!("\n=== Testing or() with CSS selectors ===");
+    let consent = page.locator("#accept").or(page.locator("#agree"));
+    assert_eq!(consent.count().await?, 1);
+    assert_eq!(consent.text_content().await?, "Agree");
+
+    println!("\n=== Testing or() with an XPath selector ===");
+    let either = page.locator("//button[@id='accept']").or(page.locator("#agree"));
+    assert_eq!(either.first().text_content().await?, "Agree");
+
+    println!("\n=== Testing and() ===");
+    let save = page.locator("button").and(page.locator("[title='Save']"));
+    assert_eq!(save.count().await?, 1);
+    assert_eq!(save.text_content().await?, "Save");
+
+    println!("\n=== Testing nth() on a combined locator ===");
+    let primary_buttons = page.locator("button").and(page.locator(".primary"));
+    assert_eq!(primary_buttons.nth(1).text_content().await?, "Publish");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
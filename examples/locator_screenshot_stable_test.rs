@@ -0,0 +1,100 @@
+//! Example demonstrating stable element screenshots
+//!
+//! Screenshots an animated component twice with animations disabled and
+//! checks the captures are identical, then masks part of it and saves a
+//! JPEG of one element to a file.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(
+        r#"
+        document.body.innerHTML = `
+            <style>
+                @keyframes spin { to { transform: rotate(360deg); } }
+                @keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+                #card { width: 200px; height: 120px; padding: 20px; background: #eee; }
+                #spinner { width: 40px; height: 40px; background: linear-gradient(red, blue); animation: spin 0.7s linear infinite; }
+                #banner { height: 20px; background: green; animation: fade 30s forwards; }
+            </style>
+            <div id="card"><div id="spinner"></div><div id="banner"></div></div>
+        `;
+    "#,
+    )
+    .await?;
+
+    let stable = ScreenshotOptionsBuilder::default()
+        .animations(ScreenshotAnimations::Disabled)
+        .build()
+        .unwrap();
+
+    println!("\n=== Testing animations: Disabled gives identical captures ===");
+    let first = page.locator("#card").screenshot_with_options(stable.clone()).await?;
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    let second = page.locator("#card").screenshot_with_options(stable.clone()).await?;
+    assert!(first.starts_with(&[0x89, b'P', b'N', b'G']));
+    assert_eq!(first, second, "captures with animations disabled should match");
+
+    let image = image::load_from_memory(&first).unwrap();
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    let scale = page.evaluate("return window.devicePixelRatio;").await?.as_f64().unwrap();
+    assert_eq!((width / scale).round(), 240.0);
+    assert_eq!((height / scale).round(), 160.0);
+
+    println!("\n=== Testing the animations resume afterwards ===");
+    let running = page
+        .evaluate("return document.getAnimations().filter((a) => a.playState === 'running').length;")
+        .await?;
+    assert_eq!(running, serde_json::json!(1), "the infinite spinner should be playing again");
+
+    println!("\n=== Testing mask ===");
+    let masked = page
+        .locator("#card")
+        .screenshot_with_options(
+            ScreenshotOptionsBuilder::default()
+                .animations(ScreenshotAnimations::Disabled)
+                .mask(vec!["#spinner".to_string()])
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let pixel = image::load_from_memory(&masked)
+        .unwrap()
+        .to_rgba8()
+        .get_pixel((40.0 * scale) as u32, (40.0 * scale) as u32)
+        .0;
+    assert_eq!(pixel, [255, 0, 255, 255], "the spinner should be covered by the mask color");
+    let overlays = page.evaluate("return document.documentElement.children.length;").await?;
+    assert_eq!(overlays, serde_json::json!(2), "mask overlays should be removed");
+
+    println!("\n=== Testing a JPEG written to path ===");
+    let path = std::env::temp_dir().join("sparkle-locator-screenshot-test.jpg");
+    page.locator("#spinner")
+        .screenshot_with_options(
+            ScreenshotOptionsBuilder::default()
+                .r#type(ScreenshotType::Jpeg)
+                .quality(80u8)
+                .animations(ScreenshotAnimations::Disabled)
+                .path(path.clone())
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let jpeg = std::fs::read(&path)?;
+    assert!(jpeg.starts_with(&[0xFF, 0xD8, 0xFF]), "file should have the JPEG signature");
+    std::fs::remove_file(&path)?;
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Example demonstrating locator.is_editable(), is_hidden() and is_disabled()
+//!
+//! Checks a readonly input, a disabled button, a field inside a disabled
+//! fieldset, a hidden element and a selector that matches nothing.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto_data_html(r#"
+        <input id="editable">
+        <input id="readonly" readonly value="fixed">
+        <button id="disabled" disabled>Disabled</button>
+        <fieldset disabled><input id="in-fieldset"></fieldset>
+        <div id="editor" contenteditable="true">Edit me</div>
+        <div id="hidden" style="display: none">Hidden</div>
+    "#).await?;
+
+    println!("\n=== Testing is_editable ===");
+    assert!(page.locator("#editable").is_editable().await?);
+    assert!(!page.locator("#readonly").is_editable().await?, "readonly input should not be editable");
+    assert!(!page.locator("#in-fieldset").is_editable().await?, "input in a disabled fieldset should not be editable");
+    assert!(page.locator("#editor").is_editable().await?, "contenteditable should be editable");
+    assert!(!page.locator("#disabled").is_editable().await?, "buttons are not editable");
+
+    println!("\n=== Testing is_disabled ===");
+    assert!(page.locator("#disabled").is_disabled().await?);
+    assert!(!page.locator("#readonly").is_disabled().await?, "readonly is not disabled");
+
+    println!("\n=== Testing is_hidden ===");
+    assert!(page.locator("#hidden").is_hidden().await?);
+    assert!(!page.locator("#editable").is_hidden().await?);
+    assert!(page.locator("#does-not-exist").is_hidden().await?, "a missing element counts as hidden");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+//! Example demonstrating LaunchOptions::max_concurrent_pages
+//!
+//! With a limit of 2, a third `new_page` must wait until a page closes.
+
+use sparkle::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(
+            LaunchOptionsBuilder::default()
+                .headless(true)
+                .max_concurrent_pages(2usize)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+
+    println!("\n=== Opening two pages ===");
+    let first = browser.new_page().await?;
+    let _second = browser.new_page().await?;
+
+    println!("\n=== Testing the third page blocks ===");
+    let blocked = tokio::time::timeout(Duration::from_millis(1500), browser.new_page()).await;
+    assert!(blocked.is_err(), "third new_page should wait while two pages are open");
+    println!("third new_page is waiting for a free slot");
+
+    println!("\n=== Testing closing a page frees a slot ===");
+    let start = std::time::Instant::now();
+    let close_delay = Duration::from_millis(800);
+    let (third, closed_at) = tokio::join!(browser.new_page(), async {
+        tokio::time::sleep(close_delay).await;
+        first.close().await?;
+        Ok::<_, Error>(start.elapsed())
+    });
+    let third = third?;
+    let closed_at = closed_at?;
+    println!("first page closed after {:?}, third page opened after {:?}", closed_at, start.elapsed());
+    assert!(start.elapsed() >= close_delay, "third new_page should only finish after a page closed");
+    third.goto("https://www.example.com", Default::default()).await?;
+    println!("third page opened: {}", third.url().await?);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -162,11 +162,7 @@ async fn main() -> Result<()> {
 </html>
     "#;
 
-    page.goto(
-        &format!("data:text/html,{}", urlencoding::encode(html_content)),
-        Default::default(),
-    )
-    .await?;
+    page.goto_data_html(html_content).await?;
 
     println!("✓ Test page loaded\n");
     tokio::time::sleep(Duration::from_millis(1000)).await;
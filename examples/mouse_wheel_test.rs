@@ -0,0 +1,52 @@
+//! Example demonstrating Mouse::wheel() and Page::mouse_wheel()
+//!
+//! Builds a tall page and checks that wheel events scroll it.
+
+use sparkle::prelude::*;
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    // Make the page tall enough to scroll
+    page.evaluate(r#"
+        const spacer = document.createElement('div');
+        spacer.style.height = '5000px';
+        document.body.appendChild(spacer);
+        window.scrollTo(0, 0);
+    "#).await?;
+
+    let before = page.evaluate("return window.scrollY;").await?.as_f64().unwrap_or(0.0);
+    println!("scrollY before: {}", before);
+
+    println!("\n=== Testing Mouse::wheel ===");
+    let mouse = page.mouse();
+    mouse.move_to(100, 100, Default::default()).await?;
+    mouse.wheel(0.0, 600.0).await?;
+    // Wheel scrolling is asynchronous in the compositor
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let after_wheel = page.evaluate("return window.scrollY;").await?.as_f64().unwrap_or(0.0);
+    println!("scrollY after wheel: {}", after_wheel);
+    assert!(after_wheel > before, "window.scrollY should increase after a wheel event");
+
+    println!("\n=== Testing Page::mouse_wheel ===");
+    page.mouse_wheel(0.0, 600.0).await?;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let after_page_wheel = page.evaluate("return window.scrollY;").await?.as_f64().unwrap_or(0.0);
+    println!("scrollY after page.mouse_wheel: {}", after_page_wheel);
+    assert!(after_page_wheel > after_wheel, "window.scrollY should keep increasing");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
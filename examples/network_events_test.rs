@@ -0,0 +1,82 @@
+//! Example demonstrating page.on_request() and page.on_response()
+//!
+//! Navigates with both handlers registered and checks the document's request
+//! and response are reported, including the response body.
+
+use std::sync::{Arc, Mutex};
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    let requests = Arc::new(Mutex::new(Vec::<Request>::new()));
+    let responses = Arc::new(Mutex::new(Vec::<Response>::new()));
+    {
+        let requests = Arc::clone(&requests);
+        page.on_request(move |request| requests.lock().unwrap().push(request)).await?;
+        let responses = Arc::clone(&responses);
+        page.on_response(move |response| responses.lock().unwrap().push(response)).await?;
+    }
+
+    println!("\n=== Testing a navigation reports the document ===");
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    let request = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|request| request.resource_type() == "document")
+        .cloned()
+        .expect("the document request should be reported");
+    println!("request: {} {}", request.method(), request.url());
+    assert_eq!(request.method(), "GET");
+    assert!(request.url().starts_with("https://www.example.com"));
+
+    let response = responses
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|response| response.request().resource_type() == "document")
+        .cloned()
+        .expect("the document response should be reported");
+    println!("response: {} {}", response.status(), response.url());
+    assert_eq!(response.url(), request.url());
+    assert!(response.ok());
+    assert!(response.headers().get("content-type").is_some_and(|value| value.contains("text/html")));
+
+    println!("\n=== Testing Response::body ===");
+    let body = response.text().await?;
+    assert!(body.contains("Example Domain"));
+
+    println!("\n=== Testing fetches are reported ===");
+    page.evaluate("return fetch('/?sparkle=1', { method: 'POST', body: 'ping' }).then((r) => r.status);")
+        .await?;
+    let fetched = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|request| request.url().contains("sparkle=1"))
+        .cloned()
+        .expect("the fetch should be reported");
+    assert_eq!(fetched.method(), "POST");
+    assert_eq!(fetched.resource_type(), "fetch");
+    assert_eq!(fetched.post_data(), Some("ping"));
+
+    println!("\n=== Testing handlers are removed on close ===");
+    let count = requests.lock().unwrap().len();
+    page.close().await?;
+    let page = browser.new_page().await?;
+    page.goto("https://www.example.com", Default::default()).await?;
+    assert_eq!(requests.lock().unwrap().len(), count);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -82,6 +82,16 @@ async fn main() -> Result<()> {
     println!("last(): {}", last_text);
     assert_eq!(last_text.trim(), "Item 5", "last() should return last item");
     
+    println!("\n=== Testing negative nth() ===");
+    
+    // nth(-1) is last(), nth(-2) the second-to-last item
+    let minus_one = page.locator(".test-item").nth(-1).text_content().await?;
+    println!("nth(-1): {}", minus_one);
+    assert_eq!(minus_one, last_text, "nth(-1) should equal last()");
+    let minus_two = page.locator(".test-item").nth(-2).text_content().await?;
+    println!("nth(-2): {}", minus_two);
+    assert_eq!(minus_two.trim(), "Item 4", "nth(-2) should return the second-to-last item");
+    
     println!("\n=== Testing count() ===");
     
     // Test count() - should return total number of elements
@@ -123,6 +133,7 @@ async fn main() -> Result<()> {
     println!("✓ nth(0), nth(1), nth(2), nth(4) work correctly");
     println!("✓ first() returns first element");
     println!("✓ last() returns last element");
+    println!("✓ nth(-1), nth(-2) count from the end");
     println!("✓ count() returns correct count");
     println!("✓ Interactions (click, is_visible) work with nth()");
     println!("✓ Edge cases handled appropriately");
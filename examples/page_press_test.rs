@@ -0,0 +1,52 @@
+//! Example demonstrating Page::press() and Locator::press()
+//!
+//! Opens a command palette with a global Control+K shortcut and submits a
+//! focused input with Enter.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <div id="palette" hidden>Command palette</div>
+            <input id="search">`;
+        window.submitted = null;
+        document.addEventListener('keydown', (e) => {
+            if (e.ctrlKey && e.key.toLowerCase() === 'k') {
+                e.preventDefault();
+                document.getElementById('palette').hidden = false;
+            }
+        });
+        document.getElementById('search').addEventListener('keydown', (e) => {
+            if (e.key === 'Enter') window.submitted = e.target.value;
+        });
+    "#).await?;
+
+    println!("\n=== Testing Page::press with a combo ===");
+    assert!(!page.locator("#palette").is_visible().await?);
+    page.press("Control+K").await?;
+    let opened = page.locator("#palette").is_visible().await?;
+    println!("palette visible: {}", opened);
+    assert!(opened, "Control+K should open the command palette");
+
+    println!("\n=== Testing Locator::press ===");
+    page.locator("#search").fill("sparkle").await?;
+    page.locator("#search").press("Enter").await?;
+    let submitted = page.evaluate("return window.submitted;").await?;
+    println!("submitted: {}", submitted);
+    assert_eq!(submitted, serde_json::json!("sparkle"));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
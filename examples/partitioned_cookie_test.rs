@@ -0,0 +1,59 @@
+//! Example checking that partitioned cookies survive a storage state round trip
+//!
+//! Restores a CHIPS cookie with a priority into a new context and reads the
+//! storage state back.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    let state = StorageState {
+        cookies: vec![CookieState {
+            name: "embed_session".to_string(),
+            value: "xyz".to_string(),
+            domain: "www.example.com".to_string(),
+            path: "/".to_string(),
+            expires: -1.0,
+            http_only: false,
+            secure: true,
+            same_site: SameSite::None,
+            priority: Some(CookiePriority::High),
+            partition_key: Some("https://example.org".to_string()),
+            same_party: None,
+            url: None,
+        }],
+        origins: vec![],
+    };
+
+    println!("\n=== Restoring a partitioned cookie ===");
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .storage_state(StorageStateSource::State(state))
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let _page = context.new_page().await?;
+
+    println!("\n=== Reading the storage state back ===");
+    let saved = context.storage_state(None::<&str>).await?;
+    let cookie = saved
+        .cookies
+        .iter()
+        .find(|c| c.name == "embed_session")
+        .expect("partitioned cookie should be present");
+    println!("cookie: {:?}", cookie);
+    assert_eq!(cookie.partition_key.as_deref(), Some("https://example.org"));
+    assert_eq!(cookie.priority, Some(CookiePriority::High));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
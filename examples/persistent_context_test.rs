@@ -0,0 +1,46 @@
+//! Example demonstrating chromium.launch_persistent_context()
+//!
+//! Sets a cookie in a fresh profile directory, relaunches against the same
+//! directory and checks the cookie was kept on disk.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let user_data_dir = std::env::temp_dir().join(format!("sparkle-persistent-test-{}", std::process::id()));
+    let options = || LaunchOptionsBuilder::default().headless(true).build().unwrap();
+
+    println!("\n=== Testing the context starts with a page ===");
+    let context = playwright
+        .chromium()
+        .launch_persistent_context(user_data_dir.clone(), options())
+        .await?;
+    let pages = context.pages().await;
+    assert_eq!(pages.len(), 1, "persistent context should have a page open");
+
+    println!("\n=== Setting a cookie ===");
+    let page = &pages[0];
+    page.goto("https://www.example.com", Default::default()).await?;
+    page.evaluate("document.cookie = 'sparkle_persist=1; max-age=3600; path=/';").await?;
+    context.close().await?;
+
+    println!("\n=== Testing the cookie survives a relaunch ===");
+    let context = playwright
+        .chromium()
+        .launch_persistent_context(user_data_dir.clone(), options())
+        .await?;
+    let page = context.pages().await.remove(0);
+    page.goto("https://www.example.com", Default::default()).await?;
+    let cookie = page.evaluate("return document.cookie;").await?;
+    println!("document.cookie: {}", cookie);
+    assert!(
+        cookie.as_str().unwrap_or_default().contains("sparkle_persist=1"),
+        "cookie should be read back from the profile"
+    );
+    context.close().await?;
+
+    std::fs::remove_dir_all(&user_data_dir)?;
+    println!("\n=== All tests passed! ===");
+    Ok(())
+}
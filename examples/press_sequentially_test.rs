@@ -0,0 +1,54 @@
+//! Example demonstrating locator.press_sequentially()
+//!
+//! Types into an input that counts its `keydown` events and checks every
+//! character produced one.
+
+use std::time::Duration;
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+    page.goto_data_html(
+        r#"<input id="name">
+        <script>
+            window.keydowns = 0;
+            window.inputs = 0;
+            const input = document.getElementById('name');
+            input.addEventListener('keydown', () => window.keydowns++);
+            input.addEventListener('input', () => window.inputs++);
+        </script>"#,
+    )
+    .await?;
+    let input = page.locator("#name");
+
+    println!("\n=== Testing a keydown per character ===");
+    let text = "Sparkle 2";
+    input
+        .press_sequentially(
+            text,
+            TypeOptionsBuilder::default().delay(Duration::from_millis(20)).build().unwrap(),
+        )
+        .await?;
+    let keydowns = page.evaluate("return window.keydowns;").await?;
+    let inputs = page.evaluate("return window.inputs;").await?;
+    println!("keydown events: {}, input events: {}", keydowns, inputs);
+    assert_eq!(keydowns, serde_json::json!(text.chars().count()));
+    assert_eq!(inputs, serde_json::json!(text.chars().count()));
+    assert_eq!(input.evaluate("return arguments[0].value;").await?, serde_json::json!(text));
+
+    println!("\n=== Testing existing text is kept ===");
+    input.press_sequentially("!\n", Default::default()).await?;
+    assert_eq!(input.evaluate("return arguments[0].value;").await?, serde_json::json!("Sparkle 2!"));
+    assert_eq!(page.evaluate("return window.keydowns;").await?, serde_json::json!(text.chars().count() + 2));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
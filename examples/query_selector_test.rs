@@ -0,0 +1,67 @@
+//! Example demonstrating page.query_selector() and ElementHandle
+//!
+//! Handles are snapshots of the element found at query time: a missing
+//! selector gives `None` right away instead of waiting.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(
+        r#"
+        document.body.innerHTML = `
+            <ul id="items"><li class="item">One</li><li class="item">Two</li><li class="item">Three</li></ul>
+            <input id="name">
+            <button id="go" onclick="this.dataset.clicked = 'yes'">Go</button>
+        `;
+    "#,
+    )
+    .await?;
+
+    println!("\n=== Testing a missing selector ===");
+    let start = std::time::Instant::now();
+    assert!(page.query_selector("#missing").await?.is_none());
+    assert!(start.elapsed() < std::time::Duration::from_secs(2), "query_selector should not wait");
+    assert!(page.query_selector_all(".missing").await?.is_empty());
+
+    println!("\n=== Testing a matching selector ===");
+    let list = page.query_selector("#items").await?.expect("#items should exist");
+    assert_eq!(list.get_attribute("id").await?.as_deref(), Some("items"));
+    assert!(list.inner_html().await?.contains("<li class=\"item\">Two</li>"));
+    let (_, _, width, height) = list.bounding_box().await?.unwrap();
+    assert!(width > 0.0 && height > 0.0);
+
+    let items = page.query_selector_all(".item").await?;
+    let mut texts = Vec::new();
+    for item in &items {
+        texts.push(item.text_content().await?);
+    }
+    assert_eq!(texts, vec!["One", "Two", "Three"]);
+
+    println!("\n=== Testing actions through handles ===");
+    let input = page.query_selector("#name").await?.unwrap();
+    input.fill("Sparkle").await?;
+    let value = page.evaluate("return document.getElementById('name').value;").await?;
+    assert_eq!(value.as_str(), Some("Sparkle"));
+
+    let button = page.query_selector("#go").await?.unwrap();
+    button.click(Default::default()).await?;
+    assert_eq!(button.get_attribute("data-clicked").await?.as_deref(), Some("yes"));
+
+    println!("\n=== Testing that handles are snapshots ===");
+    page.evaluate("document.getElementById('go').remove();").await?;
+    assert!(button.click(Default::default()).await.is_err(), "a detached handle should not be re-resolved");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
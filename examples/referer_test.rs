@@ -0,0 +1,70 @@
+//! Example demonstrating NavigationOptions::referer in page.goto()
+//!
+//! Starts a local HTTP server that echoes the Referer header, navigates with a
+//! referer and checks it arrived, then navigates again without one and checks
+//! nothing was sent.
+
+use sparkle::prelude::*;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Answer every request with its Referer header, or `none`
+async fn echo_referer_server() -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = vec![0; 4096];
+                let Ok(read) = stream.read(&mut request).await else { return };
+                let request = String::from_utf8_lossy(&request[..read]);
+                let referer = request
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("referer").then(|| value.trim().to_string())
+                    })
+                    .unwrap_or_else(|| "none".to_string());
+                let body = format!("<pre id=\"referer\">{}</pre>", referer);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(url)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let url = echo_referer_server().await?;
+
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    println!("\n=== Testing goto with a referer ===");
+    let options = NavigationOptionsBuilder::default()
+        .referer("https://search.example.com/")
+        .build()
+        .unwrap();
+    page.goto(&format!("{}/landing", url), options).await?;
+    let referer = page.locator("#referer").text_content().await?;
+    println!("Referer: {}", referer);
+    assert_eq!(referer, "https://search.example.com/");
+
+    println!("\n=== Testing the referer does not leak into the next navigation ===");
+    page.goto(&format!("{}/next", url), Default::default()).await?;
+    let referer = page.locator("#referer").text_content().await?;
+    println!("Referer: {}", referer);
+    assert_eq!(referer, "none");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Example demonstrating Page::reload()
+//!
+//! Verifies that localStorage and init scripts survive a reload.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.add_init_script("window.__initScriptRan = true;").await?;
+    page.evaluate("localStorage.setItem('token', 'abc123');").await?;
+
+    println!("\n=== Reloading ===");
+    page.reload(Default::default()).await?;
+
+    let storage = page.origin_storage().await?;
+    let token = storage
+        .local_storage
+        .iter()
+        .find(|item| item.name == "token")
+        .map(|item| item.value.clone());
+    println!("localStorage token after reload: {:?}", token);
+    assert_eq!(token.as_deref(), Some("abc123"), "localStorage should persist across reload");
+
+    let init_ran = page.evaluate("return window.__initScriptRan === true;").await?;
+    println!("init script global present: {}", init_ran);
+    assert_eq!(init_ran, serde_json::json!(true), "init script should run after reload");
+
+    println!("\n=== Reloading twice ===");
+    page.reload(Default::default()).await?;
+    let init_ran = page.evaluate("return window.__initScriptRan === true;").await?;
+    assert_eq!(init_ran, serde_json::json!(true), "init script should still run after a second reload");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Example demonstrating LaunchOptions::reuse_driver
+//!
+//! Launches three browsers on one shared ChromeDriver and checks that each
+//! still gets its own isolated session.
+
+use sparkle::driver::ChromeDriverProcess;
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let options = LaunchOptionsBuilder::default()
+        .headless(true)
+        .reuse_driver(true)
+        .build()
+        .unwrap();
+
+    println!("\n=== Launching three browsers ===");
+    let mut browsers = Vec::new();
+    let mut driver_urls = Vec::new();
+    for _ in 0..3 {
+        browsers.push(playwright.chromium().launch(options.clone()).await?);
+        driver_urls.push(ChromeDriverProcess::shared_url().await);
+    }
+    println!("driver URLs: {:?}", driver_urls);
+    assert!(driver_urls[0].is_some(), "a shared driver should be running");
+    assert!(driver_urls.iter().all(|url| url == &driver_urls[0]), "all launches should use one driver");
+
+    println!("\n=== Testing session isolation ===");
+    let first = browsers[0].new_page().await?;
+    let second = browsers[1].new_page().await?;
+    first.goto("https://www.example.com", Default::default()).await?;
+    second.goto("https://www.example.com/?other", Default::default()).await?;
+    assert!(!first.url().await?.contains("other"), "sessions should not share tabs");
+
+    println!("\n=== All tests passed! ===");
+    for browser in browsers {
+        browser.close().await?;
+    }
+    assert_eq!(
+        ChromeDriverProcess::shared_url().await,
+        None,
+        "the shared driver should stop with the last browser"
+    );
+    Ok(())
+}
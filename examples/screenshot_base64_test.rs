@@ -0,0 +1,34 @@
+//! Example demonstrating page.screenshot_as_base64()
+//!
+//! Checks that the returned string decodes to a PNG and matches the image
+//! written to `path`.
+
+use base64::prelude::*;
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Testing screenshot_as_base64 ===");
+    let path = std::env::temp_dir().join("sparkle-screenshot-base64-test.png");
+    let base64 = page
+        .screenshot_as_base64(ScreenshotOptionsBuilder::default().path(path.clone()).build().unwrap())
+        .await?;
+    println!("Base64 length: {}", base64.len());
+    let png = BASE64_STANDARD.decode(&base64).expect("screenshot should be valid base64");
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"), "decoded bytes should have the PNG signature");
+    assert_eq!(std::fs::read(&path)?, png, "path should receive the decoded image");
+    std::fs::remove_file(&path)?;
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Example demonstrating screenshot clip regions
+//!
+//! Captures a 100x100 region at (10, 10) and checks the decoded PNG size.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    let clip = Clip { x: 10.0, y: 10.0, width: 100.0, height: 100.0 };
+
+    println!("\n=== Capturing a clipped screenshot ===");
+    let png = page
+        .screenshot_with_options(ScreenshotOptionsBuilder::default().clip(clip).build().unwrap())
+        .await?;
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .expect("screenshot should be a valid PNG");
+    println!("clip size: {}x{}", image.width(), image.height());
+    assert_eq!((image.width(), image.height()), (100, 100));
+
+    println!("\n=== clip and full_page are mutually exclusive ===");
+    let err = page
+        .screenshot_with_options(
+            ScreenshotOptionsBuilder::default()
+                .clip(clip)
+                .full_page(true)
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(err, Error::InvalidArgument(_)));
+
+    println!("\n=== Capturing a JPEG to a file ===");
+    let path = std::env::temp_dir().join("sparkle_clip_test.jpg");
+    page.screenshot_with_options(
+        ScreenshotOptionsBuilder::default()
+            .clip(clip)
+            .r#type(ScreenshotType::Jpeg)
+            .quality(80)
+            .path(path.clone())
+            .build()
+            .unwrap(),
+    )
+    .await?;
+    let jpeg = std::fs::read(&path)?;
+    assert_eq!(&jpeg[..3], &[0xFF, 0xD8, 0xFF], "file should be a JPEG");
+    std::fs::remove_file(&path)?;
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
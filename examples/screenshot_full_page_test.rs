@@ -0,0 +1,39 @@
+//! Example demonstrating full-page screenshots
+//!
+//! Captures a page taller than the viewport and checks the viewport size is
+//! the same afterwards, so later actions see the original layout.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto_data_html(r#"<body style="margin: 0"><div style="height: 3000px; background: linear-gradient(red, blue)"></div></body>"#)
+        .await?;
+    let viewport = page.evaluate("return [window.innerWidth, window.innerHeight];").await?;
+    println!("viewport before: {}", viewport);
+
+    println!("\n=== Capturing a full-page screenshot ===");
+    let png = page
+        .screenshot_with_options(ScreenshotOptionsBuilder::default().full_page(true).build().unwrap())
+        .await?;
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .expect("screenshot should be a valid PNG");
+    println!("image size: {}x{}", image.width(), image.height());
+    assert!(image.height() >= 3000, "full-page capture should cover the whole document");
+
+    println!("\n=== Testing the viewport is unchanged ===");
+    let after = page.evaluate("return [window.innerWidth, window.innerHeight];").await?;
+    println!("viewport after: {}", after);
+    assert_eq!(after, viewport, "full-page screenshot should restore the viewport");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Example demonstrating page.screenshot_image()
+//!
+//! Checks that the decoded screenshot reports the viewport size times the
+//! device scale factor, and that save() writes the captured bytes.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    let viewport = page
+        .evaluate("return [window.innerWidth, window.innerHeight, window.devicePixelRatio];")
+        .await?;
+    let (width, height, scale) = (
+        viewport[0].as_f64().unwrap(),
+        viewport[1].as_f64().unwrap(),
+        viewport[2].as_f64().unwrap(),
+    );
+    println!("Viewport: {}x{} @ {}x", width, height, scale);
+
+    println!("\n=== Testing screenshot dimensions ===");
+    let screenshot = page.screenshot_image(Default::default()).await?;
+    println!("Screenshot: {}x{}", screenshot.width(), screenshot.height());
+    assert_eq!(screenshot.width(), (width * scale).round() as u32);
+    assert_eq!(screenshot.height(), (height * scale).round() as u32);
+    assert_eq!(screenshot.image().width(), screenshot.width());
+
+    println!("\n=== Testing JPEG screenshots ===");
+    let jpeg = page
+        .screenshot_image(ScreenshotOptionsBuilder::default().r#type(ScreenshotType::Jpeg).build().unwrap())
+        .await?;
+    assert!(jpeg.bytes().starts_with(&[0xFF, 0xD8]), "JPEG bytes should start with SOI");
+    assert_eq!((jpeg.width(), jpeg.height()), (screenshot.width(), screenshot.height()));
+
+    println!("\n=== Testing save() ===");
+    let path = std::env::temp_dir().join("sparkle-screenshot-image-test.png");
+    screenshot.save(&path)?;
+    assert_eq!(std::fs::read(&path)?, screenshot.bytes());
+    std::fs::remove_file(&path)?;
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Example demonstrating Locator::select_text()
+//!
+//! Selects the contents of an input and of a paragraph.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <input id="code" value="SPARKLE-2024">
+            <p id="quote">Copy this sentence.</p>`;
+    "#).await?;
+
+    println!("\n=== Selecting an input's text ===");
+    page.locator("#code").select_text().await?;
+    let range = page
+        .evaluate("const el = document.getElementById('code'); return [el.selectionStart, el.selectionEnd, document.activeElement === el];")
+        .await?;
+    println!("selection range: {}", range);
+    assert_eq!(range, serde_json::json!([0, 12, true]));
+
+    println!("\n=== Selecting a paragraph's text ===");
+    page.locator("#quote").select_text().await?;
+    let selected = page.evaluate("return window.getSelection().toString();").await?;
+    println!("selected: {}", selected);
+    assert_eq!(selected, serde_json::json!("Copy this sentence."));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
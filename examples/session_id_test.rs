@@ -0,0 +1,30 @@
+//! Example demonstrating browser.session_id() and refresh_session_capabilities()
+//!
+//! Launches a browser, checks the WebDriver session id is reported and that
+//! the version is still available after the cached capabilities are dropped.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    println!("\n=== Testing session_id() ===");
+    let session_id = browser.session_id().await?;
+    println!("Session id: {}", session_id);
+    assert!(!session_id.is_empty(), "the session id should not be empty");
+    assert_eq!(browser.session_id().await?, session_id, "the id should be stable");
+
+    println!("\n=== Testing refresh_session_capabilities() ===");
+    let version = browser.version().await?;
+    browser.refresh_session_capabilities().await;
+    assert_eq!(browser.version().await?, version);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Example demonstrating shadow-piercing selectors
+//!
+//! Defines a custom element whose button lives in its shadow root and clicks
+//! it with both the `>>>` combinator and the `pierce=` prefix.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+    page.evaluate(r#"
+        customElements.define('counter-button', class extends HTMLElement {
+            constructor() {
+                super();
+                const root = this.attachShadow({ mode: 'open' });
+                root.innerHTML = '<button class="increment">Clicked 0 times</button>';
+                let clicks = 0;
+                root.querySelector('button').addEventListener('click', (event) => {
+                    event.target.textContent = `Clicked ${++clicks} times`;
+                });
+            }
+        });
+        document.body.innerHTML = '<counter-button id="first"></counter-button><counter-button></counter-button>';
+    "#).await?;
+
+    println!("\n=== Testing plain CSS does not reach the shadow root ===");
+    assert_eq!(page.locator("button.increment").count().await?, 0);
+
+    println!("\n=== Testing the >>> combinator ===");
+    let button = page.locator("counter-button#first >>> button.increment");
+    button.click(Default::default()).await?;
+    let text = button.text_content().await?;
+    println!("button text: {}", text);
+    assert_eq!(text, "Clicked 1 times");
+
+    println!("\n=== Testing the pierce= prefix ===");
+    let buttons = page.locator("pierce=button.increment");
+    assert_eq!(buttons.count().await?, 2);
+    buttons.nth(1).click(Default::default()).await?;
+    assert_eq!(buttons.nth(1).text_content().await?, "Clicked 1 times");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -39,6 +39,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         locale: Some("en-US".to_string()),
         timezone_id: Some("America/New_York".to_string()),
         geolocation: Some((40.7128, -74.0060, 10.0)), // New York City coordinates
+        auto_timezone_from_ip: false, // Timezone and locale are set explicitly above
     };
 
     let options = LaunchOptionsBuilder::default()
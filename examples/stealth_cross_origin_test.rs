@@ -0,0 +1,33 @@
+//! Example checking that stealth patches and init scripts survive cross-origin navigation
+//!
+//! Navigates between origins and checks `navigator.webdriver` and an init
+//! script marker on every document.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+    page.add_init_script("window.__sparkleInit = true;").await?;
+
+    for url in ["https://www.example.com", "https://www.wikipedia.org", "https://www.example.com"] {
+        println!("\n=== Testing {} ===", url);
+        page.goto(url, Default::default()).await?;
+
+        let webdriver = page.evaluate("return navigator.webdriver;").await?;
+        println!("navigator.webdriver: {}", webdriver);
+        assert_eq!(webdriver, serde_json::json!(false), "stealth patch should be active on {}", url);
+
+        let init = page.evaluate("return window.__sparkleInit === true;").await?;
+        assert_eq!(init, serde_json::json!(true), "init script should run on {}", url);
+    }
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
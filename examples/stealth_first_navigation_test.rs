@@ -0,0 +1,45 @@
+//! Example checking that stealth patches apply to the very first navigation
+//!
+//! Navigates immediately after `new_page` and checks `navigator.webdriver`.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    println!("\n=== Testing the first goto of a new page ===");
+    let page = browser.new_page().await?;
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    let webdriver = page.evaluate("return navigator.webdriver;").await?;
+    println!("navigator.webdriver: {}", webdriver);
+    assert_eq!(webdriver, serde_json::json!(false), "stealth patch should be active on the first document");
+
+    let chrome_runtime = page.evaluate("return !!(window.chrome && window.chrome.runtime);").await?;
+    assert_eq!(chrome_runtime, serde_json::json!(true), "chrome.runtime stub should be present");
+
+    println!("\n=== Testing a second page in the same browser ===");
+    let second = browser.new_page().await?;
+    second.goto("https://www.example.com", Default::default()).await?;
+    let webdriver = second.evaluate("return navigator.webdriver;").await?;
+    assert_eq!(webdriver, serde_json::json!(false), "stealth patch should be active in a new tab too");
+
+    println!("\n=== Testing set_content ===");
+    page.set_content(
+        "<p id='greeting'>Hello</p><script>document.getElementById('greeting').textContent += ' from script';</script>",
+        Default::default(),
+    )
+    .await?;
+    let text = page.locator("#greeting").text_content().await?;
+    println!("greeting: {}", text);
+    assert_eq!(text, "Hello from script", "inline scripts should run");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
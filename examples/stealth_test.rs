@@ -8,7 +8,7 @@
 //! ```
 
 use sparkle::async_api::Playwright;
-use sparkle::core::{LaunchOptions, LaunchOptionsBuilder};
+use sparkle::core::LaunchOptionsBuilder;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,110 @@
+//! Example demonstrating storage_state() with IndexedDB
+//!
+//! Exports with `indexed_db: true` and checks the IndexedDB section is
+//! populated, that it's omitted by default, and that it's restored into a
+//! new context.
+
+use sparkle::prelude::*;
+
+const SEED_DB: &str = r#"
+    return new Promise((done) => {
+        const open = indexedDB.open('sparkle-test', 3);
+        open.onupgradeneeded = () => {
+            const store = open.result.createObjectStore('todos', { keyPath: 'id' });
+            store.createIndex('by_title', 'title', { unique: true });
+            open.result.createObjectStore('settings');
+        };
+        open.onsuccess = () => {
+            const db = open.result;
+            const tx = db.transaction(['todos', 'settings'], 'readwrite');
+            tx.objectStore('todos').put({ id: 1, title: 'Write tests', done: false });
+            tx.objectStore('todos').put({ id: 2, title: 'Ship it', done: true });
+            tx.objectStore('settings').put('dark', 'theme');
+            tx.oncomplete = () => { db.close(); done(true); };
+        };
+    });
+"#;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let context = browser.new_context(Default::default()).await?;
+    let page = context.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+    page.evaluate("localStorage.setItem('token', 'abc');").await?;
+    page.evaluate(SEED_DB).await?;
+
+    println!("\n=== Testing the default export omits IndexedDB ===");
+    let state = context.storage_state(None::<&str>).await?;
+    let json = state.to_json()?;
+    assert!(!json.contains("indexedDB"), "IndexedDB should only be exported on request");
+    assert!(json.contains("\"token\""));
+
+    println!("\n=== Testing the export with indexed_db: true ===");
+    let state = context
+        .storage_state(StorageStateOptionsBuilder::default().indexed_db(true).build().unwrap())
+        .await?;
+    let origin = &state.origins[0];
+    let database = origin
+        .indexed_db
+        .iter()
+        .find(|db| db.name == "sparkle-test")
+        .expect("sparkle-test database should be captured");
+    assert_eq!(database.version, 3);
+    let todos = database.stores.iter().find(|s| s.name == "todos").unwrap();
+    assert_eq!(todos.key_path.as_deref(), Some("id"));
+    assert_eq!(todos.records.len(), 2);
+    assert!(todos.indexes.iter().any(|i| i.name == "by_title" && i.unique));
+    let settings = database.stores.iter().find(|s| s.name == "settings").unwrap();
+    assert_eq!(settings.records[0].key, Some(serde_json::json!("theme")));
+    println!("{}", state.to_json()?);
+
+    println!("\n=== Testing the origins filter ===");
+    let state = context
+        .storage_state(
+            StorageStateOptionsBuilder::default()
+                .origins(vec!["https://other.example.org".to_string()])
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    assert!(state.origins.is_empty());
+
+    println!("\n=== Testing IndexedDB is restored ===");
+    let exported = context
+        .storage_state(StorageStateOptionsBuilder::default().indexed_db(true).build().unwrap())
+        .await?;
+    // Contexts share the session's storage, so start the restore from scratch
+    page.evaluate(
+        "return new Promise((done) => { indexedDB.deleteDatabase('sparkle-test').onsuccess = () => done(true); });",
+    )
+    .await?;
+    let restored = browser
+        .new_context(BrowserContextOptionsBuilder::default().storage_state(exported).build().unwrap())
+        .await?;
+    let page = restored.new_page().await?;
+    page.goto("https://www.example.com", Default::default()).await?;
+    let titles = page
+        .evaluate(
+            r#"
+            return new Promise((done) => {
+                const open = indexedDB.open('sparkle-test');
+                open.onsuccess = () => {
+                    const request = open.result.transaction('todos').objectStore('todos').getAll();
+                    request.onsuccess = () => done(request.result.map((todo) => todo.title));
+                };
+            });
+        "#,
+        )
+        .await?;
+    assert_eq!(titles, serde_json::json!(["Write tests", "Ship it"]));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Example demonstrating strict mode for locators
+//!
+//! A selector matching two buttons fails under strict mode until `first()`
+//! picks one of them.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .strict_selectors(true)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `
+            <button onclick="window.clicked = 'first'">First</button>
+            <button onclick="window.clicked = 'second'">Second</button>
+            <input id="name">`;
+        window.clicked = null;
+    "#).await?;
+
+    println!("\n=== Clicking an ambiguous selector in a strict context ===");
+    let err = page.locator("button").click(Default::default()).await.unwrap_err();
+    println!("error: {}", err);
+    assert!(matches!(err, Error::StrictModeViolation { count: 2, .. }));
+    let clicked = page.evaluate("return window.clicked;").await?;
+    assert_eq!(clicked, serde_json::Value::Null, "nothing should be clicked");
+
+    println!("\n=== Clicking after first() ===");
+    page.locator("button").first().click(Default::default()).await?;
+    let clicked = page.evaluate("return window.clicked;").await?;
+    println!("clicked: {}", clicked);
+    assert_eq!(clicked, serde_json::json!("first"));
+
+    println!("\n=== Unique selectors are unaffected ===");
+    page.locator("#name").fill("sparkle").await?;
+
+    println!("\n=== Disabling strict mode per locator ===");
+    page.locator("button").strict(false).click(Default::default()).await?;
+
+    println!("\n=== Enabling strict mode per locator ===");
+    let plain = browser.new_page().await?;
+    plain.goto("https://www.example.com", Default::default()).await?;
+    plain.evaluate("document.body.innerHTML = '<p>one</p><p>two</p>';").await?;
+    plain.locator("p").text_content().await?;
+    let err = plain.locator("p").strict(true).text_content().await.unwrap_err();
+    assert!(matches!(err, Error::StrictModeViolation { .. }));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Example demonstrating click() in a touch-enabled context
+//!
+//! Clicks a target that only listens for `touchend` and checks that the
+//! handler fired, then checks that mouse events are only sent outside touch
+//! contexts and that a right click keeps its button.
+
+use sparkle::core::MouseButton;
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .has_touch(true)
+                .is_mobile(true)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+
+    page.goto_data_html(r#"
+        <div id="target" style="width: 200px; height: 100px; background: #ccc;"></div>
+        <div id="mouse" style="width: 200px; height: 100px; background: #eee;"></div>
+        <script>
+            window.events = [];
+            window.mouseEvents = [];
+            document.getElementById('target').addEventListener('touchend', () => window.events.push('touchend'));
+            const mouse = document.getElementById('mouse');
+            mouse.addEventListener('touchend', () => window.mouseEvents.push('touchend'));
+            mouse.addEventListener('mousedown', () => window.mouseEvents.push('mousedown'));
+        </script>
+    "#).await?;
+
+    println!("\n=== Testing click() on a touchend-only handler ===");
+    page.locator("#target").click(Default::default()).await?;
+    let events = page.evaluate("return window.events;").await?;
+    println!("Events: {}", events);
+    assert_eq!(events, serde_json::json!(["touchend"]), "the touchend-only handler should fire");
+
+    println!("\n=== Testing click() with has_touch sends no mouse events ===");
+    page.locator("#mouse").click(Default::default()).await?;
+    let events = page.evaluate("return window.mouseEvents;").await?;
+    println!("Events: {}", events);
+    assert_eq!(events, serde_json::json!(["touchend"]), "click should tap without mouse events");
+
+    println!("\n=== Testing click() without has_touch ===");
+    let desktop = browser.new_page().await?;
+    desktop.goto_data_html(r#"
+        <div id="target" style="width: 200px; height: 100px; background: #ccc;"></div>
+        <script>
+            window.events = [];
+            const target = document.getElementById('target');
+            target.addEventListener('touchend', () => window.events.push('touchend'));
+            target.addEventListener('mousedown', (event) => window.events.push(`mousedown:${event.button}`));
+        </script>
+    "#).await?;
+    desktop.locator("#target").click(Default::default()).await?;
+    let events = desktop.evaluate("return window.events;").await?;
+    assert_eq!(events, serde_json::json!(["mousedown:0"]), "other contexts keep clicking with the mouse");
+
+    println!("\n=== Testing right click() without has_touch ===");
+    desktop
+        .locator("#target")
+        .click(ClickOptionsBuilder::default().button(MouseButton::Right).build().unwrap())
+        .await?;
+    let events = desktop.evaluate("return window.events;").await?;
+    println!("Events: {}", events);
+    assert_eq!(events, serde_json::json!(["mousedown:0", "mousedown:2"]), "right click should keep its button");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Example demonstrating Touchscreen::tap() and Locator::tap()
+//!
+//! Creates a touch-enabled context and checks that a `touchstart` listener fires.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+
+    let context = browser
+        .new_context(
+            BrowserContextOptionsBuilder::default()
+                .has_touch(true)
+                .is_mobile(true)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+    let page = context.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        window.touched = false;
+        const target = document.createElement('div');
+        target.id = 'target';
+        target.style.cssText = 'width: 200px; height: 100px; background: #ccc;';
+        target.addEventListener('touchstart', () => { window.touched = true; });
+        document.body.appendChild(target);
+    "#).await?;
+
+    println!("\n=== Testing Locator::tap ===");
+    page.locator("#target").tap(Default::default()).await?;
+    let touched = page.evaluate("return window.touched;").await?;
+    println!("touchstart fired: {}", touched);
+    assert_eq!(touched, serde_json::json!(true), "touchstart listener should set the flag");
+
+    println!("\n=== Testing Touchscreen::tap ===");
+    page.evaluate("window.touched = false;").await?;
+    page.touchscreen().tap(5.0, 5.0).await?;
+    let touched = page.evaluate("return window.touched;").await?;
+    println!("touchstart fired outside target: {}", touched);
+    assert_eq!(touched, serde_json::json!(false), "tapping outside the target should not set the flag");
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Example demonstrating Page::wait_for_selector()
+//!
+//! Waits for elements that appear, become visible or are removed after the
+//! wait starts.
+
+use sparkle::prelude::*;
+use std::time::{Duration, Instant};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    page.evaluate(r#"
+        document.body.innerHTML = `<div id="banner" style="display: none">Saved</div>`;
+        setTimeout(() => {
+            const late = document.createElement('p');
+            late.id = 'late';
+            late.textContent = 'Loaded';
+            document.body.appendChild(late);
+        }, 300);
+        setTimeout(() => document.getElementById('banner').style.display = 'block', 600);
+        setTimeout(() => document.getElementById('banner').remove(), 900);
+    "#).await?;
+
+    println!("\n=== Testing an element added later ===");
+    let start = Instant::now();
+    let late = page.wait_for_selector("#late", Default::default()).await?;
+    let elapsed = start.elapsed();
+    println!("#late detected after {:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(2));
+    let late = late.expect("visible state should return the element");
+    assert_eq!(late.text_content().await?, "Loaded");
+
+    println!("\n=== Testing an element that becomes visible ===");
+    page.wait_for_selector("#banner", Default::default()).await?;
+    assert!(page.is_visible("#banner").await?);
+
+    println!("\n=== Testing an element that is removed ===");
+    let detached = WaitForSelectorOptionsBuilder::default()
+        .state(WaitForSelectorState::Detached)
+        .timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    assert!(page.wait_for_selector("#banner", detached).await?.is_none());
+    assert_eq!(page.locator("#banner").count().await?, 0);
+
+    println!("\n=== Testing a timeout ===");
+    let short = WaitForSelectorOptionsBuilder::default()
+        .timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    let result = page.wait_for_selector("#never", short).await;
+    assert!(matches!(result, Err(Error::Timeout { .. })));
+
+    println!("\n=== Testing an invalid selector ===");
+    let result = page.wait_for_selector("div[", Default::default()).await;
+    assert!(matches!(result, Err(Error::InvalidArgument(_))));
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Example demonstrating page.on_web_socket()
+//!
+//! Starts a local echo server, has the page open a WebSocket to it and checks
+//! the sent and received frames and the close are reported.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use sparkle::prelude::*;
+
+/// Echo every message back until the client closes the connection
+async fn echo_server() -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}/echo", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let Ok(mut socket) = tokio_tungstenite::accept_async(stream).await else { return };
+                while let Some(Ok(message)) = socket.next().await {
+                    if message.is_text() || message.is_binary() {
+                        let _ = socket.send(message).await;
+                    }
+                }
+            });
+        }
+    });
+    Ok(url)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let url = echo_server().await?;
+
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sockets = Arc::new(Mutex::new(Vec::new()));
+    {
+        let events = Arc::clone(&events);
+        let sockets = Arc::clone(&sockets);
+        page.on_web_socket(move |ws| {
+            sockets.lock().unwrap().push(ws.clone());
+            let sent = Arc::clone(&events);
+            ws.on_frame_sent(move |frame| sent.lock().unwrap().push(format!("sent {}", frame.payload())));
+            let received = Arc::clone(&events);
+            ws.on_frame_received(move |frame| {
+                received.lock().unwrap().push(format!("received {}", frame.payload()))
+            });
+            let closed = Arc::clone(&events);
+            ws.on_close(move || closed.lock().unwrap().push("close".to_string()));
+        })
+        .await?;
+    }
+
+    println!("\n=== Testing frames through an echo server ===");
+    let echoed = page
+        .evaluate(&format!(
+            r#"
+            return new Promise((done) => {{
+                const ws = new WebSocket('{}');
+                ws.onopen = () => ws.send('hello sparkle');
+                ws.onmessage = (event) => {{ ws.close(); done(event.data); }};
+            }});
+        "#,
+            url
+        ))
+        .await?;
+    assert_eq!(echoed.as_str(), Some("hello sparkle"));
+
+    // Events arrive over a separate CDP connection
+    for _ in 0..50 {
+        if events.lock().unwrap().iter().any(|e| e == "close") {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let sockets = sockets.lock().unwrap().clone();
+    assert_eq!(sockets.len(), 1);
+    assert_eq!(sockets[0].url(), url);
+    assert!(sockets[0].is_closed());
+    let events = events.lock().unwrap().clone();
+    println!("events: {:?}", events);
+    assert_eq!(events, vec!["sent hello sparkle", "received hello sparkle", "close"]);
+
+    println!("\n=== All tests passed! ===");
+    browser.close().await?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Example demonstrating Browser::webdriver()
+//!
+//! Uses the raw thirtyfour handle alongside Sparkle's own API.
+
+use sparkle::prelude::*;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let playwright = Playwright::new().await?;
+    let browser = playwright
+        .chromium()
+        .launch(LaunchOptionsBuilder::default().headless(true).build().unwrap())
+        .await?;
+    let page = browser.new_page().await?;
+
+    page.goto("https://www.example.com", Default::default()).await?;
+
+    println!("\n=== Testing raw WebDriver access ===");
+    let driver = browser.webdriver().await?;
+    let (raw_url, page_url) = tokio::join!(driver.current_url(), page.url());
+    let raw_url = raw_url?.to_string();
+    let page_url = page_url?;
+    println!("thirtyfour current_url: {}", raw_url);
+    println!("sparkle page.url:       {}", page_url);
+    assert_eq!(raw_url, page_url, "both APIs should see the same session");
+
+    println!("\n=== Testing the handle fails after close ===");
+    browser.close().await?;
+    assert!(browser.webdriver().await.is_err(), "no handle once the browser is closed");
+
+    println!("\n=== All tests passed! ===");
+    Ok(())
+}
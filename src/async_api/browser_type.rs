@@ -2,8 +2,10 @@
 //!
 //! This module provides the BrowserType interface for launching browsers.
 
-use crate::async_api::browser::Browser;
-use crate::core::{ConnectOptions, ConnectOverCdpOptions, Error, LaunchOptions, Result};
+use crate::async_api::browser::{check_page_limit, Browser, BrowserContext};
+use crate::core::{geolocation, ConnectOptions, ConnectOverCdpOptions, Error, HeadlessMode, LaunchOptions, Protocol, Result, SlowMoConfig};
+use crate::driver::capabilities::{is_edge_channel, matches_arg};
+use crate::driver::chromedriver_process::chromium_install_key;
 use crate::driver::{ChromeDriverProcess, ChromiumCapabilities, WebDriverAdapter};
 use std::path::PathBuf;
 
@@ -77,6 +79,7 @@ impl BrowserType {
     /// # }
     /// ```
     pub async fn launch(&self, options: LaunchOptions) -> Result<Browser> {
+        check_page_limit(options.max_concurrent_pages)?;
         match self.name {
             BrowserName::Chromium => self.launch_chromium(options).await,
             BrowserName::Firefox => Err(Error::not_implemented("Firefox support")),
@@ -84,6 +87,81 @@ impl BrowserType {
         }
     }
 
+    /// Launch a browser with a persistent profile and return its context
+    ///
+    /// Chrome is started with `--user-data-dir`, so cookies, storage,
+    /// extensions and settings are read from and written to `user_data_dir`
+    /// (created if missing) and survive across launches; `storage_state` is
+    /// not needed. The returned context already has a page open, and closing
+    /// it closes the browser.
+    ///
+    /// # Arguments
+    /// * `user_data_dir` - Profile directory, e.g. a copy of a real Chrome profile
+    /// * `options` - Launch configuration options
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidArgument`] if `options.args` already sets `--user-data-dir`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::BrowserType;
+    /// # async fn example(chromium: &BrowserType) -> sparkle::core::Result<()> {
+    /// let context = chromium
+    ///     .launch_persistent_context("/tmp/sparkle-profile", Default::default())
+    ///     .await?;
+    /// let page = context.pages().await.remove(0);
+    /// page.goto("https://example.com", Default::default()).await?;
+    /// context.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn launch_persistent_context(
+        &self,
+        user_data_dir: impl Into<PathBuf>,
+        options: LaunchOptions,
+    ) -> Result<BrowserContext> {
+        let options = Self::persistent_launch_options(user_data_dir.into(), options)?;
+        self.launch(options).await?.into_persistent_context().await
+    }
+
+    /// `options` with `--user-data-dir` pointing at the profile directory
+    fn persistent_launch_options(user_data_dir: PathBuf, mut options: LaunchOptions) -> Result<LaunchOptions> {
+        if options.args.iter().any(|arg| matches_arg(arg, "--user-data-dir")) {
+            return Err(Error::invalid_argument(
+                "Pass the profile directory to launch_persistent_context instead of a --user-data-dir argument",
+            ));
+        }
+
+        std::fs::create_dir_all(&user_data_dir).map_err(|e| {
+            Error::invalid_argument(format!("Cannot create user data dir {}: {}", user_data_dir.display(), e))
+        })?;
+        // Chrome resolves relative profile paths against its own working directory
+        let user_data_dir = user_data_dir.canonicalize().unwrap_or(user_data_dir);
+        tracing::debug!("Using persistent profile at {}", user_data_dir.display());
+        options.args.push(format!("--user-data-dir={}", user_data_dir.display()));
+        Ok(options)
+    }
+
+    /// Default arguments for stability, minus those the options ignore
+    ///
+    /// `--disable-blink-features=AutomationControlled` and the headless
+    /// switches are added, and filtered, by the capabilities builder.
+    fn default_args(options: &LaunchOptions) -> Vec<String> {
+        if options.ignore_all_default_args == Some(true) {
+            return Vec::new();
+        }
+
+        let mut default_args = Vec::new();
+        // Only add --no-sandbox if chromium_sandbox is not explicitly enabled
+        if !options.chromium_sandbox.unwrap_or(false) {
+            default_args.push("--no-sandbox".to_string());
+        }
+        default_args.push("--disable-dev-shm-usage".to_string());
+
+        default_args.retain(|arg| !options.ignore_default_args.iter().any(|pattern| matches_arg(arg, pattern)));
+        default_args
+    }
+
     /// Launch Chromium browser
     async fn launch_chromium(&self, options: LaunchOptions) -> Result<Browser> {
         tracing::info!("Launching Chromium browser");
@@ -91,7 +169,16 @@ impl BrowserType {
             options.headless, options.devtools, options.timeout);
         
         // Get stealth options (default to enabled)
-        let stealth = options.stealth.clone().unwrap_or_default();
+        let mut stealth = options.stealth.clone().unwrap_or_default();
+        if stealth.enabled && stealth.auto_timezone_from_ip && stealth.timezone_id.is_none() {
+            match geolocation::lookup(geolocation::GEOLOCATION_URL, options.proxy.as_ref()).await {
+                Ok(location) => {
+                    tracing::info!("Derived timezone {} from IP geolocation", location.timezone);
+                    geolocation::apply(&mut stealth, &location);
+                }
+                Err(e) => tracing::warn!("Skipping timezone derivation: {}", e),
+            }
+        }
         
         if stealth.enabled {
             tracing::info!("Stealth mode enabled (Patchright-style undetectable automation)");
@@ -104,13 +191,18 @@ impl BrowserType {
         let mut caps = ChromiumCapabilities::new().stealth(stealth.clone());
 
         // Handle devtools option - if devtools is true, force headless to false
-        let headless = if options.devtools == Some(true) {
-            false
+        let headless_mode = if options.devtools == Some(true) {
+            HeadlessMode::Off
+        } else if let Some(mode) = options.headless_mode {
+            mode
         } else {
-            options.headless.unwrap_or(true) // Default to headless
+            match options.headless {
+                Some(false) => HeadlessMode::Off,
+                _ => HeadlessMode::New, // Default to headless
+            }
         };
         
-        caps = caps.headless(headless);
+        caps = caps.headless_mode(headless_mode);
 
         // Add devtools argument if requested
         if options.devtools == Some(true) {
@@ -127,26 +219,19 @@ impl BrowserType {
             tracing::debug!("Added {} custom arguments", options.args.len());
         }
 
-        // Build list of default arguments for stability
-        let mut default_args = Vec::new();
-        
-        // Only add --no-sandbox if chromium_sandbox is not explicitly enabled
-        if !options.chromium_sandbox.unwrap_or(false) {
-            default_args.push("--no-sandbox".to_string());
+        if let Some((width, height)) = options.window_size {
+            caps = caps.window_size(width, height);
         }
-        default_args.push("--disable-dev-shm-usage".to_string());
-        // Note: --disable-blink-features=AutomationControlled is now added in capabilities builder with stealth mode
-
-        // Filter default args based on ignore options
-        if options.ignore_all_default_args != Some(true) {
-            // Only add default args that are not in the ignore list
-            for arg in default_args {
-                if !options.ignore_default_args.contains(&arg) {
-                    caps = caps.arg(arg);
-                }
-            }
+
+        let bidi = options.protocol == Some(Protocol::Bidi);
+        if bidi {
+            tracing::debug!("Requesting a WebDriver BiDi session");
+            caps = caps.web_socket_url(true);
         }
-        // If ignore_all_default_args is true, skip adding all default args
+
+        caps = caps
+            .args(Self::default_args(&options))
+            .ignore_default_args(options.ignore_default_args.iter().cloned());
 
         // Set Chrome binary path
         // Priority: executable_path > channel > find_installed_chrome
@@ -171,6 +256,12 @@ impl BrowserType {
             }
         }
 
+        // Edge channels need Edge-specific capabilities (ms:edgeOptions) and msedgedriver
+        if let Some(channel) = &options.channel {
+            caps = caps.channel(channel);
+        }
+        let edge = options.channel.as_deref().is_some_and(is_edge_channel);
+
         // Add environment variables
         if !options.env.is_empty() {
             tracing::debug!("Setting {} environment variables", options.env.len());
@@ -184,6 +275,7 @@ impl BrowserType {
         }
 
         // Set proxy if specified
+        let proxy = options.proxy.clone();
         if let Some(proxy) = options.proxy {
             tracing::debug!("Configuring proxy: {}", proxy.server);
             caps = caps.proxy(&proxy.server, proxy.bypass.as_deref());
@@ -199,38 +291,70 @@ impl BrowserType {
         tracing::debug!("Total timeout: {:?}, ChromeDriver timeout: {:?}", total_timeout, driver_timeout);
 
         // Determine ChromeDriver URL or launch ChromeDriver automatically
-        let (chromedriver_url, driver_process) = if let Ok(url) = std::env::var("CHROMEDRIVER_URL") {
+        let (chromedriver_url, driver_process, shared_driver) = if let Ok(url) = std::env::var("CHROMEDRIVER_URL") {
             // Use custom ChromeDriver URL from environment variable
             tracing::info!("Using ChromeDriver URL from environment: {}", url);
-            (url, None)
+            (url, None, None)
         } else {
-            // Check if custom ChromeDriver path is provided via CHROMEDRIVER_PATH
-            let driver_path = std::env::var("CHROMEDRIVER_PATH")
-                .ok()
-                .map(PathBuf::from);
+            let driver_path = if edge {
+                let path = ChromeDriverProcess::find_msedgedriver()
+                    .map_err(|e| Error::BrowserNotFound(e.to_string()))?;
+                tracing::info!("Using msedgedriver: {}", path.display());
+                Some(path)
+            } else {
+                // Check if custom ChromeDriver path is provided via CHROMEDRIVER_PATH
+                let driver_path = std::env::var("CHROMEDRIVER_PATH")
+                    .ok()
+                    .map(PathBuf::from);
+
+                if let Some(ref path) = driver_path {
+                    tracing::info!("Using custom ChromeDriver path: {}", path.display());
+                } else {
+                    tracing::debug!("Launching ChromeDriver from installed location");
+                }
+                driver_path
+            };
             
-            if let Some(ref path) = driver_path {
-                tracing::info!("Using custom ChromeDriver path: {}", path.display());
+            // The shared driver is a ChromeDriver, which can't drive Edge
+            let reuse_driver = !edge && options.reuse_driver.unwrap_or_else(|| {
+                std::env::var("SPARKLE_REUSE_CHROMEDRIVER").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            });
+
+            if reuse_driver {
+                // Share one ChromeDriver; each browser still gets its own session
+                let lease = ChromeDriverProcess::launch_shared(driver_path, 9515, &options.env, driver_timeout)
+                    .await
+                    .map_err(|e| Error::internal(format!("Failed to launch ChromeDriver: {}", e)))?;
+                tracing::info!("Using shared ChromeDriver at {}", lease.url());
+                (lease.url().to_string(), None, Some(lease))
             } else {
-                tracing::debug!("Launching ChromeDriver from installed location");
+                // Launch ChromeDriver automatically from installed location or custom path
+                let process = ChromeDriverProcess::launch(driver_path, 9515, &options.env, driver_timeout)
+                    .await
+                    .map_err(|e| Error::internal(format!("Failed to launch ChromeDriver: {}", e)))?;
+                let url = process.url().to_string();
+                tracing::info!("ChromeDriver launched successfully at {}", url);
+                (url, Some(process), None)
             }
-            
-            // Launch ChromeDriver automatically from installed location or custom path
-            let process = ChromeDriverProcess::launch(driver_path, 9515, &options.env, driver_timeout)
-                .await
-                .map_err(|e| Error::internal(format!("Failed to launch ChromeDriver: {}", e)))?;
-            let url = process.url().to_string();
-            tracing::info!("ChromeDriver launched successfully at {}", url);
-            (url, Some(process))
         };
 
         // Create WebDriver adapter with slow_mo
-        tracing::debug!("Creating WebDriver adapter, slow_mo: {:?}", options.slow_mo);
-        let adapter = WebDriverAdapter::create(&chromedriver_url, capabilities, options.slow_mo).await?;
+        let slow_mo = options.slow_mo_config.or(options.slow_mo.map(SlowMoConfig::from));
+        tracing::debug!("Creating WebDriver adapter, slow_mo: {:?}", slow_mo);
+        let adapter = WebDriverAdapter::create(&chromedriver_url, capabilities, slow_mo).await?;
+        if bidi && !adapter.has_bidi().await {
+            let _ = adapter.close().await;
+            return Err(Error::not_implemented(
+                "WebDriver BiDi: the driver returned no webSocketUrl for the session",
+            ));
+        }
 
         // Create and return browser with driver process and stealth options
         tracing::info!("Browser launched successfully");
-        Ok(Browser::new(adapter, driver_process, Some(stealth)))
+        Ok(Browser::new(adapter, driver_process, Some(stealth))
+            .with_page_limit(options.max_concurrent_pages)
+            .with_proxy(proxy)
+            .with_shared_driver(shared_driver))
     }
 
     /// Connect to an existing browser instance via remote WebDriver
@@ -293,39 +417,58 @@ impl BrowserType {
             tracing::warn!("Channel option is not yet supported for remote connections");
         }
 
-        let capabilities = caps.build();
+        // User capabilities are merged last so they take precedence
+        let capabilities = caps.capabilities(options.capabilities).build();
 
         // Determine timeout for connection
         let timeout = options.timeout.unwrap_or(std::time::Duration::from_secs(30));
+        let slow_mo = options.slow_mo_config.or(options.slow_mo.map(SlowMoConfig::from));
+        let retry = options.retry.unwrap_or_default();
         let start = std::time::Instant::now();
         
-        tracing::debug!("Connection timeout: {:?}", timeout);
+        tracing::debug!("Connection timeout: {:?}, retry: {:?}", timeout, retry);
 
-        // Attempt to connect to the remote WebDriver server
+        // Attempt to connect to the remote WebDriver server, backing off between
+        // attempts that failed for transient reasons
+        let mut attempt = 0;
         let adapter = loop {
-            match WebDriverAdapter::create(endpoint_url, capabilities.clone(), options.slow_mo).await {
+            match WebDriverAdapter::create_with_headers(endpoint_url, capabilities.clone(), &options.headers, slow_mo).await {
                 Ok(adapter) => {
                     tracing::info!("Successfully connected to remote WebDriver");
                     break adapter;
                 }
+                Err(e) if !e.is_retryable() => {
+                    tracing::error!("Failed to connect: {}", e);
+                    return Err(Error::connection_failed(format!(
+                        "Failed to connect to WebDriver at '{}': {}",
+                        endpoint_url, e
+                    )));
+                }
                 Err(e) => {
-                    if start.elapsed() >= timeout {
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
                         tracing::error!("Failed to connect after {:?}: {}", timeout, e);
                         return Err(Error::connection_failed(format!(
-                            "Failed to connect to WebDriver at '{}' after {:?}: {}",
-                            endpoint_url, timeout, e
+                            "Failed to connect to WebDriver at '{}' after {} attempts in {:?}; last error: {}",
+                            endpoint_url,
+                            attempt + 1,
+                            timeout,
+                            e
                         )));
                     }
-                    tracing::trace!("Connection attempt failed, retrying: {}", e);
-                    // Wait a bit before retrying
-                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    let delay = retry
+                        .jittered_delay_for(attempt, random_fraction())
+                        .min(timeout - elapsed);
+                    tracing::trace!("Connection attempt {} failed, retrying in {:?}: {}", attempt + 1, delay, e);
+                    attempt += 1;
+                    tokio::time::sleep(delay).await;
                 }
             }
         };
 
         // Create and return browser without driver process (remote connection)
         // Stealth not applicable for remote connections
-        Ok(Browser::new(adapter, None, None))
+        Ok(Browser::new(adapter, None, None).with_keepalive(options.keepalive_interval))
     }
 
     /// Connect to a browser via Chrome DevTools Protocol
@@ -389,13 +532,20 @@ impl BrowserType {
 
         // Determine timeout for connection
         let timeout = options.timeout.unwrap_or(std::time::Duration::from_secs(30));
+        let slow_mo = options.slow_mo_config.or(options.slow_mo.map(SlowMoConfig::from));
         let start = std::time::Instant::now();
 
         // Attempt to connect to the CDP endpoint via WebDriver
         // Chrome with --remote-debugging-port exposes both CDP and WebDriver protocols
         let adapter = loop {
-            match WebDriverAdapter::create(endpoint_url, caps.clone(), options.slow_mo).await {
+            match WebDriverAdapter::create(endpoint_url, caps.clone(), slow_mo).await {
                 Ok(adapter) => break adapter,
+                Err(e) if !e.is_retryable() => {
+                    return Err(Error::connection_failed(format!(
+                        "Failed to connect to CDP endpoint at '{}': {}",
+                        endpoint_url, e
+                    )));
+                }
                 Err(e) => {
                     if start.elapsed() >= timeout {
                         return Err(Error::connection_failed(format!(
@@ -473,10 +623,8 @@ impl BrowserType {
         if let Ok(entries) = std::fs::read_dir(&install_dir) {
             for entry in entries.flatten() {
                 if let Ok(file_name) = entry.file_name().into_string() {
-                    if file_name.starts_with("chromium-") {
-                        if let Some(revision) = file_name.strip_prefix("chromium-") {
-                            versions.push((revision.to_string(), entry.path()));
-                        }
+                    if let Some(key) = chromium_install_key(&file_name) {
+                        versions.push((key, entry.path()));
                     }
                 }
             }
@@ -579,7 +727,7 @@ impl BrowserType {
 
         // On Windows, search standard installation directories
         if cfg!(windows) {
-            let base_paths = vec![
+            let base_paths = [
                 std::env::var("ProgramFiles").ok().map(PathBuf::from),
                 std::env::var("ProgramFiles(x86)").ok().map(PathBuf::from),
                 std::env::var("LOCALAPPDATA").ok().map(PathBuf::from),
@@ -671,9 +819,16 @@ impl BrowserType {
     }
 }
 
+/// A random sample in `[0, 1)` for retry jitter
+fn random_fraction() -> f64 {
+    rand::random::<f64>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{ConnectOptionsBuilder, RetryOptionsBuilder};
+    use std::time::Duration;
 
     #[test]
     fn test_browser_name_display() {
@@ -682,12 +837,58 @@ mod tests {
         assert_eq!(BrowserName::WebKit.to_string(), "webkit");
     }
 
+    #[tokio::test]
+    async fn test_launch_rejects_zero_page_limit() {
+        let options = LaunchOptions {
+            max_concurrent_pages: Some(0),
+            ..Default::default()
+        };
+        let result = BrowserType::new(BrowserName::Chromium).launch(options).await;
+        assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    }
+
     #[test]
     fn test_browser_type_creation() {
         let chromium = BrowserType::new(BrowserName::Chromium);
         assert_eq!(chromium.name(), BrowserName::Chromium);
     }
 
+    #[test]
+    fn test_ignore_default_args_matches_by_name() {
+        let options = LaunchOptions::default();
+        assert_eq!(BrowserType::default_args(&options), vec!["--no-sandbox", "--disable-dev-shm-usage"]);
+
+        // Leading dashes are optional, and unrelated defaults remain
+        let options = LaunchOptions {
+            ignore_default_args: vec!["no-sandbox".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(BrowserType::default_args(&options), vec!["--disable-dev-shm-usage"]);
+
+        let options = LaunchOptions {
+            ignore_all_default_args: Some(true),
+            ..Default::default()
+        };
+        assert!(BrowserType::default_args(&options).is_empty());
+    }
+
+    #[test]
+    fn test_persistent_launch_options_add_user_data_dir() {
+        let dir = std::env::temp_dir().join(format!("sparkle-profile-{}", std::process::id()));
+        let options = BrowserType::persistent_launch_options(dir.join("nested"), LaunchOptions::default()).unwrap();
+        assert!(dir.join("nested").is_dir(), "profile directory should be created");
+        let expected = dir.join("nested").canonicalize().unwrap();
+        assert_eq!(options.args, vec![format!("--user-data-dir={}", expected.display())]);
+
+        let options = LaunchOptions {
+            args: vec!["--user-data-dir=/elsewhere".to_string()],
+            ..Default::default()
+        };
+        let err = BrowserType::persistent_launch_options(dir.clone(), options).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_executable_path_not_implemented() {
         // Firefox and WebKit should return NotImplemented error
@@ -721,4 +922,98 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_connect_backs_off_until_grid_is_ready() {
+        use crate::driver::fake_webdriver::{self, FakeResponse};
+        use serde_json::{json, Value};
+
+        // The "Grid" rejects the first three session requests while it spins up
+        let (url, commands) = fake_webdriver::start(|command, seen| match command {
+            "POST /session" if seen < 3 => FakeResponse::error(500, "session not created", "no free nodes"),
+            "POST /session" => FakeResponse::ok(json!({ "sessionId": "fake", "capabilities": {} })),
+            _ => FakeResponse::ok(Value::Null),
+        })
+        .await;
+
+        let options = ConnectOptionsBuilder::default()
+            .timeout(Duration::from_secs(10))
+            .retry(
+                RetryOptionsBuilder::default()
+                    .initial_delay(Duration::from_millis(50))
+                    .multiplier(2.0)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        BrowserType::new(BrowserName::Chromium)
+            .connect(&url, options)
+            .await
+            .expect("connection should succeed once the Grid is ready");
+
+        let attempts: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command == "POST /session")
+            .map(|(at, _, _)| *at)
+            .collect();
+        assert_eq!(attempts.len(), 4);
+        let gaps: Vec<_> = attempts.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        assert!(gaps[0] >= Duration::from_millis(50), "first delay was {:?}", gaps[0]);
+        assert!(gaps[2] >= Duration::from_millis(200), "third delay was {:?}", gaps[2]);
+        assert!(gaps[2] > gaps[0], "delays should grow: {:?}", gaps);
+    }
+
+    #[tokio::test]
+    async fn test_connect_reports_last_error() {
+        use crate::driver::fake_webdriver::{self, FakeResponse};
+
+        let (url, _) = fake_webdriver::start(|_, _| {
+            FakeResponse::error(500, "session not created", "no free nodes")
+        })
+        .await;
+
+        let options = ConnectOptionsBuilder::default()
+            .timeout(Duration::from_millis(300))
+            .build()
+            .unwrap();
+        let err = BrowserType::new(BrowserName::Chromium)
+            .connect(&url, options)
+            .await
+            .err()
+            .expect("connection should fail");
+        assert!(matches!(err, Error::ConnectionFailed(_)));
+        assert!(err.to_string().contains("no free nodes"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_connect_does_not_retry_terminal_errors() {
+        use crate::driver::fake_webdriver::{self, FakeResponse};
+
+        let (url, commands) = fake_webdriver::start(|_, _| {
+            FakeResponse::error(400, "invalid argument", "unrecognized capability: foo")
+        })
+        .await;
+
+        let options = ConnectOptionsBuilder::default()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let err = BrowserType::new(BrowserName::Chromium)
+            .connect(&url, options)
+            .await
+            .err()
+            .expect("connection should fail");
+        assert!(matches!(err, Error::ConnectionFailed(_)));
+        assert!(err.to_string().contains("unrecognized capability"), "{}", err);
+        let attempts = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command == "POST /session")
+            .count();
+        assert_eq!(attempts, 1);
+    }
 }
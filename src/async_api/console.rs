@@ -0,0 +1,227 @@
+//! Console API
+//!
+//! Console messages logged by a page are observed over a CDP websocket with
+//! the Runtime domain (`Runtime.consoleAPICalled`), or, on sessions launched
+//! with [`Protocol::Bidi`](crate::core::Protocol::Bidi), through the WebDriver
+//! BiDi `log.entryAdded` event.
+
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::core::Result;
+use crate::driver::WebDriverAdapter;
+
+pub(crate) type ConsoleHandler = Arc<dyn Fn(ConsoleMessage) + Send + Sync>;
+
+/// A message logged with the page's console API
+///
+/// Passed to the handlers registered with
+/// [`Page::on_console`](crate::async_api::Page::on_console).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleMessage {
+    r#type: String,
+    text: String,
+}
+
+impl ConsoleMessage {
+    /// The console method, e.g. `log`, `info`, `warning`, `error` or `debug`
+    pub fn r#type(&self) -> &str {
+        &self.r#type
+    }
+
+    /// The logged values, formatted and joined with spaces
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// From a CDP `Runtime.consoleAPICalled` event
+    fn from_cdp(params: &Value) -> Self {
+        let text = params["args"]
+            .as_array()
+            .map(|args| args.iter().map(remote_object_text).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        Self {
+            r#type: params["type"].as_str().unwrap_or("log").to_string(),
+            text,
+        }
+    }
+
+    /// From a BiDi `log.entryAdded` event, `None` for entries that are not
+    /// console calls (e.g. uncaught exceptions)
+    fn from_bidi(params: &Value) -> Option<Self> {
+        if params["type"] != "console" {
+            return None;
+        }
+        let r#type = match params["method"].as_str().unwrap_or("log") {
+            // Match the CDP name, as Playwright does
+            "warn" => "warning",
+            method => method,
+        };
+        Some(Self {
+            r#type: r#type.to_string(),
+            text: params["text"].as_str().unwrap_or_default().to_string(),
+        })
+    }
+}
+
+/// How a CDP `RemoteObject` argument prints in the console
+fn remote_object_text(arg: &Value) -> String {
+    match &arg["value"] {
+        Value::String(value) => value.clone(),
+        Value::Null => arg["unserializableValue"]
+            .as_str()
+            .or(arg["description"].as_str())
+            .unwrap_or_else(|| arg["type"].as_str().unwrap_or_default())
+            .to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// Turn one CDP or BiDi event into a console message
+fn message_from_event(event: &Value) -> Option<ConsoleMessage> {
+    match event["method"].as_str()? {
+        "Runtime.consoleAPICalled" => Some(ConsoleMessage::from_cdp(&event["params"])),
+        "log.entryAdded" => ConsoleMessage::from_bidi(&event["params"]),
+        _ => None,
+    }
+}
+
+/// Background listener reporting the console messages of a page
+pub(crate) struct ConsoleWatcher {
+    handlers: Arc<Mutex<Vec<ConsoleHandler>>>,
+    task: JoinHandle<()>,
+}
+
+impl ConsoleWatcher {
+    /// Subscribe to console messages over BiDi if the session has it,
+    /// otherwise enable the CDP Runtime domain, and start listening
+    pub(crate) async fn start(adapter: &WebDriverAdapter) -> Result<Self> {
+        let mut events = if adapter.has_bidi().await {
+            adapter.connect_bidi_events(&["log.entryAdded"]).await?
+        } else {
+            adapter.connect_cdp_events(&[("Runtime.enable", json!({}))]).await?
+        };
+
+        let handlers: Arc<Mutex<Vec<ConsoleHandler>>> = Arc::default();
+        let task_handlers = Arc::clone(&handlers);
+        let task = tokio::spawn(async move {
+            while let Some(Ok(message)) = events.next().await {
+                let Message::Text(text) = message else { continue };
+                let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+                let Some(message) = message_from_event(&event) else { continue };
+                let handlers = task_handlers.lock().unwrap().clone();
+                for handler in handlers {
+                    handler(message.clone());
+                }
+            }
+        });
+
+        Ok(Self { handlers, task })
+    }
+
+    /// Call `handler` for every console message the page logs from now on
+    pub(crate) fn add_handler(&self, handler: ConsoleHandler) {
+        self.handlers.lock().unwrap().push(handler);
+    }
+}
+
+impl Drop for ConsoleWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdp_and_bidi_events_give_same_message() {
+        let cdp = json!({
+            "method": "Runtime.consoleAPICalled",
+            "params": {
+                "type": "warning",
+                "args": [
+                    { "type": "string", "value": "count" },
+                    { "type": "number", "value": 3 },
+                    { "type": "number", "unserializableValue": "NaN", "description": "NaN" },
+                    { "type": "object", "className": "Object", "description": "Object" },
+                ],
+            },
+        });
+        let bidi = json!({
+            "type": "event",
+            "method": "log.entryAdded",
+            "params": { "type": "console", "method": "warn", "level": "warn", "text": "count 3 NaN Object", "args": [] },
+        });
+
+        let expected = ConsoleMessage { r#type: "warning".to_string(), text: "count 3 NaN Object".to_string() };
+        assert_eq!(message_from_event(&cdp), Some(expected.clone()));
+        assert_eq!(message_from_event(&bidi), Some(expected));
+    }
+
+    #[tokio::test]
+    async fn test_bidi_session_subscribes_to_log_entries() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use futures::SinkExt;
+
+        // BiDi endpoint: acknowledge the subscription, then log one entry
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}/session/fake", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let Some(Ok(Message::Text(subscribe))) = socket.next().await else { panic!("no subscription") };
+            let subscribe: Value = serde_json::from_str(&subscribe).unwrap();
+            let reply = json!({ "type": "success", "id": subscribe["id"], "result": {} });
+            socket.send(Message::Text(reply.to_string().into())).await.unwrap();
+            let entry = json!({
+                "type": "event",
+                "method": "log.entryAdded",
+                "params": { "type": "console", "method": "log", "level": "info", "text": "hello from bidi" },
+            });
+            socket.send(Message::Text(entry.to_string().into())).await.unwrap();
+            // Keep the socket open until the test is done
+            let _ = socket.next().await;
+            subscribe
+        });
+
+        let (adapter, _) = fake_adapter(move |command, _| match command {
+            "GET /session/fake" => Some(FakeResponse::ok(json!({ "capabilities": { "webSocketUrl": ws_url } }))),
+            "GET /session/fake/window" => Some(FakeResponse::ok(json!("CDwindow-CONTEXT1"))),
+            _ => None,
+        })
+        .await;
+
+        let watcher = ConsoleWatcher::start(&adapter).await.unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        watcher.add_handler(Arc::new(move |message| {
+            let _ = sender.send(message);
+        }));
+
+        let message = tokio::time::timeout(std::time::Duration::from_secs(5), receiver.recv())
+            .await
+            .expect("the log entry should be reported")
+            .unwrap();
+        assert_eq!(message.r#type(), "log");
+        assert_eq!(message.text(), "hello from bidi");
+
+        drop(watcher);
+        let subscribe = server.await.unwrap();
+        assert_eq!(subscribe["method"], "session.subscribe");
+        assert_eq!(subscribe["params"], json!({ "events": ["log.entryAdded"], "contexts": ["CONTEXT1"] }));
+    }
+
+    #[test]
+    fn test_bidi_exceptions_are_not_console_messages() {
+        let event = json!({
+            "method": "log.entryAdded",
+            "params": { "type": "javascript", "level": "error", "text": "Uncaught ReferenceError: x is not defined" },
+        });
+        assert_eq!(message_from_event(&event), None);
+    }
+}
@@ -0,0 +1,337 @@
+//! Download API
+//!
+//! Downloads started by a page are captured over a CDP websocket:
+//! `Browser.setDownloadBehavior` saves them under their GUID, into
+//! `LaunchOptions::downloads_path` or else a temporary directory, and
+//! `downloadWillBegin`/`downloadProgress` events report their suggested name
+//! and completion.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::core::{Error, Result};
+use crate::driver::webdriver_adapter::CdpEventStream;
+use crate::driver::WebDriverAdapter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DownloadState {
+    InProgress,
+    Completed,
+    Canceled,
+}
+
+/// A file downloaded by a page
+///
+/// Returned by [`Page::wait_for_download`](crate::async_api::Page::wait_for_download).
+/// The file is kept in a temporary directory that is removed when the page
+/// is dropped; use [`Download::save_as`] to keep it.
+///
+/// # Example
+/// ```no_run
+/// # use sparkle::async_api::Page;
+/// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+/// page.locator("a#report").click(Default::default()).await?;
+/// let download = page.wait_for_download(None).await?;
+/// download.save_as(format!("reports/{}", download.suggested_filename())).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Download {
+    url: String,
+    suggested_filename: String,
+    path: PathBuf,
+    state: watch::Receiver<DownloadState>,
+}
+
+impl Download {
+    /// URL the download was requested from
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// File name suggested by the server or the link's `download` attribute
+    pub fn suggested_filename(&self) -> &str {
+        &self.suggested_filename
+    }
+
+    /// Wait for the download to finish and return where it was saved
+    ///
+    /// The file has a generated name; its original name is
+    /// [`Download::suggested_filename`].
+    pub async fn path(&self) -> Result<PathBuf> {
+        let mut state = self.state.clone();
+        let finished = state
+            .wait_for(|state| *state != DownloadState::InProgress)
+            .await
+            .map(|state| *state)
+            .map_err(|_| Error::DownloadFailed(format!("Download of {} was interrupted", self.url)))?;
+
+        match finished {
+            DownloadState::Completed => Ok(self.path.clone()),
+            _ => Err(Error::DownloadFailed(format!("Download of {} was canceled", self.url))),
+        }
+    }
+
+    /// Wait for the download to finish and copy it to `dest`
+    ///
+    /// Parent directories of `dest` are created as needed.
+    pub async fn save_as(&self, dest: impl AsRef<Path>) -> Result<()> {
+        let source = self.path().await?;
+        let dest = dest.as_ref();
+        if let Some(parent) = dest.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(&source, dest).await?;
+        Ok(())
+    }
+}
+
+/// Background listener collecting the downloads started by a page
+pub(crate) struct DownloadWatcher {
+    downloads: Mutex<mpsc::UnboundedReceiver<Download>>,
+    dir: PathBuf,
+    /// Whether `dir` is a temporary directory to remove on drop
+    temporary: bool,
+    task: JoinHandle<()>,
+}
+
+impl DownloadWatcher {
+    /// Route the page's downloads into the launch's downloads directory (or a
+    /// temporary one) and start listening
+    ///
+    /// The download behavior is set on the page's browser context only, and
+    /// downloads started by other pages of that context are ignored.
+    pub(crate) async fn start(adapter: &WebDriverAdapter) -> Result<Self> {
+        let (dir, temporary) = match adapter.downloads_path() {
+            Some(dir) => (dir, false),
+            None => (download_dir(), true),
+        };
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let (mut events, mut frames) = match Self::listen(adapter, &dir).await {
+            Ok(listening) => listening,
+            Err(error) => {
+                if temporary {
+                    let _ = tokio::fs::remove_dir_all(&dir).await;
+                }
+                return Err(error);
+            }
+        };
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let task_dir = dir.clone();
+        let task = tokio::spawn(async move {
+            let mut states: HashMap<String, watch::Sender<DownloadState>> = HashMap::new();
+
+            while let Some(Ok(message)) = events.next().await {
+                let Message::Text(text) = message else { continue };
+                let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+                let params = &event["params"];
+
+                // Track the page's frames so downloads of other pages can be told apart
+                match event["method"].as_str() {
+                    Some("Page.frameAttached") => {
+                        if let Some(frame_id) = params["frameId"].as_str() {
+                            frames.insert(frame_id.to_string());
+                        }
+                        continue;
+                    }
+                    Some("Page.frameNavigated") => {
+                        if let Some(frame_id) = params["frame"]["id"].as_str() {
+                            frames.insert(frame_id.to_string());
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+                let Some(guid) = params["guid"].as_str() else { continue };
+
+                // Chrome reports downloads on both the Page and Browser domains
+                match event["method"].as_str() {
+                    Some("Browser.downloadWillBegin" | "Page.downloadWillBegin") => {
+                        let frame_id = params["frameId"].as_str().unwrap_or_default();
+                        if states.contains_key(guid) || !frames.contains(frame_id) {
+                            continue;
+                        }
+                        let (state_sender, state) = watch::channel(DownloadState::InProgress);
+                        states.insert(guid.to_string(), state_sender);
+                        let _ = sender.send(Download {
+                            url: params["url"].as_str().unwrap_or_default().to_string(),
+                            suggested_filename: params["suggestedFilename"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                            path: task_dir.join(guid),
+                            state,
+                        });
+                    }
+                    Some("Browser.downloadProgress" | "Page.downloadProgress") => {
+                        let state = match params["state"].as_str() {
+                            Some("completed") => DownloadState::Completed,
+                            Some("canceled") => DownloadState::Canceled,
+                            _ => continue,
+                        };
+                        if let Some(state_sender) = states.get(guid) {
+                            state_sender.send_replace(state);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        Ok(Self {
+            downloads: Mutex::new(receiver),
+            dir,
+            temporary,
+            task,
+        })
+    }
+
+    /// Set the download behavior of the page's browser context and open the
+    /// event stream, returning it with the ids of the page's current frames
+    async fn listen(adapter: &WebDriverAdapter, dir: &Path) -> Result<(CdpEventStream, HashSet<String>)> {
+        let target = adapter.execute_cdp("Target.getTargetInfo").await?;
+        let mut behavior = json!({
+            "behavior": "allowAndName",
+            "downloadPath": dir,
+            "eventsEnabled": true,
+        });
+        if let Some(context) = target["targetInfo"]["browserContextId"].as_str() {
+            behavior["browserContextId"] = json!(context);
+        }
+
+        let events = adapter
+            .connect_cdp_events(&[
+                ("Page.enable", json!({})),
+                ("Browser.setDownloadBehavior", behavior),
+            ])
+            .await?;
+
+        // Frames attached after this are picked up from the event stream
+        let frame_tree = adapter.execute_cdp("Page.getFrameTree").await?;
+        let mut frames = HashSet::new();
+        collect_frame_ids(&frame_tree["frameTree"], &mut frames);
+        Ok((events, frames))
+    }
+
+    /// Wait for the next download that has not been handed out yet
+    pub(crate) async fn next(&self, timeout: Duration) -> Result<Download> {
+        let mut downloads = self.downloads.lock().await;
+        match tokio::time::timeout(timeout, downloads.recv()).await {
+            Ok(Some(download)) => Ok(download),
+            Ok(None) => Err(Error::DownloadFailed(
+                "CDP connection closed while waiting for a download".to_string(),
+            )),
+            Err(_) => Err(Error::timeout_duration("wait for download", timeout)),
+        }
+    }
+}
+
+impl Drop for DownloadWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+        if self.temporary {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+/// Add the ids of the frames in a `Page.getFrameTree` node and its children
+fn collect_frame_ids(node: &Value, frames: &mut HashSet<String>) {
+    if let Some(frame_id) = node["frame"]["id"].as_str() {
+        frames.insert(frame_id.to_string());
+    }
+    for child in node["childFrames"].as_array().into_iter().flatten() {
+        collect_frame_ids(child, frames);
+    }
+}
+
+/// A fresh directory under the system temp dir for one page's downloads
+fn download_dir() -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!(
+        "sparkle-downloads-{}-{}-{}",
+        std::process::id(),
+        nanos,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_download(path: &Path) -> (Download, watch::Sender<DownloadState>) {
+        let (sender, state) = watch::channel(DownloadState::InProgress);
+        let download = Download {
+            url: "https://example.com/report.csv".to_string(),
+            suggested_filename: "report.csv".to_string(),
+            path: path.to_path_buf(),
+            state,
+        };
+        (download, sender)
+    }
+
+    #[tokio::test]
+    async fn test_save_as_waits_for_completion() {
+        let dir = download_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("8f2c-guid");
+        let (download, sender) = pending_download(&source);
+
+        let finish = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            std::fs::write(&source, "a,b\n1,2\n").unwrap();
+            sender.send_replace(DownloadState::Completed);
+            sender
+        });
+
+        let dest = dir.join("saved").join(download.suggested_filename());
+        download.save_as(&dest).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "a,b\n1,2\n");
+
+        drop(finish.await.unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_frame_ids_include_nested_frames() {
+        let tree = json!({
+            "frame": { "id": "MAIN" },
+            "childFrames": [
+                { "frame": { "id": "A" }, "childFrames": [{ "frame": { "id": "A1" } }] },
+                { "frame": { "id": "B" } },
+            ],
+        });
+        let mut frames = HashSet::new();
+        collect_frame_ids(&tree, &mut frames);
+        let expected: HashSet<String> = ["MAIN", "A", "A1", "B"].into_iter().map(String::from).collect();
+        assert_eq!(frames, expected);
+    }
+
+    #[tokio::test]
+    async fn test_path_fails_for_canceled_or_abandoned_downloads() {
+        let (download, sender) = pending_download(Path::new("/nonexistent"));
+        sender.send_replace(DownloadState::Canceled);
+        assert!(matches!(download.path().await, Err(Error::DownloadFailed(_))));
+
+        // The listener going away mid-download is an error, not a hang
+        let (download, sender) = pending_download(Path::new("/nonexistent"));
+        drop(sender);
+        assert!(matches!(download.path().await, Err(Error::DownloadFailed(_))));
+    }
+}
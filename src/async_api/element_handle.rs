@@ -8,25 +8,28 @@ use thirtyfour::prelude::*;
 
 /// Represents a handle to an in-page DOM element
 ///
-/// ElementHandles are created by querying the page or locator. They point to a
-/// specific element in the page and can be used to interact with it.
+/// ElementHandles are created by [`Page::query_selector`](crate::async_api::Page::query_selector)
+/// and [`Page::query_selector_all`](crate::async_api::Page::query_selector_all).
+/// They are snapshots: actions run immediately against the element that was
+/// found, without waiting or re-querying, and fail once it is detached.
 ///
 /// # Example
 /// ```no_run
 /// # use sparkle::async_api::Page;
 /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
-/// // Note: In practice, you'd use Locators instead of ElementHandles
-/// // ElementHandles are lower-level and don't auto-retry
+/// if let Some(banner) = page.query_selector("#cookie-banner").await? {
+///     banner.click(Default::default()).await?;
+/// }
 /// # Ok(())
 /// # }
 /// ```
+#[derive(Debug, Clone)]
 pub struct ElementHandle {
     element: WebElement,
 }
 
 impl ElementHandle {
     /// Create a new ElementHandle from a WebElement
-    #[allow(dead_code)]
     pub(crate) fn new(element: WebElement) -> Self {
         Self { element }
     }
@@ -99,6 +102,14 @@ impl ElementHandle {
         self.text_content().await
     }
 
+    /// Get the element's inner HTML
+    pub async fn inner_html(&self) -> Result<String> {
+        let html = self.element.inner_html().await.map_err(|e| {
+            Error::ActionFailed(format!("Failed to get inner HTML: {}", e))
+        })?;
+        Ok(html)
+    }
+
     /// Get an attribute value
     ///
     /// # Arguments
@@ -183,8 +194,6 @@ impl ElementHandle {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_element_handle_compiles() {
         // Structure compilation test
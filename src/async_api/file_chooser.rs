@@ -0,0 +1,148 @@
+//! File chooser API
+//!
+//! File pickers opened by the page are intercepted over a CDP websocket with
+//! `Page.setInterceptFileChooserDialog`; each `Page.fileChooserOpened` event
+//! becomes a [`FileChooser`] that can be fulfilled with `set_files`.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::core::{Error, Result};
+use crate::driver::WebDriverAdapter;
+
+/// A file picker opened by the page
+///
+/// Returned by [`Page::wait_for_file_chooser`](crate::async_api::Page::wait_for_file_chooser).
+///
+/// # Example
+/// ```no_run
+/// # use sparkle::async_api::Page;
+/// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+/// let (chooser, _) = tokio::try_join!(
+///     page.wait_for_file_chooser(None),
+///     page.click("button.upload", Default::default()),
+/// )?;
+/// chooser.set_files(&["avatar.png"]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct FileChooser {
+    adapter: Arc<WebDriverAdapter>,
+    backend_node_id: i64,
+    multiple: bool,
+}
+
+impl std::fmt::Debug for FileChooser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileChooser")
+            .field("backend_node_id", &self.backend_node_id)
+            .field("multiple", &self.multiple)
+            .finish()
+    }
+}
+
+impl FileChooser {
+    /// Whether the underlying input accepts multiple files
+    pub fn is_multiple(&self) -> bool {
+        self.multiple
+    }
+
+    /// Fulfill the chooser with the given files
+    ///
+    /// Paths are made absolute and must exist. The input's `input` and
+    /// `change` events fire as if the user had picked the files.
+    pub async fn set_files<P: AsRef<Path>>(&self, paths: &[P]) -> Result<()> {
+        if !self.multiple && paths.len() > 1 {
+            return Err(Error::invalid_argument(
+                "Cannot set multiple files on a file chooser that accepts a single file",
+            ));
+        }
+
+        let files = paths
+            .iter()
+            .map(|path| {
+                let path = path.as_ref();
+                std::fs::canonicalize(path)
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .map_err(|e| Error::invalid_argument(format!("Cannot upload {}: {}", path.display(), e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.adapter
+            .execute_cdp_with_params(
+                "DOM.setFileInputFiles",
+                json!({ "files": files, "backendNodeId": self.backend_node_id }),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Background listener intercepting the file pickers opened by a page
+pub(crate) struct FileChooserWatcher {
+    choosers: Mutex<mpsc::UnboundedReceiver<FileChooser>>,
+    task: JoinHandle<()>,
+}
+
+impl FileChooserWatcher {
+    /// Enable file chooser interception and start listening
+    pub(crate) async fn start(adapter: &Arc<WebDriverAdapter>) -> Result<Self> {
+        let mut events = adapter
+            .connect_cdp_events(&[
+                ("Page.enable", json!({})),
+                ("Page.setInterceptFileChooserDialog", json!({ "enabled": true })),
+            ])
+            .await?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let adapter = Arc::clone(adapter);
+        let task = tokio::spawn(async move {
+            while let Some(Ok(message)) = events.next().await {
+                let Message::Text(text) = message else { continue };
+                let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+                if event["method"] != "Page.fileChooserOpened" {
+                    continue;
+                }
+                // Pickers not backed by an <input type=file> (showOpenFilePicker) can't be fulfilled
+                let Some(backend_node_id) = event["params"]["backendNodeId"].as_i64() else { continue };
+                let _ = sender.send(FileChooser {
+                    adapter: Arc::clone(&adapter),
+                    backend_node_id,
+                    multiple: event["params"]["mode"] == "selectMultiple",
+                });
+            }
+        });
+
+        Ok(Self {
+            choosers: Mutex::new(receiver),
+            task,
+        })
+    }
+
+    /// Wait for the next file chooser that has not been handed out yet
+    pub(crate) async fn next(&self, timeout: Duration) -> Result<FileChooser> {
+        let mut choosers = self.choosers.lock().await;
+        match tokio::time::timeout(timeout, choosers.recv()).await {
+            Ok(Some(chooser)) => Ok(chooser),
+            Ok(None) => Err(Error::ActionFailed(
+                "CDP connection closed while waiting for a file chooser".to_string(),
+            )),
+            Err(_) => Err(Error::timeout_duration("wait for file chooser", timeout)),
+        }
+    }
+}
+
+impl Drop for FileChooserWatcher {
+    fn drop(&mut self) {
+        // Closing the websocket also turns interception off again
+        self.task.abort();
+    }
+}
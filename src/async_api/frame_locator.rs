@@ -7,9 +7,11 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use thirtyfour::common::types::ElementRect;
+use thirtyfour::{WebElement, WindowHandle};
 
-use crate::core::{ClickOptions, Error, Result, TypeOptions};
-use crate::driver::WebDriverAdapter;
+use crate::async_api::locator::{matches_accessible_name, role_selector};
+use crate::core::{ClickOptions, Error, GetByRoleOptions, Result, TypeOptions};
+use crate::driver::{WebDriverAdapter, WindowGuard};
 
 /// Represents a locator scoped to an iframe
 ///
@@ -34,6 +36,8 @@ pub struct FrameLocator {
     /// Parent frame locator (for nested iframes)
     parent: Option<Box<FrameLocator>>,
     timeout: Duration,
+    /// Tab of the page that created this frame locator
+    window: Option<WindowHandle>,
 }
 
 impl FrameLocator {
@@ -48,9 +52,16 @@ impl FrameLocator {
             frame_selector: frame_selector.into(),
             parent: None,
             timeout: Duration::from_secs(30),
+            window: None,
         }
     }
 
+    /// Bind this frame locator to the tab of the page that created it
+    pub(crate) fn with_window(mut self, window: Option<WindowHandle>) -> Self {
+        self.window = window;
+        self
+    }
+
     /// Create a nested frame locator (child frame within parent frame)
     ///
     /// # Arguments
@@ -59,11 +70,13 @@ impl FrameLocator {
     fn new_nested(parent: FrameLocator, frame_selector: impl Into<String>) -> Self {
         let adapter = parent.adapter.clone();
         let timeout = parent.timeout;
+        let window = parent.window.clone();
         Self {
             adapter,
             frame_selector: frame_selector.into(),
             parent: Some(Box::new(parent)),
             timeout,
+            window,
         }
     }
 
@@ -82,19 +95,82 @@ impl FrameLocator {
     }
 
     /// Switch to this frame's context
-    async fn switch_to_frame_context(&self) -> Result<()> {
+    ///
+    /// The driver stays on the page's tab until the returned guard is dropped.
+    async fn switch_to_frame_context(&self) -> Result<Option<WindowGuard>> {
         // Switch to parent frame first if nested
-        if let Some(parent) = &self.parent {
+        let window = if let Some(parent) = &self.parent {
             // Box::pin is needed for recursive async calls
-            Box::pin(parent.switch_to_frame_context()).await?;
+            Box::pin(parent.switch_to_frame_context()).await?
         } else {
-            // If no parent, switch to default content first
+            // If no parent, switch to this page's tab and its default content first
+            let window = match &self.window {
+                Some(window) => Some(self.adapter.activate_window(window).await?),
+                None => None,
+            };
             self.adapter.switch_to_default_content().await?;
-        }
+            window
+        };
 
         // Now switch to this frame using the new selector-based method
         self.adapter.switch_to_frame_by_selector(&self.frame_selector).await?;
-        Ok(())
+        Ok(window)
+    }
+
+    /// Evaluate JavaScript inside this frame
+    ///
+    /// Switches into the frame, runs the script with `args` (available as
+    /// `arguments[n]`), and switches back to the top-level document.
+    ///
+    /// # Arguments
+    /// * `expression` - JavaScript code to execute; use `return` to produce a value
+    /// * `args` - Arguments to pass to the script
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let version = page
+    ///     .frame_locator("#widget")
+    ///     .evaluate("return window.widgetVersion;", vec![])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn evaluate(
+        &self,
+        expression: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let _window = self.switch_to_frame_context().await?;
+
+        let result = self.adapter.execute_script_with_args(expression, args).await;
+
+        // Always switch back to default content, even if the script failed
+        self.adapter.switch_to_default_content().await?;
+
+        result
+    }
+
+    /// Get the frame's document as HTML, including its doctype
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let html = page.frame_locator("#preview").content().await?;
+    /// assert!(html.starts_with("<!DOCTYPE html>"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn content(&self) -> Result<String> {
+        let _window = self.switch_to_frame_context().await?;
+
+        let result = self.adapter.document_content().await;
+
+        self.adapter.switch_to_default_content().await?;
+
+        result
     }
 
     /// Create a locator for an element within this frame
@@ -108,6 +184,7 @@ impl FrameLocator {
         ElementInFrame {
             frame_locator: self.clone(),
             element_selector: selector.into(),
+            accessible_name: None,
             timeout: self.timeout,
         }
     }
@@ -129,9 +206,15 @@ impl FrameLocator {
 
     /// Locate element by role
     pub fn get_by_role(&self, role: impl Into<String>) -> ElementInFrame {
+        self.get_by_role_with_options(role, GetByRoleOptions::default())
+    }
+
+    /// Locate element by role, narrowed by ARIA state and accessible name
+    pub fn get_by_role_with_options(&self, role: impl Into<String>, options: GetByRoleOptions) -> ElementInFrame {
         let role = role.into();
-        let selector = format!("[role='{}']", role);
-        self.locator(selector)
+        let mut element = self.locator(role_selector(&role, &options));
+        element.accessible_name = options.name.map(|name| (name, options.exact.unwrap_or(false)));
+        element
     }
 
     /// Locate element by label text
@@ -164,19 +247,35 @@ impl FrameLocator {
 pub struct ElementInFrame {
     frame_locator: FrameLocator,
     element_selector: String,
+    /// Accessible name and `exact` flag from `get_by_role_with_options`
+    accessible_name: Option<(String, bool)>,
     timeout: Duration,
 }
 
 impl ElementInFrame {
+    /// Find the element, assuming the frame context is already active
+    async fn find_element(&self) -> Result<WebElement> {
+        let adapter = &self.frame_locator.adapter;
+        let Some((name, exact)) = &self.accessible_name else {
+            return adapter.find_element(&self.element_selector).await;
+        };
+
+        for element in adapter.find_elements(&self.element_selector).await? {
+            if matches_accessible_name(adapter, &element, name, *exact).await? {
+                return Ok(element);
+            }
+        }
+        Err(Error::element_not_found(format!(
+            "{} with accessible name '{}'",
+            self.element_selector, name
+        )))
+    }
+
     pub(crate) async fn element_rect(&self) -> Result<ElementRect> {
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
 
         let result = async {
-            let element = self
-                .frame_locator
-                .adapter
-                .find_element(&self.element_selector)
-                .await?;
+            let element = self.find_element().await?;
             element.rect().await.map_err(Error::from)
         }
         .await;
@@ -189,11 +288,11 @@ impl ElementInFrame {
     /// Click the element within the frame
     pub async fn click(&self, _options: ClickOptions) -> Result<()> {
         // Switch to frame context
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
         
         // Find and click element, ensuring we always switch back to default content
         let result = async {
-            let element = self.frame_locator.adapter.find_element(&self.element_selector).await?;
+            let element = self.find_element().await?;
             element.click().await?;
             Ok(())
         }.await;
@@ -207,11 +306,11 @@ impl ElementInFrame {
     /// Fill text into an input element within the frame
     pub async fn fill(&self, text: &str) -> Result<()> {
         // Switch to frame context
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
         
         // Find and fill element, ensuring we always switch back
         let result = async {
-            let element = self.frame_locator.adapter.find_element(&self.element_selector).await?;
+            let element = self.find_element().await?;
             element.clear().await?;
             element.send_keys(text).await?;
             Ok(())
@@ -232,11 +331,11 @@ impl ElementInFrame {
     /// Get the text content of the element
     pub async fn text_content(&self) -> Result<Option<String>> {
         // Switch to frame context
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
         
         // Find element and get text, ensuring we always switch back
         let result = async {
-            let element = self.frame_locator.adapter.find_element(&self.element_selector).await?;
+            let element = self.find_element().await?;
             let text = element.text().await?;
             Ok(Some(text))
         }.await;
@@ -255,11 +354,11 @@ impl ElementInFrame {
     /// Get an attribute value
     pub async fn get_attribute(&self, name: &str) -> Result<Option<String>> {
         // Switch to frame context
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
         
         // Find element and get attribute, ensuring we always switch back
         let result = async {
-            let element = self.frame_locator.adapter.find_element(&self.element_selector).await?;
+            let element = self.find_element().await?;
             let attr = element.attr(name).await?;
             Ok(attr)
         }.await;
@@ -273,11 +372,11 @@ impl ElementInFrame {
     /// Check if element is visible
     pub async fn is_visible(&self) -> Result<bool> {
         // Switch to frame context
-        self.frame_locator.switch_to_frame_context().await?;
+        let _window = self.frame_locator.switch_to_frame_context().await?;
         
         // Find element and check visibility, ensuring we always switch back
         let result = async {
-            let element = self.frame_locator.adapter.find_element(&self.element_selector).await?;
+            let element = self.find_element().await?;
             let visible = element.is_displayed().await?;
             Ok(visible)
         }.await;
@@ -298,7 +397,7 @@ impl ElementInFrame {
                 _ => {
                     if start.elapsed() >= self.timeout {
                         return Err(Error::timeout_duration(
-                            &format!("element not visible: {}", self.element_selector),
+                            format!("element not visible: {}", self.element_selector),
                             self.timeout,
                         ));
                     }
@@ -311,8 +410,6 @@ impl ElementInFrame {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     // Note: Tests require a mock WebDriverAdapter which doesn't exist yet
     // These tests will be skipped for now
     
@@ -3,12 +3,374 @@
 //! Locators are the central piece of Playwright's auto-waiting and retry-ability.
 //! Locators represent a way to find element(s) on the page at any moment.
 
-use crate::core::{ClickOptions, Error, Result, TypeOptions};
-use crate::driver::WebDriverAdapter;
+use crate::async_api::Touchscreen;
+use crate::core::{
+    ClickOptions, Clip, Error, GetByRoleOptions, KeyboardModifier, MouseButton, NotActionableReason, Position, Result,
+    ScreenshotOptions, TapOptions, TypeOptions,
+};
+use crate::driver::{WebDriverAdapter, WindowGuard};
+use derive_builder::Builder;
+use regex::Regex;
 use std::sync::Arc;
 use std::time::Duration;
 use thirtyfour::prelude::*;
 
+/// Text matcher used by locator filters
+///
+/// Plain strings match a case-insensitive substring of the element's text
+/// (with whitespace normalized), regular expressions are matched as-is.
+#[derive(Debug, Clone)]
+pub enum TextMatch {
+    /// Case-insensitive substring match
+    Text(String),
+    /// Regular expression match
+    Regex(Regex),
+}
+
+impl TextMatch {
+    /// Check whether the given element text matches
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            TextMatch::Text(expected) => {
+                let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+                normalize(text).contains(&normalize(expected))
+            }
+            TextMatch::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+impl From<&str> for TextMatch {
+    fn from(text: &str) -> Self {
+        TextMatch::Text(text.to_string())
+    }
+}
+
+impl From<String> for TextMatch {
+    fn from(text: String) -> Self {
+        TextMatch::Text(text)
+    }
+}
+
+impl From<Regex> for TextMatch {
+    fn from(regex: Regex) -> Self {
+        TextMatch::Regex(regex)
+    }
+}
+
+/// Options for [`Locator::filter`]
+#[derive(Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct FilterOptions {
+    /// Keep only elements whose text matches
+    pub has_text: Option<TextMatch>,
+
+    /// Keep only elements whose text does not match
+    pub has_not_text: Option<TextMatch>,
+
+    /// Keep only elements containing a descendant matching this locator
+    pub has: Option<Locator>,
+
+    /// Keep only elements without a descendant matching this locator
+    pub has_not: Option<Locator>,
+}
+
+/// Native elements carrying an implicit ARIA role
+fn implicit_role_selectors(role: &str) -> &'static [&'static str] {
+    match role {
+        "button" => &[
+            "button",
+            "input[type='button']",
+            "input[type='submit']",
+            "input[type='reset']",
+            "input[type='image']",
+            "summary",
+        ],
+        "checkbox" => &["input[type='checkbox']"],
+        "radio" => &["input[type='radio']"],
+        "link" => &["a[href]", "area[href]"],
+        "textbox" => &[
+            "input:not([type])",
+            "input[type='text']",
+            "input[type='email']",
+            "input[type='tel']",
+            "input[type='url']",
+            "textarea",
+        ],
+        "searchbox" => &["input[type='search']"],
+        "combobox" => &["select:not([multiple])"],
+        "listbox" => &["select[multiple]"],
+        "option" => &["option"],
+        "heading" => &["h1", "h2", "h3", "h4", "h5", "h6"],
+        "list" => &["ul", "ol"],
+        "listitem" => &["li"],
+        "img" => &["img"],
+        "table" => &["table"],
+        "row" => &["tr"],
+        "cell" => &["td"],
+        "columnheader" => &["th"],
+        "navigation" => &["nav"],
+        "main" => &["main"],
+        "form" => &["form"],
+        "dialog" => &["dialog"],
+        "article" => &["article"],
+        "slider" => &["input[type='range']"],
+        "spinbutton" => &["input[type='number']"],
+        "progressbar" => &["progress"],
+        _ => &[],
+    }
+}
+
+/// Build a CSS selector matching elements with the given ARIA role and states
+///
+/// Matches both explicit `[role=...]` elements and native elements with the
+/// same implicit role (unless they override it with their own `role`).
+pub(crate) fn role_selector(role: &str, options: &GetByRoleOptions) -> String {
+    let role = role.trim().to_lowercase();
+    let mut alternatives = vec![format!("[role='{}']", role)];
+    alternatives.extend(
+        implicit_role_selectors(&role)
+            .iter()
+            .map(|selector| format!("{}:not([role])", selector)),
+    );
+
+    let mut selector = if alternatives.len() == 1 {
+        alternatives.remove(0)
+    } else {
+        format!(":is({})", alternatives.join(", "))
+    };
+
+    if let Some(checked) = options.checked {
+        selector.push_str(if checked {
+            ":is(:checked, [aria-checked='true'])"
+        } else {
+            ":not(:checked):not([aria-checked='true'])"
+        });
+    }
+    if let Some(disabled) = options.disabled {
+        selector.push_str(if disabled {
+            ":is(:disabled, [aria-disabled='true'])"
+        } else {
+            ":not(:disabled):not([aria-disabled='true'])"
+        });
+    }
+    if let Some(expanded) = options.expanded {
+        selector.push_str(&format!("[aria-expanded='{}']", expanded));
+    }
+    if let Some(pressed) = options.pressed {
+        selector.push_str(if pressed {
+            "[aria-pressed='true']"
+        } else {
+            ":not([aria-pressed='true'])"
+        });
+    }
+    if let Some(selected) = options.selected {
+        selector.push_str(if selected {
+            ":is(option:checked, [aria-selected='true'])"
+        } else {
+            ":not(option:checked):not([aria-selected='true'])"
+        });
+    }
+
+    selector
+}
+
+/// Check an element's accessible name against `name`
+///
+/// Approximates the accessible name computation: `aria-labelledby`,
+/// `aria-label`, associated `<label>`s, button values, `alt`, text content,
+/// then `title`/`placeholder`.
+const ACCESSIBLE_NAME_MATCH_JS: &str = r#"
+    const [el, expected, exact] = arguments;
+    const normalize = (s) => (s || '').replace(/\s+/g, ' ').trim();
+    const accessibleName = () => {
+        const labelledBy = el.getAttribute('aria-labelledby');
+        if (labelledBy) {
+            const text = labelledBy.split(/\s+/)
+                .map((id) => document.getElementById(id))
+                .filter(Boolean)
+                .map((ref) => ref.textContent)
+                .join(' ');
+            if (normalize(text)) return text;
+        }
+        const label = el.getAttribute('aria-label');
+        if (normalize(label)) return label;
+        if (el.labels && el.labels.length) {
+            return Array.from(el.labels).map((l) => l.textContent).join(' ');
+        }
+        if (el instanceof HTMLInputElement) {
+            if (['button', 'submit', 'reset'].includes(el.type)) {
+                return el.value || { submit: 'Submit', reset: 'Reset' }[el.type] || '';
+            }
+            if (el.type === 'image') return el.alt || '';
+        }
+        if (el instanceof HTMLImageElement) return el.alt || '';
+        if (!(el instanceof HTMLInputElement || el instanceof HTMLTextAreaElement || el instanceof HTMLSelectElement)) {
+            const text = el.textContent;
+            if (normalize(text)) return text;
+        }
+        return el.getAttribute('title') || el.getAttribute('placeholder') || '';
+    };
+    const actual = normalize(accessibleName());
+    return exact
+        ? actual === normalize(expected)
+        : actual.toLowerCase().includes(normalize(expected).toLowerCase());
+"#;
+
+/// Whether `element`'s accessible name matches `name`
+pub(crate) async fn matches_accessible_name(
+    adapter: &WebDriverAdapter,
+    element: &WebElement,
+    name: &str,
+    exact: bool,
+) -> Result<bool> {
+    let matched = adapter
+        .execute_script_with_args(
+            ACCESSIBLE_NAME_MATCH_JS,
+            vec![element.to_json()?, serde_json::json!(name), serde_json::json!(exact)],
+        )
+        .await?;
+    Ok(matched.as_bool().unwrap_or(false))
+}
+
+/// Draws an outlined box over each element of `arguments[0]`
+///
+/// The boxes are absolutely positioned at the elements' bounding rects in
+/// document coordinates, ignore pointer events and are tagged with
+/// `data-sparkle-highlight` so [`HIDE_HIGHLIGHTS_JS`] can find them.
+const HIGHLIGHT_JS: &str = r#"
+    for (const el of arguments[0]) {
+        const rect = el.getBoundingClientRect();
+        const box = document.createElement('div');
+        box.setAttribute('data-sparkle-highlight', '');
+        Object.assign(box.style, {
+            position: 'absolute',
+            left: `${rect.left + scrollX}px`,
+            top: `${rect.top + scrollY}px`,
+            width: `${rect.width}px`,
+            height: `${rect.height}px`,
+            boxSizing: 'border-box',
+            border: '2px solid #ff00ff',
+            background: 'rgba(255, 0, 255, 0.15)',
+            pointerEvents: 'none',
+            zIndex: '2147483647',
+        });
+        document.documentElement.appendChild(box);
+    }
+"#;
+
+/// Removes every box drawn by [`HIGHLIGHT_JS`]
+pub(crate) const HIDE_HIGHLIGHTS_JS: &str = r#"
+    document.querySelectorAll('[data-sparkle-highlight]').forEach((box) => box.remove());
+"#;
+
+/// Whether `arguments[0]` is disabled natively, through an ancestor
+/// `<fieldset disabled>`, or with `aria-disabled`
+const IS_DISABLED_JS: &str = r#"
+    const el = arguments[0];
+    return el.disabled === true || !!el.closest('fieldset:disabled') || el.getAttribute('aria-disabled') === 'true';
+"#;
+
+/// Whether `arguments[0]` accepts text input: an enabled, writable form field
+/// or a `contenteditable` element
+const IS_EDITABLE_JS: &str = r#"
+    const el = arguments[0];
+    if (el.disabled === true || el.closest('fieldset:disabled') || el.getAttribute('aria-disabled') === 'true') return false;
+    if (el.isContentEditable) return true;
+    if (!['INPUT', 'TEXTAREA', 'SELECT'].includes(el.tagName)) return false;
+    return !el.readOnly && el.getAttribute('aria-readonly') !== 'true';
+"#;
+
+/// The live DOM property `arguments[1]` of `arguments[0]`
+const GET_PROPERTY_JS: &str = "return arguments[0][arguments[1]];";
+
+/// Forced pointer input for elements that can't receive real pointer events
+///
+/// Scrolls `arguments[0]` into view and hit-tests the target point. Returns
+/// `'native'` when the element would get real input there; otherwise (e.g.
+/// `pointer-events: none`, or covered by an overlay) dispatches the pointer,
+/// mouse and click events on the element directly and returns `'dispatched'`.
+const FORCE_DISPATCH_JS: &str = r#"
+    const [el, opts] = arguments;
+    let rect = el.getBoundingClientRect();
+    if (rect.top < 0 || rect.left < 0 || rect.bottom > window.innerHeight || rect.right > window.innerWidth) {
+        el.scrollIntoView({ block: 'center', inline: 'center', behavior: 'instant' });
+        rect = el.getBoundingClientRect();
+    }
+    const x = rect.left + (opts.position ? opts.position.x : rect.width / 2);
+    const y = rect.top + (opts.position ? opts.position.y : rect.height / 2);
+
+    let hit = document.elementFromPoint(x, y);
+    while (hit && hit.shadowRoot) {
+        const inner = hit.shadowRoot.elementFromPoint(x, y);
+        if (!inner || inner === hit) break;
+        hit = inner;
+    }
+    for (let node = hit; node; node = node.parentNode || node.host) {
+        if (node === el) return 'native';
+    }
+
+    const init = {
+        bubbles: true, cancelable: true, composed: true, view: window,
+        clientX: x, clientY: y, button: opts.button,
+        ctrlKey: opts.ctrlKey, shiftKey: opts.shiftKey, altKey: opts.altKey, metaKey: opts.metaKey,
+    };
+    const pointer = { ...init, pointerId: 1, pointerType: opts.touch ? 'touch' : 'mouse', isPrimary: true };
+    // `buttons` is a bitmask in which the middle and right buttons are swapped
+    const buttons = { 0: 1, 1: 4, 2: 2 }[opts.button];
+    for (let detail = 1; detail <= opts.clickCount; detail++) {
+        el.dispatchEvent(new PointerEvent('pointerdown', { ...pointer, detail, buttons }));
+        el.dispatchEvent(new MouseEvent('mousedown', { ...init, detail, buttons }));
+        if (detail === 1 && typeof el.focus === 'function') el.focus();
+        if (opts.button === 2) el.dispatchEvent(new MouseEvent('contextmenu', { ...init, detail, buttons }));
+        el.dispatchEvent(new PointerEvent('pointerup', { ...pointer, detail }));
+        el.dispatchEvent(new MouseEvent('mouseup', { ...init, detail }));
+        el.dispatchEvent(new MouseEvent(opts.button === 0 ? 'click' : 'auxclick', { ...init, detail }));
+    }
+    if (opts.button === 0 && opts.clickCount >= 2) {
+        el.dispatchEvent(new MouseEvent('dblclick', { ...init, detail: 2 }));
+    }
+    return 'dispatched';
+"#;
+
+/// Map a failing state reported by the actionability script to its check
+///
+/// `None` means the element was never found.
+fn not_actionable_reason(state: &str) -> Option<NotActionableReason> {
+    match state {
+        "not found" => None,
+        "hidden" => Some(NotActionableReason::NotVisible),
+        "disabled" => Some(NotActionableReason::NotEnabled),
+        "unstable" => Some(NotActionableReason::NotStable),
+        "intercepted" => Some(NotActionableReason::Intercepted),
+        _ => Some(NotActionableReason::NotEditable),
+    }
+}
+
+/// Position of `nth(index)` among `count` matches, if it exists
+///
+/// Negative indices count from the end, so `-1` is the last match.
+fn resolve_index(index: i64, count: usize) -> Option<usize> {
+    if index >= 0 {
+        usize::try_from(index).ok().filter(|&position| position < count)
+    } else {
+        count.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
+    }
+}
+
+/// A single filter applied to the elements matched by a locator
+#[derive(Clone)]
+enum LocatorFilter {
+    HasText(TextMatch),
+    HasNotText(TextMatch),
+    Has(Box<Locator>),
+    HasNot(Box<Locator>),
+    /// Accessible name for elements of `role`, from `get_by_role()`
+    AccessibleName { role: String, name: String, exact: bool },
+    /// Also matched by this locator, from `and()`
+    And(Box<Locator>),
+}
+
 /// Represents a way to locate elements on a page
 ///
 /// Locators are the recommended way to interact with elements in Playwright.
@@ -28,8 +390,22 @@ pub struct Locator {
     adapter: Arc<WebDriverAdapter>,
     selector: String,
     timeout: Duration,
-    /// nth index for element selection (None = all elements, Some(n) = nth element, 0-based)
-    nth_index: Option<usize>,
+    /// nth index for element selection (None = all elements, Some(n) = nth element,
+    /// 0-based; negative values count from the end)
+    nth_index: Option<i64>,
+    /// Filters narrowing the matched elements, applied before nth_index
+    filters: Vec<LocatorFilter>,
+    /// Tab of the page that created this locator
+    window: Option<WindowHandle>,
+    /// Locator whose elements scope this one (set by `locator()` chaining)
+    parent: Option<Box<Locator>>,
+    /// Whether actions fail when the locator matches more than one element
+    strict: bool,
+    /// Whether the page's context has touch enabled, so `click()` taps
+    touch: bool,
+    /// Locators whose combined matches replace the selector's, from `or()`
+    /// when the selectors can't be joined with a CSS `,`
+    alternatives: Vec<Locator>,
 }
 
 impl Locator {
@@ -44,6 +420,40 @@ impl Locator {
             selector: selector.into(),
             timeout: Duration::from_secs(30),
             nth_index: None,
+            filters: Vec::new(),
+            window: None,
+            parent: None,
+            strict: false,
+            touch: false,
+            alternatives: Vec::new(),
+        }
+    }
+
+    /// Bind this locator to the tab of the page that created it
+    pub(crate) fn with_window(mut self, window: Option<WindowHandle>) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Tap instead of clicking, for pages of a context with `has_touch`
+    pub(crate) fn with_touch(mut self, touch: bool) -> Self {
+        self.touch = touch;
+        self
+    }
+
+    /// Switch the driver to this locator's tab until the guard is dropped
+    async fn activate(&self) -> Result<Option<WindowGuard>> {
+        match &self.window {
+            Some(window) => Ok(Some(self.adapter.activate_window(window).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Switch to this locator's tab and bring it to the front for input
+    async fn activate_for_input(&self) -> Result<Option<WindowGuard>> {
+        match &self.window {
+            Some(window) => Ok(Some(self.adapter.focus_window_for_input(window).await?)),
+            None => Ok(None),
         }
     }
 
@@ -56,6 +466,25 @@ impl Locator {
         self
     }
 
+    /// Enable or disable strict mode for this locator
+    ///
+    /// In strict mode, actions fail with `Error::StrictModeViolation` when the
+    /// locator matches more than one element, unless `nth()`, `first()` or
+    /// `last()` picked one. Pages start from the context's `strict_selectors`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// page.locator("button").strict(true).first().click(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     /// Get the selector string
     pub fn selector(&self) -> &str {
         &self.selector
@@ -91,6 +520,7 @@ impl Locator {
         loop {
             match self.resolve_element().await {
                 Ok(element) => return Ok(element),
+                Err(e @ Error::StrictModeViolation { .. }) => return Err(e),
                 Err(_e) => {
                     if start.elapsed() >= self.timeout {
                         break;
@@ -103,41 +533,209 @@ impl Locator {
         Err(Error::timeout_duration("element not found", self.timeout))
     }
 
-    /// Resolve the actual element based on selector and nth_index
+    /// Resolve the actual element based on selector, filters and nth_index
     async fn resolve_element(&self) -> Result<WebElement> {
+        if self.strict && self.nth_index.is_none() {
+            let mut elements = self.find_elements().await?;
+            return match elements.len() {
+                0 => Err(Error::element_not_found(&self.selector)),
+                1 => Ok(elements.remove(0)),
+                count => Err(Error::strict_mode_violation(&self.selector, count)),
+            };
+        }
+
+        if self.nth_index.is_none() && !self.is_plain() {
+            // Filtered, chained and combined locators resolve to the first remaining element
+            let elements = self.find_elements().await?;
+            return elements.into_iter().next().ok_or_else(|| {
+                Error::element_not_found(&self.selector)
+            });
+        }
+
         if let Some(index) = self.nth_index {
+            if self.is_plain() {
+                return self.resolve_indexed(index).await;
+            }
+
             // Get all elements
-            let elements = self.adapter.find_elements(&self.selector).await?;
+            let elements = self.find_elements().await?;
             
             if elements.is_empty() {
                 return Err(Error::element_not_found(&self.selector));
             }
             
-            // Handle last() case (marked with usize::MAX)
-            if index == usize::MAX {
-                return elements.last().cloned().ok_or_else(|| {
-                    Error::element_not_found(&format!("{}:last", self.selector))
-                });
-            }
-            
-            // Return the nth element (0-based)
-            elements.get(index).cloned().ok_or_else(|| {
-                Error::element_not_found(&format!("{}[{}]", self.selector, index))
-            })
+            // Return the nth element (0-based, negative from the end)
+            resolve_index(index, elements.len())
+                .map(|position| elements[position].clone())
+                .ok_or_else(|| Error::element_not_found(format!("{}[{}]", self.selector, index)))
         } else {
             // Return the first element (default behavior)
+            let _window = self.activate().await?;
             self.adapter.find_element(&self.selector).await
         }
     }
 
+    /// Whether the selector alone decides the matches: no filters, parent or `or()` alternatives
+    fn is_plain(&self) -> bool {
+        self.filters.is_empty() && self.parent.is_none() && self.alternatives.is_empty()
+    }
+
+    /// Resolve `nth()`/`last()` on a plain selector
+    ///
+    /// Negative indices count the matches first; either way only the chosen
+    /// element is fetched.
+    async fn resolve_indexed(&self, index: i64) -> Result<WebElement> {
+        let _window = self.activate().await?;
+        let position = if index >= 0 {
+            Some(index as usize)
+        } else {
+            let count = self.adapter.count_elements(&self.selector).await?;
+            if count == 0 {
+                return Err(Error::element_not_found(&self.selector));
+            }
+            resolve_index(index, count)
+        };
+
+        let element = match position {
+            Some(position) => self.adapter.find_nth_element(&self.selector, position).await?,
+            None => None,
+        };
+        element.ok_or_else(|| Error::element_not_found(format!("{}[{}]", self.selector, index)))
+    }
+
     /// Find all matching elements
     async fn find_elements(&self) -> Result<Vec<WebElement>> {
-        self.adapter.find_elements(&self.selector).await
+        let _window = self.activate().await?;
+        if !self.alternatives.is_empty() {
+            let mut elements = Vec::new();
+            for alternative in &self.alternatives {
+                for element in Box::pin(alternative.resolve_scope()).await? {
+                    if !elements.contains(&element) {
+                        elements.push(element);
+                    }
+                }
+            }
+            return self.apply_filters(elements).await;
+        }
+
+        let elements = match &self.parent {
+            Some(parent) => {
+                // Search within each element the parent resolves to, in document order
+                let mut elements = Vec::new();
+                for root in Box::pin(parent.resolve_scope()).await? {
+                    for element in root.find_all(By::Css(self.selector.as_str())).await? {
+                        if !elements.contains(&element) {
+                            elements.push(element);
+                        }
+                    }
+                }
+                elements
+            }
+            None => self.adapter.find_elements(&self.selector).await?,
+        };
+        self.apply_filters(elements).await
+    }
+
+    /// Elements that scope a chained child locator
+    ///
+    /// A parent with `nth()`/`first()`/`last()` scopes to that single element;
+    /// otherwise every matching element is searched.
+    async fn resolve_scope(&self) -> Result<Vec<WebElement>> {
+        match self.nth_index {
+            Some(_) => self.resolve_element().await.map(|element| vec![element]),
+            None => self.find_elements().await,
+        }
+    }
+
+    /// Drop elements that don't satisfy this locator's filters
+    async fn apply_filters(&self, elements: Vec<WebElement>) -> Result<Vec<WebElement>> {
+        if self.filters.is_empty() {
+            return Ok(elements);
+        }
+
+        // Resolve `and()` locators and accessibility tree lookups once, not per element
+        let mut intersections = Vec::new();
+        for filter in &self.filters {
+            match filter {
+                LocatorFilter::And(other) => intersections.push(Box::pin(other.resolve_scope()).await?),
+                LocatorFilter::AccessibleName { role, name, exact } if self.adapter.has_cdp() => {
+                    match self.adapter.find_elements_by_accessible_name(role, name, *exact).await {
+                        Ok(named) => intersections.push(named),
+                        Err(e) => tracing::debug!("Accessibility tree lookup failed, matching names in page: {}", e),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut matched = Vec::new();
+        for element in elements {
+            if intersections.iter().all(|other| other.contains(&element))
+                && self.element_matches_filters(&element).await?
+            {
+                matched.push(element);
+            }
+        }
+        Ok(matched)
+    }
+
+    async fn element_matches_filters(&self, element: &WebElement) -> Result<bool> {
+        for filter in &self.filters {
+            let keep = match filter {
+                LocatorFilter::HasText(matcher) => matcher.is_match(&element.text().await?),
+                LocatorFilter::HasNotText(matcher) => !matcher.is_match(&element.text().await?),
+                LocatorFilter::Has(inner) => inner.has_match_within(element).await?,
+                LocatorFilter::HasNot(inner) => !inner.has_match_within(element).await?,
+                // Without CDP, approximate the accessible name in the page
+                LocatorFilter::AccessibleName { name, exact, .. } if !self.adapter.has_cdp() => {
+                    matches_accessible_name(&self.adapter, element, name, *exact).await?
+                }
+                // Otherwise checked against the accessibility tree in `apply_filters`
+                LocatorFilter::AccessibleName { .. } => true,
+                // Checked against the whole set in `apply_filters`
+                LocatorFilter::And(_) => true,
+            };
+            if !keep {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check whether any descendant of `root` matches this locator (including its filters)
+    async fn has_match_within(&self, root: &WebElement) -> Result<bool> {
+        Ok(!self.matches_within(root).await?.is_empty())
+    }
+
+    /// Descendants of `root` matching this locator (including its filters)
+    async fn matches_within(&self, root: &WebElement) -> Result<Vec<WebElement>> {
+        let descendants = if self.alternatives.is_empty() {
+            root.find_all(By::Css(self.selector.as_str())).await?
+        } else {
+            let mut descendants = Vec::new();
+            for alternative in &self.alternatives {
+                for element in Box::pin(alternative.matches_within(root)).await? {
+                    if !descendants.contains(&element) {
+                        descendants.push(element);
+                    }
+                }
+            }
+            descendants
+        };
+        // Box the recursion since nested locators can carry their own `has` filters
+        Box::pin(self.apply_filters(descendants)).await
     }
 
     /// Click the element
     ///
-    /// This method waits for the element to be visible and enabled before clicking.
+    /// This method waits for the element to be visible, stable, enabled and not
+    /// covered by another element before clicking, failing with
+    /// `Error::NotActionable` naming the check when it never is.
+    ///
+    /// On pages of a context created with `has_touch`, a plain left click is
+    /// performed as a tap (see [`Locator::tap`]), so handlers listening only
+    /// to touch events fire and no mouse events are sent. Right, middle,
+    /// multiple and modified clicks still use the mouse.
     ///
     /// # Arguments
     /// * `options` - Click options (timeout, modifiers, etc.)
@@ -153,28 +751,186 @@ impl Locator {
     pub async fn click(&self, options: ClickOptions) -> Result<()> {
         let timeout = options.timeout.unwrap_or(self.timeout);
         let start = std::time::Instant::now();
+        let button = options.button.unwrap_or(MouseButton::Left);
+        let click_count = options.click_count.unwrap_or(1);
+        let tap = self.touch
+            && options.modifiers.is_empty()
+            && matches!(button, MouseButton::Left)
+            && click_count == 1;
 
-        // Wait for element and click
-        let element = self.find_element().await.map_err(|_| {
-            Error::timeout_duration(&format!("Timeout waiting for element '{}'", self.selector), timeout)
-        })?;
+        // Wait for the element to be actionable, then click. Forced clicks only
+        // wait for the element, and fall back to dispatching the events in-page
+        // when it can't receive real pointer input. The tab is only held once
+        // the wait is over, so other pages can run commands meanwhile.
+        let element = if options.force == Some(true) {
+            let element = self.clone().timeout(timeout).find_element().await?;
+            let _window = self.activate_for_input().await?;
+            let dom_button = match button {
+                MouseButton::Left => 0,
+                MouseButton::Middle => 1,
+                MouseButton::Right => 2,
+            };
+            if self
+                .force_dispatch(&element, dom_button, click_count, options.position, &options.modifiers, tap)
+                .await?
+            {
+                return Ok(());
+            }
+            element
+        } else {
+            self.wait_for_actionable(timeout, false).await?
+        };
 
         // Check if we have time left
         if start.elapsed() >= timeout {
             return Err(Error::timeout_duration("click", timeout));
         }
 
-        // Perform the click
-        element.click().await.map_err(|e| {
+        let _window = self.activate_for_input().await?;
+        if tap {
+            return self.tap_element(&element, options.position).await;
+        }
+
+        // Perform the click, holding any modifier keys. `element.click()` only
+        // knows single left clicks, so other buttons and counts go through input events.
+        let clicked = if !matches!(button, MouseButton::Left) || click_count > 1 {
+            self.adapter
+                .click_with_button(&element, button, click_count, options.position, &options.modifiers)
+                .await
+        } else if options.modifiers.is_empty() {
+            element.click().await.map_err(Error::from)
+        } else {
+            self.adapter.click_with_modifiers(&element, &options.modifiers).await
+        };
+        clicked.map_err(|e| {
             Error::ActionFailed(format!("Failed to click '{}': {}", self.selector, e))
         })?;
 
         Ok(())
     }
 
+    /// Click the element while holding modifier keys given as a string
+    ///
+    /// Shorthand for setting `ClickOptions::modifiers`; `modifiers` is a
+    /// `+`-separated list such as `"Control+Shift"`.
+    ///
+    /// # Arguments
+    /// * `modifiers` - Modifier names, e.g. `"Control"`, `"Shift+Alt"`, `"ControlOrMeta"`
+    /// * `options` - Click options; parsed modifiers are added to `options.modifiers`
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// // Open the link in a new tab
+    /// locator.click_with_modifiers("Control", Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn click_with_modifiers(&self, modifiers: &str, mut options: ClickOptions) -> Result<()> {
+        options.modifiers.extend(KeyboardModifier::parse_list(modifiers)?);
+        self.click(options).await
+    }
+
+    /// Tap the element with a touch event
+    ///
+    /// Scrolls the element into view and taps its center (or `options.position`).
+    /// Requires a context created with `has_touch` enabled. With
+    /// `options.force`, an element that can't receive pointer events (e.g.
+    /// `pointer-events: none`) gets the touch pointer and click events
+    /// dispatched on it directly.
+    ///
+    /// # Arguments
+    /// * `options` - Tap options (timeout, position)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// locator.tap(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn tap(&self, options: TapOptions) -> Result<()> {
+        let locator = match options.timeout {
+            Some(timeout) => self.clone().timeout(timeout),
+            None => self.clone(),
+        };
+        let element = locator.find_element().await?;
+        let _window = self.activate_for_input().await?;
+
+        if options.force == Some(true)
+            && self.force_dispatch(&element, 0, 1, options.position, &[], true).await?
+        {
+            return Ok(());
+        }
+
+        self.tap_element(&element, options.position).await
+    }
+
+    /// Scroll `element` into view and tap its center, or `position` within it
+    async fn tap_element(&self, element: &WebElement, position: Option<Position>) -> Result<()> {
+        let rect = self
+            .adapter
+            .execute_script_with_args(
+                r#"
+                const el = arguments[0];
+                el.scrollIntoView({ block: 'center', inline: 'center' });
+                const r = el.getBoundingClientRect();
+                return { x: r.left, y: r.top, width: r.width, height: r.height };
+                "#,
+                vec![element.to_json()?],
+            )
+            .await?;
+
+        let value = |key: &str| rect.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let (x, y) = match position {
+            Some(position) => (value("x") + position.x, value("y") + position.y),
+            None => (value("x") + value("width") / 2.0, value("y") + value("height") / 2.0),
+        };
+
+        Touchscreen::new(Arc::clone(&self.adapter)).tap(x, y).await
+    }
+
+    /// Dispatch click events on `element` in-page unless it can receive real input
+    ///
+    /// Returns `true` if the events were dispatched, `false` if the caller
+    /// should perform a real click or tap instead.
+    async fn force_dispatch(
+        &self,
+        element: &WebElement,
+        button: u8,
+        click_count: u32,
+        position: Option<Position>,
+        modifiers: &[KeyboardModifier],
+        touch: bool,
+    ) -> Result<bool> {
+        let options = serde_json::json!({
+            "button": button,
+            "clickCount": click_count.max(1),
+            "position": position,
+            "ctrlKey": modifiers.contains(&KeyboardModifier::Control),
+            "shiftKey": modifiers.contains(&KeyboardModifier::Shift),
+            "altKey": modifiers.contains(&KeyboardModifier::Alt),
+            "metaKey": modifiers.contains(&KeyboardModifier::Meta),
+            "touch": touch,
+        });
+        let outcome = self
+            .adapter
+            .execute_script_with_args(FORCE_DISPATCH_JS, vec![element.to_json()?, options])
+            .await?;
+        if outcome == "dispatched" {
+            tracing::debug!("'{}' can't receive pointer events; dispatched the click in-page", self.selector);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
     /// Fill an input field with text
     ///
-    /// This clears the existing value and types the new text.
+    /// Waits for the element to be visible, enabled and not read-only, then
+    /// clears the existing value and types the new text. If it never becomes
+    /// editable, fails with `Error::NotActionable` naming the failing check.
     ///
     /// # Arguments
     /// * `text` - The text to fill
@@ -188,7 +944,8 @@ impl Locator {
     /// # }
     /// ```
     pub async fn fill(&self, text: &str) -> Result<()> {
-        let element = self.find_element().await?;
+        let element = self.wait_for_editable().await?;
+        let _window = self.activate_for_input().await?;
         
         // Clear existing value
         element.clear().await.map_err(|e| {
@@ -205,24 +962,28 @@ impl Locator {
 
     /// Type text into the element
     ///
-    /// Unlike fill(), this does not clear existing text first.
+    /// Unlike fill(), this does not clear existing text first. Like fill(), it
+    /// waits for the element to be editable.
     ///
     /// # Arguments
     /// * `text` - The text to type
     /// * `options` - Type options (delay, etc.)
     pub async fn r#type(&self, text: &str, options: TypeOptions) -> Result<()> {
-        let element = self.find_element().await?;
+        let element = self.wait_for_editable().await?;
 
         if let Some(delay) = options.delay {
-            // Type with delay between keystrokes
+            // Type with delay between keystrokes, leaving the tab to other pages in between
             for ch in text.chars() {
+                let window = self.activate_for_input().await?;
                 element.send_keys(ch.to_string()).await.map_err(|e| {
                     Error::ActionFailed(format!("Failed to type into '{}': {}", self.selector, e))
                 })?;
+                drop(window);
                 tokio::time::sleep(delay).await;
             }
         } else {
             // Type all at once
+            let _window = self.activate_for_input().await?;
             element.send_keys(text).await.map_err(|e| {
                 Error::ActionFailed(format!("Failed to type into '{}': {}", self.selector, e))
             })?;
@@ -231,76 +992,438 @@ impl Locator {
         Ok(())
     }
 
-    /// Get the text content of the element
+    /// Type text into the element one key press at a time
+    ///
+    /// Every character is dispatched with CDP `Input.dispatchKeyEvent`, so
+    /// `keydown`, `keypress`, `input` and `keyup` handlers fire for each one,
+    /// unlike `fill()` which sets the text in bulk. Existing text is kept.
+    /// Waits for the element to be editable and focuses it first.
+    ///
+    /// # Arguments
+    /// * `text` - The text to type; `'\n'` presses Enter
+    /// * `options` - Delay between key presses and how long to wait for the element
     ///
     /// # Example
     /// ```no_run
+    /// # use std::time::Duration;
     /// # use sparkle::async_api::Locator;
+    /// # use sparkle::core::TypeOptionsBuilder;
     /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
-    /// let text = locator.text_content().await?;
-    /// println!("Text: {}", text);
+    /// locator
+    ///     .press_sequentially(
+    ///         "sparkle",
+    ///         TypeOptionsBuilder::default().delay(Duration::from_millis(100)).build().unwrap(),
+    ///     )
+    ///     .await?;
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn text_content(&self) -> Result<String> {
-        let element = self.find_element().await?;
-        let text = element.text().await.map_err(|e| {
-            Error::ActionFailed(format!("Failed to get text from '{}': {}", self.selector, e))
-        })?;
-        Ok(text)
+    pub async fn press_sequentially(&self, text: &str, options: TypeOptions) -> Result<()> {
+        let element = self
+            .wait_for_actionable(options.timeout.unwrap_or(self.timeout), true)
+            .await?;
+        let mut window = self.activate_for_input().await?;
+        self.adapter
+            .execute_script_with_args("arguments[0].focus();", vec![element.to_json()?])
+            .await
+            .map_err(|e| Error::ActionFailed(format!("Failed to focus '{}': {}", self.selector, e)))?;
+
+        for (index, ch) in text.chars().enumerate() {
+            if index > 0 {
+                if let Some(delay) = options.delay {
+                    drop(window);
+                    tokio::time::sleep(delay).await;
+                    window = self.activate_for_input().await?;
+                }
+            }
+            self.adapter.type_char(ch).await.map_err(|e| {
+                Error::ActionFailed(format!("Failed to type into '{}': {}", self.selector, e))
+            })?;
+        }
+        Ok(())
     }
 
-    /// Get the inner text of the element
+    /// Check a checkbox or radio button
     ///
-    /// This is an alias for text_content()
-    pub async fn inner_text(&self) -> Result<String> {
-        self.text_content().await
+    /// Clicks the element unless it is already checked, then verifies that it
+    /// became checked. Waits for the element to be actionable like `click`.
+    pub async fn check(&self) -> Result<()> {
+        self.set_checked(true).await
     }
 
-    /// Get an attribute value
+    /// Uncheck a checkbox
+    ///
+    /// Clicks the element if it is checked, then verifies that it became
+    /// unchecked.
+    pub async fn uncheck(&self) -> Result<()> {
+        self.set_checked(false).await
+    }
+
+    /// Check or uncheck a checkbox or radio button
     ///
     /// # Arguments
-    /// * `name` - The attribute name
-    pub async fn get_attribute(&self, name: &str) -> Result<Option<String>> {
-        let element = self.find_element().await?;
-        let attr = element.attr(name).await.map_err(|e| {
-            Error::ActionFailed(format!("Failed to get attribute '{}' from '{}': {}", name, self.selector, e))
-        })?;
-        Ok(attr)
+    /// * `checked` - Desired state
+    pub async fn set_checked(&self, checked: bool) -> Result<()> {
+        if self.is_checked().await.unwrap_or(!checked) == checked {
+            return Ok(());
+        }
+        self.click(ClickOptions::default()).await?;
+        if self.is_checked().await? != checked {
+            return Err(Error::ActionFailed(format!(
+                "Clicking '{}' did not {} it",
+                self.selector,
+                if checked { "check" } else { "uncheck" }
+            )));
+        }
+        Ok(())
     }
 
-    /// Check if the element is visible
+    /// Select an option of a `<select>` element by value or label
+    ///
+    /// Fires `input` and `change` events like a user selection and returns
+    /// the value of the selected option. Fails with `InvalidArgument` if no
+    /// option has the given value or label.
     ///
     /// # Example
     /// ```no_run
     /// # use sparkle::async_api::Locator;
     /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
-    /// if locator.is_visible().await? {
-    ///     println!("Element is visible");
-    /// }
+    /// let value = locator.select_option("Canada").await?;
+    /// println!("Selected {}", value);
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn is_visible(&self) -> Result<bool> {
-        let element = self.find_element().await?;
-        let visible = element.is_displayed().await.map_err(|e| {
-            Error::ActionFailed(format!("Failed to check visibility of '{}': {}", self.selector, e))
-        })?;
-        Ok(visible)
+    pub async fn select_option(&self, value: &str) -> Result<String> {
+        const SELECT_OPTION_JS: &str = r#"
+            const [select, wanted] = arguments;
+            if (!(select instanceof HTMLSelectElement)) return { error: 'not a <select> element' };
+            const option = Array.from(select.options).find((o) => o.value === wanted)
+                || Array.from(select.options).find((o) => o.label.trim() === wanted.trim());
+            if (!option) return { error: 'no option with value or label "' + wanted + '"' };
+            option.selected = true;
+            select.dispatchEvent(new Event('input', { bubbles: true }));
+            select.dispatchEvent(new Event('change', { bubbles: true }));
+            return { value: option.value };
+        "#;
+
+        let element = self.wait_for_actionable(self.timeout, false).await?;
+        let _window = self.activate_for_input().await?;
+        let result = self
+            .adapter
+            .execute_script_with_args(SELECT_OPTION_JS, vec![element.to_json()?, serde_json::json!(value)])
+            .await?;
+
+        if let Some(error) = result["error"].as_str() {
+            return Err(Error::invalid_argument(format!(
+                "Cannot select option in '{}': {}",
+                self.selector, error
+            )));
+        }
+        Ok(result["value"].as_str().unwrap_or_default().to_string())
     }
 
-    /// Check if the element is enabled
-    pub async fn is_enabled(&self) -> Result<bool> {
+    /// Focus the element
+    pub async fn focus(&self) -> Result<()> {
         let element = self.find_element().await?;
-        let enabled = element.is_enabled().await.map_err(|e| {
-            Error::ActionFailed(format!("Failed to check if '{}' is enabled: {}", self.selector, e))
-        })?;
-        Ok(enabled)
+        let _window = self.activate_for_input().await?;
+        self.adapter
+            .execute_script_with_args("arguments[0].focus();", vec![element.to_json()?])
+            .await
+            .map_err(|e| Error::ActionFailed(format!("Failed to focus '{}': {}", self.selector, e)))?;
+        Ok(())
+    }
+
+    /// Select all text inside the element
+    ///
+    /// Inputs and textareas are focused and selected with `select()`; other
+    /// elements get a document selection spanning their contents.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// locator.select_text().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn select_text(&self) -> Result<()> {
+        const SELECT_TEXT_JS: &str = r#"
+            const el = arguments[0];
+            if (el instanceof HTMLInputElement || el instanceof HTMLTextAreaElement) {
+                el.focus();
+                el.select();
+                return;
+            }
+            const range = document.createRange();
+            range.selectNodeContents(el);
+            const selection = window.getSelection();
+            selection.removeAllRanges();
+            selection.addRange(range);
+        "#;
+
+        let element = self.find_element().await?;
+        let _window = self.activate_for_input().await?;
+        self.adapter
+            .execute_script_with_args(SELECT_TEXT_JS, vec![element.to_json()?])
+            .await
+            .map_err(|e| Error::ActionFailed(format!("Failed to select text in '{}': {}", self.selector, e)))?;
+        Ok(())
+    }
+
+    /// Focus the element and press a key or key combination
+    ///
+    /// See `Page::press` for the combo syntax.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// locator.press("Enter").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn press(&self, key: &str) -> Result<()> {
+        self.focus().await?;
+        let _window = self.activate_for_input().await?;
+        self.adapter.press_key(key).await
+    }
+
+    /// Get the text content of the element
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// let text = locator.text_content().await?;
+    /// println!("Text: {}", text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn text_content(&self) -> Result<String> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        let text = element.text().await.map_err(|e| {
+            Error::ActionFailed(format!("Failed to get text from '{}': {}", self.selector, e))
+        })?;
+        Ok(text)
+    }
+
+    /// Get the inner text of the element
+    ///
+    /// This is an alias for text_content()
+    pub async fn inner_text(&self) -> Result<String> {
+        self.text_content().await
+    }
+
+    /// Get an attribute value
+    ///
+    /// # Arguments
+    /// * `name` - The attribute name
+    pub async fn get_attribute(&self, name: &str) -> Result<Option<String>> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        let attr = element.attr(name).await.map_err(|e| {
+            Error::ActionFailed(format!("Failed to get attribute '{}' from '{}': {}", name, self.selector, e))
+        })?;
+        Ok(attr)
+    }
+
+    /// Get a JavaScript property of the element, e.g. `checked` or `value`
+    ///
+    /// Unlike [`get_attribute`](Self::get_attribute), which returns the HTML
+    /// attribute as written in the markup, this reads the live DOM property:
+    /// after the user ticks a checkbox its `checked` property is `true` while
+    /// the `checked` attribute stays absent. The raw value is returned, so
+    /// booleans and numbers keep their JSON type.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let checked = page.locator("#terms").get_property("checked").await?;
+    /// assert_eq!(checked, serde_json::json!(false));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_property(&self, name: &str) -> Result<serde_json::Value> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        self.adapter
+            .execute_script_with_args(GET_PROPERTY_JS, vec![element.to_json()?, serde_json::json!(name)])
+            .await
+            .map_err(|e| {
+                Error::ActionFailed(format!("Failed to get property '{}' from '{}': {}", name, self.selector, e))
+            })
+    }
+
+    /// Run JavaScript with the element as `arguments[0]`
+    ///
+    /// Waits for the element like other actions. As with `Page::evaluate`,
+    /// the script is a function body: use `return` to produce a value, and a
+    /// returned promise is awaited.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let height = page.locator("#feed").evaluate("return arguments[0].scrollHeight;").await?;
+    /// println!("Scroll height: {}", height);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn evaluate(&self, script: &str) -> Result<serde_json::Value> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        self.adapter
+            .execute_script_with_args(script, vec![element.to_json()?])
+            .await
+    }
+
+    /// Run JavaScript with the element as `arguments[0]` and deserialize the result
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let tag: String = page.locator("#submit").evaluate_as("return arguments[0].tagName;").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn evaluate_as<T: serde::de::DeserializeOwned>(&self, script: &str) -> Result<T> {
+        let value = self.evaluate(script).await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Run JavaScript with an array of all matching elements as `arguments[0]`
+    ///
+    /// Doesn't wait for matches: with none, the script gets an empty array.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let lengths = page
+    ///     .locator("li")
+    ///     .evaluate_all("return arguments[0].map((el) => el.textContent.length);")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn evaluate_all(&self, script: &str) -> Result<serde_json::Value> {
+        let elements = self
+            .resolve_scope()
+            .await?
+            .iter()
+            .map(|element| element.to_json())
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.adapter
+            .execute_script_with_args(script, vec![serde_json::Value::Array(elements)])
+            .await
+    }
+
+    /// Check if the element is visible
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// if locator.is_visible().await? {
+    ///     println!("Element is visible");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn is_visible(&self) -> Result<bool> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        let visible = element.is_displayed().await.map_err(|e| {
+            Error::ActionFailed(format!("Failed to check visibility of '{}': {}", self.selector, e))
+        })?;
+        Ok(visible)
+    }
+
+    /// Check if the element is enabled
+    ///
+    /// An element disabled natively, through an ancestor `<fieldset
+    /// disabled>` or with `aria-disabled="true"` is not enabled, matching
+    /// [`Locator::is_editable`].
+    pub async fn is_enabled(&self) -> Result<bool> {
+        let element = self.find_element().await?;
+        Ok(!self.element_is_disabled(&element).await?)
+    }
+
+    /// Check if the element is hidden
+    ///
+    /// The inverse of [`Locator::is_visible`], except that it does not wait:
+    /// an element that isn't in the DOM counts as hidden.
+    pub async fn is_hidden(&self) -> Result<bool> {
+        let element = match self.resolve_element().await {
+            Ok(element) => element,
+            Err(Error::ElementNotFound { .. }) => return Ok(true),
+            Err(e) => return Err(e),
+        };
+        let _window = self.activate().await?;
+        let visible = element.is_displayed().await.map_err(|e| {
+            Error::ActionFailed(format!("Failed to check visibility of '{}': {}", self.selector, e))
+        })?;
+        Ok(!visible)
+    }
+
+    /// Check if the element is disabled
+    ///
+    /// The inverse of [`Locator::is_enabled`], except that it does not wait:
+    /// an element that isn't in the DOM counts as not disabled.
+    pub async fn is_disabled(&self) -> Result<bool> {
+        let element = match self.resolve_element().await {
+            Ok(element) => element,
+            Err(Error::ElementNotFound { .. }) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        self.element_is_disabled(&element).await
+    }
+
+    async fn element_is_disabled(&self, element: &WebElement) -> Result<bool> {
+        let _window = self.activate().await?;
+        let disabled = self
+            .adapter
+            .execute_script_with_args(IS_DISABLED_JS, vec![element.to_json()?])
+            .await
+            .map_err(|e| Error::ActionFailed(format!("Failed to check if '{}' is enabled: {}", self.selector, e)))?;
+        Ok(disabled == serde_json::Value::Bool(true))
+    }
+
+    /// Check if the element accepts text input
+    ///
+    /// True for enabled `input`, `textarea` and `select` elements that are not
+    /// read-only, and for `contenteditable` elements. Elements disabled
+    /// through an ancestor `<fieldset disabled>` or `aria-disabled` are not
+    /// editable.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Locator;
+    /// # async fn example(locator: &Locator) -> sparkle::core::Result<()> {
+    /// if locator.is_editable().await? {
+    ///     locator.fill("hello").await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn is_editable(&self) -> Result<bool> {
+        let element = self.find_element().await?;
+        let _window = self.activate().await?;
+        let editable = self
+            .adapter
+            .execute_script_with_args(IS_EDITABLE_JS, vec![element.to_json()?])
+            .await
+            .map_err(|e| Error::ActionFailed(format!("Failed to check if '{}' is editable: {}", self.selector, e)))?;
+        Ok(editable == serde_json::Value::Bool(true))
     }
 
     /// Check if a checkbox or radio is checked
     pub async fn is_checked(&self) -> Result<bool> {
         let element = self.find_element().await?;
+        let _window = self.activate().await?;
         let checked = element.is_selected().await.map_err(|e| {
             Error::ActionFailed(format!("Failed to check if '{}' is checked: {}", self.selector, e))
         })?;
@@ -309,6 +1432,9 @@ impl Locator {
 
     /// Count the number of matching elements
     ///
+    /// Plain selectors are counted in the page without fetching element
+    /// handles; filtered or chained locators resolve each element.
+    ///
     /// # Example
     /// ```no_run
     /// # use sparkle::async_api::Locator;
@@ -319,6 +1445,11 @@ impl Locator {
     /// # }
     /// ```
     pub async fn count(&self) -> Result<usize> {
+        if self.is_plain() && self.nth_index.is_none() {
+            let _window = self.activate().await?;
+            return self.adapter.count_elements(&self.selector).await;
+        }
+
         let elements = self.find_elements().await?;
         Ok(elements.len())
     }
@@ -326,10 +1457,11 @@ impl Locator {
     /// Get the nth element (0-based index)
     ///
     /// Returns locator to the n-th matching element. It's zero based, nth(0) selects the first element.
+    /// Negative indices count from the end, so nth(-1) is the last element.
     /// This matches Playwright's behavior.
     ///
     /// # Arguments
-    /// * `index` - Zero-based index of the element
+    /// * `index` - Zero-based index of the element, or negative to count from the end
     ///
     /// # Example
     /// ```no_run
@@ -338,15 +1470,16 @@ impl Locator {
     /// // Get the third list item (index 2)
     /// let item = page.locator("li").nth(2);
     /// item.click(Default::default()).await?;
+    ///
+    /// // Get the second-to-last list item
+    /// let item = page.locator("li").nth(-2);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn nth(&self, index: usize) -> Locator {
+    pub fn nth(&self, index: i64) -> Locator {
         Locator {
-            adapter: Arc::clone(&self.adapter),
-            selector: self.selector.clone(),
-            timeout: self.timeout,
             nth_index: Some(index),
+            ..self.clone()
         }
     }
 
@@ -369,7 +1502,8 @@ impl Locator {
 
     /// Get the last matching element
     ///
-    /// Note: This requires counting all elements, so it's less efficient than nth() or first().
+    /// This is equivalent to nth(-1). Plain selectors are counted in the page and only the final match is
+    /// fetched; filtered or chained locators resolve every match.
     ///
     /// # Example
     /// ```no_run
@@ -381,16 +1515,207 @@ impl Locator {
     /// # }
     /// ```
     pub fn last(&self) -> Locator {
-        // Create a special locator that will resolve to the last element
-        // We use a very large index and handle it specially in resolve_element
+        self.nth(-1)
+    }
+
+    /// Locate descendants of this locator's element by ARIA role
+    ///
+    /// Matches explicit `role` attributes as well as native elements with an
+    /// implicit role (e.g. `<button>` for `"button"`), optionally narrowed by
+    /// ARIA state.
+    ///
+    /// # Arguments
+    /// * `role` - ARIA role, e.g. `"button"`, `"checkbox"`, `"menuitem"`
+    /// * `options` - State filters (`checked`, `disabled`, `expanded`, `pressed`, `selected`)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # use sparkle::core::GetByRoleOptionsBuilder;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let expanded = page.locator("nav").get_by_role(
+    ///     "button",
+    ///     GetByRoleOptionsBuilder::default().expanded(true).build().unwrap(),
+    /// );
+    /// expanded.click(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_by_role(&self, role: &str, options: GetByRoleOptions) -> Locator {
+        self.locator(&role_selector(role, &options)).with_accessible_name(role, &options)
+    }
+
+    /// Apply the `name`/`exact` part of [`GetByRoleOptions`] as a filter
+    ///
+    /// With CDP the name is matched against the browser's accessibility tree,
+    /// otherwise it is approximated in the page.
+    pub(crate) fn with_accessible_name(mut self, role: &str, options: &GetByRoleOptions) -> Locator {
+        if let Some(name) = &options.name {
+            self.filters.push(LocatorFilter::AccessibleName {
+                role: role.trim().to_lowercase(),
+                name: name.clone(),
+                exact: options.exact.unwrap_or(false),
+            });
+        }
+        self
+    }
+
+    /// Locate descendants of this locator's elements
+    ///
+    /// The child selector is searched within each element matched by this
+    /// locator, so `nth()`, `first()`, `last()` and `filter()` on the parent
+    /// narrow down where the child is looked up.
+    ///
+    /// # Arguments
+    /// * `selector` - CSS selector, matched against descendants of this locator's elements
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// // Click the button inside the second card
+    /// page.locator(".card").nth(1).locator("button").click(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn locator(&self, selector: &str) -> Locator {
         Locator {
             adapter: Arc::clone(&self.adapter),
-            selector: self.selector.clone(),
+            selector: selector.to_string(),
             timeout: self.timeout,
-            nth_index: Some(usize::MAX), // Marker for "last" element
+            nth_index: None,
+            filters: Vec::new(),
+            window: self.window.clone(),
+            parent: Some(Box::new(self.clone())),
+            strict: self.strict,
+            touch: self.touch,
+            alternatives: Vec::new(),
         }
     }
 
+    /// Narrow this locator down with text and descendant filters
+    ///
+    /// Filters are applied to every element matched by the selector before
+    /// `nth()`/`first()`/`last()` are resolved, and can be chained.
+    ///
+    /// # Arguments
+    /// * `options` - Text (`has_text`, `has_not_text`) and descendant (`has`, `has_not`) filters
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::{FilterOptionsBuilder, Page};
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// // Pick the list row containing "Active" that also has a button
+    /// let row = page.locator("li").filter(
+    ///     FilterOptionsBuilder::default()
+    ///         .has_text("Active")
+    ///         .has(page.locator("button"))
+    ///         .build()
+    ///         .unwrap(),
+    /// );
+    /// assert_eq!(row.count().await?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn filter(&self, options: FilterOptions) -> Locator {
+        let mut filters = self.filters.clone();
+        if let Some(text) = options.has_text {
+            filters.push(LocatorFilter::HasText(text));
+        }
+        if let Some(text) = options.has_not_text {
+            filters.push(LocatorFilter::HasNotText(text));
+        }
+        if let Some(has) = options.has {
+            filters.push(LocatorFilter::Has(Box::new(has)));
+        }
+        if let Some(has_not) = options.has_not {
+            filters.push(LocatorFilter::HasNot(Box::new(has_not)));
+        }
+
+        Locator {
+            filters,
+            ..self.clone()
+        }
+    }
+
+    /// Match elements found by either this locator or `other`
+    ///
+    /// Plain CSS selectors are joined with a `,`, so the matches stay in
+    /// document order. Otherwise, e.g. for XPath, filtered or chained
+    /// locators, `other`'s matches follow this locator's. `nth()`, `first()`,
+    /// `last()` and `filter()` apply to the combined matches.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// // Dismiss whichever dialog showed up
+    /// let dismiss = page.locator("#cookie-banner button").or(page.locator("#newsletter .close"));
+    /// dismiss.first().click(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or(&self, other: Locator) -> Locator {
+        if self.is_css_only() && other.is_css_only() {
+            return Locator {
+                selector: format!("{}, {}", self.selector, other.selector),
+                ..self.clone()
+            };
+        }
+        let selector = format!("{} or {}", self.selector, other.selector);
+        self.union(selector, vec![self.clone(), other])
+    }
+
+    /// Match elements found by both this locator and `other`
+    ///
+    /// CSS can't express an arbitrary intersection, so both locators are
+    /// resolved and only elements present in each are kept. `nth()`,
+    /// `first()` and `last()` apply to the intersection.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let subscribe = page.locator("button").and(page.locator("[title='Subscribe']"));
+    /// subscribe.click(Default::default()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn and(&self, other: Locator) -> Locator {
+        // An `nth()` already picked picks before intersecting
+        let mut locator = match self.nth_index {
+            Some(_) => self.union(self.selector.clone(), vec![self.clone()]),
+            None => self.clone(),
+        };
+        locator.filters.push(LocatorFilter::And(Box::new(other)));
+        locator
+    }
+
+    /// A locator matching everything `alternatives` match, named `selector` in errors
+    fn union(&self, selector: String, alternatives: Vec<Locator>) -> Locator {
+        Locator {
+            adapter: Arc::clone(&self.adapter),
+            selector,
+            timeout: self.timeout,
+            nth_index: None,
+            filters: Vec::new(),
+            window: self.window.clone(),
+            parent: None,
+            strict: self.strict,
+            touch: self.touch,
+            alternatives,
+        }
+    }
+
+    /// Whether this is a bare CSS selector that can be joined into a `,` list
+    fn is_css_only(&self) -> bool {
+        let xpath = self.selector.starts_with("xpath=")
+            || self.selector.starts_with("//")
+            || self.selector.starts_with('(');
+        let deep = self.selector.starts_with("pierce=") || self.selector.contains(">>>");
+        self.is_plain() && self.nth_index.is_none() && !xpath && !deep
+    }
+
     /// Wait for the element to be visible
     pub async fn wait_for(&self) -> Result<()> {
         let start = std::time::Instant::now();
@@ -401,7 +1726,7 @@ impl Locator {
                 Ok(false) | Err(_) => {
                     if start.elapsed() >= self.timeout {
                         return Err(Error::timeout_duration(
-                            &format!("Element '{}' not visible", self.selector),
+                            format!("Element '{}' not visible", self.selector),
                             self.timeout,
                         ));
                     }
@@ -411,20 +1736,539 @@ impl Locator {
         }
     }
 
+    /// Wait until the element is visible, enabled and not read-only
+    ///
+    /// Inputs often render disabled or `readonly` until an SPA hydrates, so
+    /// fill/type poll this instead of failing with "element not interactable".
+    async fn wait_for_editable(&self) -> Result<WebElement> {
+        self.wait_for_actionable(self.timeout, true).await
+    }
+
+    /// Wait until the element passes the actionability checks for an action
+    ///
+    /// Every action needs the element visible and enabled. Clicks (`editable`
+    /// false) also need it stable across two animation frames and not covered
+    /// by another element at its center; fills need it editable. On timeout
+    /// the `NotActionable` error names the check that was still failing.
+    async fn wait_for_actionable(&self, timeout: Duration, editable: bool) -> Result<WebElement> {
+        const ACTIONABILITY_STATE_JS: &str = r#"
+            const [el, forClick, done] = arguments;
+            const style = window.getComputedStyle(el);
+            if (el.getClientRects().length === 0 || style.visibility === 'hidden') return done('hidden');
+            if (el.disabled === true || el.closest('fieldset:disabled') || el.getAttribute('aria-disabled') === 'true') return done('disabled');
+            if (!forClick) {
+                if (el.isContentEditable) return done('editable');
+                if (!('readOnly' in el)) return done('not editable');
+                if (el.readOnly || el.getAttribute('aria-readonly') === 'true') return done('readonly');
+                return done('editable');
+            }
+
+            let before = el.getBoundingClientRect();
+            if (before.top < 0 || before.left < 0 || before.bottom > window.innerHeight || before.right > window.innerWidth) {
+                el.scrollIntoView({ block: 'center', inline: 'center', behavior: 'instant' });
+                before = el.getBoundingClientRect();
+            }
+            let checked = false;
+            const check = () => {
+                if (checked) return;
+                checked = true;
+                const after = el.getBoundingClientRect();
+                if (['x', 'y', 'width', 'height'].some((k) => before[k] !== after[k])) return done('unstable');
+
+                const x = after.left + after.width / 2;
+                const y = after.top + after.height / 2;
+                let hit = document.elementFromPoint(x, y);
+                while (hit && hit.shadowRoot) {
+                    const inner = hit.shadowRoot.elementFromPoint(x, y);
+                    if (!inner || inner === hit) break;
+                    hit = inner;
+                }
+                for (let node = hit; node; node = node.parentNode || node.host) {
+                    if (node === el) return done('clickable');
+                }
+                done('intercepted');
+            };
+            requestAnimationFrame(() => requestAnimationFrame(check));
+            // Background tabs may not run animation frames
+            setTimeout(check, 100);
+        "#;
+
+        let start = std::time::Instant::now();
+        let mut last_state = String::from("not found");
+
+        loop {
+            // Hold the tab for this check only, not across the sleep
+            let window = self.activate().await?;
+            let resolved = match self.resolve_element().await {
+                Err(e @ Error::StrictModeViolation { .. }) => return Err(e),
+                resolved => resolved,
+            };
+            if let Ok(element) = resolved {
+                let state = self
+                    .adapter
+                    .execute_async_script(
+                        ACTIONABILITY_STATE_JS,
+                        vec![element.to_json()?, serde_json::json!(!editable)],
+                    )
+                    .await?;
+                let state = state.as_str().map(String::from).unwrap_or_else(|| state.to_string());
+                let ready = match state.as_str() {
+                    "clickable" | "editable" => true,
+                    "readonly" | "not editable" => !editable,
+                    _ => false,
+                };
+                if ready {
+                    return Ok(element);
+                }
+                last_state = state;
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(match not_actionable_reason(&last_state) {
+                    Some(reason) => Error::not_actionable(&self.selector, reason),
+                    None => Error::timeout_duration(
+                        format!("Timeout waiting for element '{}'", self.selector),
+                        timeout,
+                    ),
+                });
+            }
+            drop(window);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
     /// Take a screenshot of the element
     pub async fn screenshot(&self) -> Result<Vec<u8>> {
         let element = self.find_element().await?;
+        let _window = self.activate_for_input().await?;
         let screenshot = element.screenshot_as_png().await.map_err(|e| {
             Error::ActionFailed(format!("Failed to screenshot '{}': {}", self.selector, e))
         })?;
         Ok(screenshot)
     }
+
+    /// Take a screenshot of the element with options
+    ///
+    /// Scrolls the element into view and captures its bounding box with the
+    /// same implementation as `Page::screenshot_with_options`, including
+    /// `animations: Disabled` and `mask`. `clip` and `full_page` don't apply
+    /// to element screenshots.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # use sparkle::core::{ScreenshotAnimations, ScreenshotOptionsBuilder};
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// let png = page
+    ///     .locator("#card")
+    ///     .screenshot_with_options(
+    ///         ScreenshotOptionsBuilder::default()
+    ///             .animations(ScreenshotAnimations::Disabled)
+    ///             .mask(vec![".timestamp".to_string()])
+    ///             .build()
+    ///             .unwrap(),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn screenshot_with_options(&self, mut options: ScreenshotOptions) -> Result<Vec<u8>> {
+        const ELEMENT_BOX_JS: &str = r#"
+            const el = arguments[0];
+            el.scrollIntoView({ block: 'nearest', inline: 'nearest' });
+            const rect = el.getBoundingClientRect();
+            return [rect.left + scrollX, rect.top + scrollY, rect.width, rect.height];
+        "#;
+
+        if options.clip.is_some() || options.full_page == Some(true) {
+            return Err(Error::invalid_argument("clip and full_page cannot be used for element screenshots"));
+        }
+        let element = self.find_element().await?;
+        let _window = self.activate_for_input().await?;
+        let rect = self
+            .adapter
+            .execute_script_with_args(ELEMENT_BOX_JS, vec![element.to_json()?])
+            .await?;
+        let value = |i: usize| rect[i].as_f64().unwrap_or(0.0);
+        let clip = Clip { x: value(0), y: value(1), width: value(2), height: value(3) };
+        if clip.width <= 0.0 || clip.height <= 0.0 {
+            return Err(Error::ScreenshotFailed(format!("'{}' has an empty bounding box", self.selector)));
+        }
+        options.clip = Some(clip);
+        self.adapter.screenshot_with_options(&options).await
+    }
+
+    /// Draw an overlay around every matching element
+    ///
+    /// A debugging aid for selectors that match the wrong element: each match
+    /// gets a magenta box at its current position. The boxes don't follow
+    /// later layout changes and stay until
+    /// [`Page::hide_highlights`](crate::async_api::Page::hide_highlights) or
+    /// the next navigation. Doesn't wait for matches.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// page.locator("form button").highlight().await?;
+    /// page.screenshot_with_options(Default::default()).await?;
+    /// page.hide_highlights().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn highlight(&self) -> Result<()> {
+        self.evaluate_all(HIGHLIGHT_JS).await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_text_match_substring_is_case_insensitive() {
+        let matcher = TextMatch::from("active");
+        assert!(matcher.is_match("Item 2 -  ACTIVE\n Edit"));
+        assert!(!matcher.is_match("Item 1 - Pending"));
+
+        // Whitespace in both the text and the expectation is normalized
+        assert!(TextMatch::from("Item  2").is_match("Item\n2 - Active"));
+    }
+
+    #[test]
+    fn test_text_match_regex() {
+        let matcher = TextMatch::from(Regex::new(r"^Item \d+ - Active$").unwrap());
+        assert!(matcher.is_match("Item 2 - Active"));
+        assert!(!matcher.is_match("Item 2 - Active soon"));
+    }
+
+    #[test]
+    fn test_role_selector_includes_implicit_roles() {
+        let selector = role_selector("button", &GetByRoleOptions::default());
+        assert!(selector.starts_with(":is([role='button'], button:not([role])"));
+        assert!(selector.contains("input[type='submit']:not([role])"));
+
+        // Roles without native equivalents only match the attribute
+        assert_eq!(role_selector("menuitem", &GetByRoleOptions::default()), "[role='menuitem']");
+    }
+
+    #[test]
+    fn test_role_selector_expanded_button() {
+        let options = crate::core::GetByRoleOptionsBuilder::default()
+            .expanded(true)
+            .build()
+            .unwrap();
+        let selector = role_selector("button", &options);
+        assert!(selector.ends_with("[aria-expanded='true']"));
+
+        let options = crate::core::GetByRoleOptionsBuilder::default()
+            .expanded(false)
+            .build()
+            .unwrap();
+        assert!(role_selector("button", &options).ends_with("[aria-expanded='false']"));
+    }
+
+    #[test]
+    fn test_role_selector_states() {
+        let options = crate::core::GetByRoleOptionsBuilder::default()
+            .checked(true)
+            .disabled(false)
+            .pressed(true)
+            .selected(false)
+            .build()
+            .unwrap();
+        let selector = role_selector("checkbox", &options);
+        assert!(selector.contains(":is(:checked, [aria-checked='true'])"));
+        assert!(selector.contains(":not(:disabled):not([aria-disabled='true'])"));
+        assert!(selector.contains("[aria-pressed='true']"));
+        assert!(selector.contains(":not(option:checked):not([aria-selected='true'])"));
+    }
+
+    #[test]
+    fn test_role_selector_leaves_name_to_filter() {
+        let options = crate::core::GetByRoleOptionsBuilder::default()
+            .name("Save")
+            .exact(true)
+            .build()
+            .unwrap();
+        // The accessible name can't be expressed in CSS, so it's applied as a filter
+        assert_eq!(
+            role_selector("button", &options),
+            role_selector("button", &GetByRoleOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_not_actionable_reason_for_each_state() {
+        assert_eq!(not_actionable_reason("not found"), None);
+        assert_eq!(not_actionable_reason("hidden"), Some(NotActionableReason::NotVisible));
+        assert_eq!(not_actionable_reason("disabled"), Some(NotActionableReason::NotEnabled));
+        assert_eq!(not_actionable_reason("unstable"), Some(NotActionableReason::NotStable));
+        assert_eq!(not_actionable_reason("intercepted"), Some(NotActionableReason::Intercepted));
+        assert_eq!(not_actionable_reason("readonly"), Some(NotActionableReason::NotEditable));
+        assert_eq!(not_actionable_reason("not editable"), Some(NotActionableReason::NotEditable));
+    }
+
+    #[test]
+    fn test_filter_options_builder() {
+        let options = FilterOptionsBuilder::default()
+            .has_text("Active")
+            .has_not_text(Regex::new("Archived").unwrap())
+            .build()
+            .unwrap();
+
+        assert!(matches!(options.has_text, Some(TextMatch::Text(ref t)) if t == "Active"));
+        assert!(matches!(options.has_not_text, Some(TextMatch::Regex(_))));
+        assert!(options.has.is_none());
+        assert!(options.has_not.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_last_fetches_only_the_final_element() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // The count gets just the selector, the lookup also the index
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/execute/sync" if body["args"] == json!(["li"]) => Some(FakeResponse::ok(json!(5000))),
+            "POST /session/fake/execute/sync" => Some(FakeResponse::ok(
+                json!({ "element-6066-11e4-a52e-4f735466cecf": "item-4999" }),
+            )),
+            "GET /session/fake/element/item-4999/text" => Some(FakeResponse::ok(json!("Item 5000"))),
+            _ => None,
+        })
+        .await;
+
+        let text = Locator::new(adapter, "li").last().text_content().await.unwrap();
+        assert_eq!(text, "Item 5000");
+
+        let sent: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, command, body)| (command.clone(), body["args"].clone()))
+            .collect();
+        assert_eq!(
+            sent,
+            vec![
+                ("POST /session/fake/execute/sync".to_string(), json!(["li"])),
+                ("POST /session/fake/execute/sync".to_string(), json!(["li", 4999])),
+                ("GET /session/fake/element/item-4999/text".to_string(), serde_json::Value::Null),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_last_on_empty_set_is_not_found() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        let (adapter, _) = fake_adapter(|command, _| match command {
+            "POST /session/fake/execute/sync" => Some(FakeResponse::ok(json!(0))),
+            _ => None,
+        })
+        .await;
+
+        let error = Locator::new(adapter, "li").last().resolve_element().await.unwrap_err();
+        assert!(matches!(error, Error::ElementNotFound { .. }), "unexpected error: {:?}", error);
+    }
+
+    #[tokio::test]
+    async fn test_aria_disabled_field_is_disabled_and_not_editable() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // <input aria-disabled="true">: WebDriver's enabled state ignores ARIA,
+        // the page scripts don't
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "a" }))),
+            "GET /session/fake/element/a/enabled" => Some(FakeResponse::ok(json!(true))),
+            "POST /session/fake/execute/sync" => Some(match body["script"].as_str() {
+                Some(IS_DISABLED_JS) => FakeResponse::ok(json!(true)),
+                Some(IS_EDITABLE_JS) => FakeResponse::ok(json!(false)),
+                _ => FakeResponse::error(500, "javascript error", "unexpected script"),
+            }),
+            _ => None,
+        })
+        .await;
+        let locator = Locator::new(adapter, "input[aria-disabled]");
+
+        assert!(locator.is_disabled().await.unwrap());
+        assert!(!locator.is_enabled().await.unwrap());
+        assert!(!locator.is_editable().await.unwrap());
+
+        let commands = commands.lock().unwrap();
+        assert!(commands.iter().all(|(_, command, _)| !command.ends_with("/enabled")));
+        let (_, command, body) = commands.last().unwrap();
+        assert_eq!(command, "POST /session/fake/execute/sync");
+        assert_eq!(body["args"], json!([{ "element-6066-11e4-a52e-4f735466cecf": "a" }]));
+    }
+
+    #[tokio::test]
+    async fn test_checked_property_is_read_live_not_from_the_attribute() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // A checkbox the user has ticked: the live property is set, the markup has no attribute
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "a" }))),
+            "GET /session/fake/element/a/attribute/checked" => Some(FakeResponse::ok(json!(null))),
+            "POST /session/fake/execute/sync" => Some(match (body["script"].as_str(), body["args"][1].as_str()) {
+                (Some(GET_PROPERTY_JS), Some("checked")) => FakeResponse::ok(json!(true)),
+                (Some(GET_PROPERTY_JS), Some("value")) => FakeResponse::ok(json!("on")),
+                _ => FakeResponse::error(500, "javascript error", "unexpected script"),
+            }),
+            _ => None,
+        })
+        .await;
+        let locator = Locator::new(adapter, "#terms");
+
+        assert_eq!(locator.get_property("checked").await.unwrap(), json!(true));
+        assert_eq!(locator.get_property("value").await.unwrap(), json!("on"));
+        assert_eq!(locator.get_attribute("checked").await.unwrap(), None);
+
+        let commands = commands.lock().unwrap();
+        let (_, _, body) = commands
+            .iter()
+            .find(|(_, command, _)| command == "POST /session/fake/execute/sync")
+            .unwrap();
+        assert_eq!(body["script"], GET_PROPERTY_JS);
+        assert_eq!(body["args"], json!([{ "element-6066-11e4-a52e-4f735466cecf": "a" }, "checked"]));
+    }
+
+    #[tokio::test]
+    async fn test_forced_right_click_keeps_its_button_on_real_input() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // Nothing covers the element, so the forced click falls back to real input
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "a" }))),
+            "POST /session/fake/execute/sync" if body["script"] == FORCE_DISPATCH_JS => {
+                Some(FakeResponse::ok(json!("native")))
+            }
+            "POST /session/fake/execute/sync" => {
+                Some(FakeResponse::ok(json!({ "x": 10.0, "y": 20.0, "width": 100.0, "height": 40.0 })))
+            }
+            _ => None,
+        })
+        .await;
+
+        let options = ClickOptions {
+            button: Some(MouseButton::Right),
+            force: Some(true),
+            ..Default::default()
+        };
+        Locator::new(adapter, "#row").click(options).await.unwrap();
+
+        let commands = commands.lock().unwrap();
+        assert!(commands.iter().all(|(_, command, _)| !command.ends_with("/click")), "element.click() is left-only");
+        let mouse: Vec<_> = commands
+            .iter()
+            .filter(|(_, _, body)| body["cmd"] == "Input.dispatchMouseEvent")
+            .map(|(_, _, body)| body["params"].clone())
+            .collect();
+        assert_eq!(mouse.len(), 3);
+        assert_eq!(mouse[1]["type"], "mousePressed");
+        assert_eq!(mouse[1]["button"], "right");
+        assert_eq!(mouse[1]["buttons"], 2);
+        assert_eq!((mouse[1]["x"].as_f64(), mouse[1]["y"].as_f64()), (Some(60.0), Some(40.0)));
+        assert_eq!(mouse[2]["type"], "mouseReleased");
+    }
+
+    #[tokio::test]
+    async fn test_waiting_click_leaves_the_tab_to_other_pages() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // The element never becomes actionable
+        let (adapter, _) = fake_adapter(|command, _| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "a" }))),
+            "POST /session/fake/execute/async" => Some(FakeResponse::ok(json!("hidden"))),
+            _ => None,
+        })
+        .await;
+        let waiting = Locator::new(Arc::clone(&adapter), "#spinner")
+            .with_window(Some(WindowHandle::from("tab-a".to_string())))
+            .timeout(Duration::from_secs(5));
+        let waiting = tokio::spawn(async move { waiting.click(Default::default()).await });
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        // Another page gets its tab between actionability checks
+        let start = std::time::Instant::now();
+        let other = tokio::time::timeout(
+            Duration::from_secs(2),
+            adapter.activate_window(&WindowHandle::from("tab-b".to_string())),
+        )
+        .await;
+        assert!(other.is_ok(), "the other page waited for the click to time out");
+        assert!(start.elapsed() < Duration::from_secs(1), "switching tabs took {:?}", start.elapsed());
+        assert!(!waiting.is_finished());
+        waiting.abort();
+    }
+
+    #[tokio::test]
+    async fn test_missing_element_is_hidden_without_waiting() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, _) = fake_adapter(|command, _| match command {
+            "POST /session/fake/element" => Some(FakeResponse::error(404, "no such element", "no such element")),
+            _ => None,
+        })
+        .await;
+        let locator = Locator::new(adapter, "#gone");
+
+        let start = std::time::Instant::now();
+        assert!(locator.is_hidden().await.unwrap());
+        assert!(start.elapsed() < Duration::from_secs(1), "is_hidden waited {:?}", start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_missing_element_is_not_disabled_without_waiting() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, _) = fake_adapter(|command, _| match command {
+            "POST /session/fake/element" => Some(FakeResponse::error(404, "no such element", "no such element")),
+            _ => None,
+        })
+        .await;
+        let locator = Locator::new(adapter, "#gone");
+
+        let start = std::time::Instant::now();
+        assert!(!locator.is_disabled().await.unwrap());
+        assert!(start.elapsed() < Duration::from_secs(1), "is_disabled waited {:?}", start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_highlight_draws_over_every_match() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            "POST /session/fake/elements" => Some(FakeResponse::ok(json!([
+                { "element-6066-11e4-a52e-4f735466cecf": "a" },
+                { "element-6066-11e4-a52e-4f735466cecf": "b" },
+            ]))),
+            _ => None,
+        })
+        .await;
+
+        Locator::new(adapter, "button").highlight().await.unwrap();
+
+        let commands = commands.lock().unwrap();
+        let (_, command, body) = commands.last().unwrap();
+        assert_eq!(command, "POST /session/fake/execute/sync");
+        assert_eq!(body["script"], HIGHLIGHT_JS);
+        assert_eq!(
+            body["args"],
+            json!([[
+                { "element-6066-11e4-a52e-4f735466cecf": "a" },
+                { "element-6066-11e4-a52e-4f735466cecf": "b" },
+            ]])
+        );
+    }
+
     #[test]
     fn test_locator_selector() {
         // Mock test - would need real WebDriver for full testing
@@ -466,9 +2310,266 @@ mod tests {
     }
 
     #[test]
-    fn test_last_uses_special_marker() {
-        // Verify last() uses usize::MAX as a marker
-        // This is an implementation detail
-        // Integration tests will verify actual behavior
+    fn test_resolve_negative_index() {
+        assert_eq!(resolve_index(0, 5), Some(0));
+        assert_eq!(resolve_index(4, 5), Some(4));
+        assert_eq!(resolve_index(5, 5), None);
+        assert_eq!(resolve_index(-1, 5), Some(4));
+        assert_eq!(resolve_index(-2, 5), Some(3));
+        assert_eq!(resolve_index(-5, 5), Some(0));
+        assert_eq!(resolve_index(-6, 5), None);
+        assert_eq!(resolve_index(-1, 0), None);
+        assert_eq!(resolve_index(i64::MIN, 5), None);
+    }
+
+    #[tokio::test]
+    async fn test_or_finds_whichever_button_exists() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // Only the second button is on the page; the XPath lookup finds nothing
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "agree" }))),
+            "POST /session/fake/elements" if body["using"] == "xpath" => Some(FakeResponse::ok(json!([]))),
+            "POST /session/fake/elements" => Some(FakeResponse::ok(json!([
+                { "element-6066-11e4-a52e-4f735466cecf": "agree" }
+            ]))),
+            _ => None,
+        })
+        .await;
+
+        // CSS selectors are joined into one lookup
+        let either = Locator::new(Arc::clone(&adapter), "#accept").or(Locator::new(Arc::clone(&adapter), "#agree"));
+        assert_eq!(either.selector(), "#accept, #agree");
+        let element = either.resolve_element().await.unwrap();
+        assert_eq!(element.element_id().to_string(), "agree");
+        let (_, _, body) = commands.lock().unwrap().last().unwrap().clone();
+        assert_eq!(body["value"], "#accept, #agree");
+
+        // Other selectors take the union, and first() applies to it
+        let either = Locator::new(Arc::clone(&adapter), "//button[@id='accept']")
+            .or(Locator::new(Arc::clone(&adapter), "#agree"))
+            .first();
+        let element = either.resolve_element().await.unwrap();
+        assert_eq!(element.element_id().to_string(), "agree");
+    }
+
+    #[tokio::test]
+    async fn test_and_keeps_elements_matching_both() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        fn element(id: &str) -> serde_json::Value {
+            json!({ "element-6066-11e4-a52e-4f735466cecf": id })
+        }
+
+        // `button` matches a and b, `.primary` matches b and c
+        let (adapter, _) = fake_adapter(|command, body| match command {
+            "POST /session/fake/elements" if body["value"] == "button" => {
+                Some(FakeResponse::ok(json!([element("a"), element("b")])))
+            }
+            "POST /session/fake/elements" => Some(FakeResponse::ok(json!([element("b"), element("c")]))),
+            _ => None,
+        })
+        .await;
+        let both = Locator::new(Arc::clone(&adapter), "button").and(Locator::new(adapter, ".primary"));
+
+        assert_eq!(both.count().await.unwrap(), 1);
+        let first = both.first().resolve_element().await.unwrap();
+        assert_eq!(first.element_id().to_string(), "b");
+
+        // nth() before and() picks the first button (a), which isn't primary
+        let (adapter, _) = fake_adapter(|command, _| match command {
+            "POST /session/fake/execute/sync" => Some(FakeResponse::ok(element("a"))),
+            "POST /session/fake/elements" => Some(FakeResponse::ok(json!([element("b"), element("c")]))),
+            _ => None,
+        })
+        .await;
+        let first_primary = Locator::new(Arc::clone(&adapter), "button")
+            .first()
+            .and(Locator::new(adapter, ".primary"));
+        assert_eq!(first_primary.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_negative_nth_counts_from_the_end() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        // The count gets just the selector, the lookup also the index
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/execute/sync" if body["args"] == json!(["li"]) => Some(FakeResponse::ok(json!(10))),
+            "POST /session/fake/execute/sync" => Some(FakeResponse::ok(
+                json!({ "element-6066-11e4-a52e-4f735466cecf": "item" }),
+            )),
+            _ => None,
+        })
+        .await;
+        let locator = Locator::new(adapter, "li");
+
+        let mut lookups = Vec::new();
+        for target in [locator.nth(-1), locator.last(), locator.nth(-2)] {
+            target.resolve_element().await.unwrap();
+            let sent = std::mem::take(&mut *commands.lock().unwrap());
+            lookups.push(sent.last().unwrap().2["args"].clone());
+        }
+        assert_eq!(lookups[0], json!(["li", 9]));
+        assert_eq!(lookups[1], lookups[0], "nth(-1) should equal last()");
+        assert_eq!(lookups[2], json!(["li", 8]), "nth(-2) should be the second-to-last");
+    }
+
+    #[tokio::test]
+    async fn test_get_by_role_uses_accessibility_tree_names() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+
+        fn element(id: &str) -> serde_json::Value {
+            json!({ "element-6066-11e4-a52e-4f735466cecf": id })
+        }
+
+        // <a href="#" aria-label="Close dialog">X</a> <a href="/docs">X</a>
+        // Backend node 10 is pushed as node 7 ("close"), 11 as node 8 ("docs")
+        let stashed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (adapter, commands) = fake_adapter(move |command, body| match command {
+            "POST /session/fake/goog/cdp/execute" => Some(FakeResponse::ok(match body["cmd"].as_str() {
+                Some("Accessibility.getFullAXTree") => json!({ "nodes": [
+                    { "nodeId": "1", "role": { "value": "RootWebArea" }, "name": { "value": "" },
+                      "childIds": ["2", "3"], "backendDOMNodeId": 1 },
+                    { "nodeId": "2", "parentId": "1", "role": { "value": "link" },
+                      "name": { "value": "Close dialog" }, "backendDOMNodeId": 10 },
+                    { "nodeId": "3", "parentId": "1", "role": { "value": "link" },
+                      "name": { "value": "X" }, "backendDOMNodeId": 11 }
+                ] }),
+                Some("DOM.pushNodesByBackendIdsToFrontend") => {
+                    let ids = body["params"]["backendNodeIds"].as_array().cloned().unwrap_or_default();
+                    json!({ "nodeIds": ids.iter().map(|id| id.as_i64().unwrap() - 3).collect::<Vec<_>>() })
+                }
+                Some("DOM.resolveNode") => {
+                    json!({ "object": { "objectId": format!("node-{}", body["params"]["nodeId"]) } })
+                }
+                Some("Runtime.callFunctionOn") => {
+                    let arguments = body["params"]["arguments"].as_array().cloned().unwrap_or_default();
+                    *stashed.lock().unwrap() = arguments[1..].iter().map(|arg| arg["objectId"].clone()).collect();
+                    json!({ "result": {} })
+                }
+                _ => json!({}),
+            })),
+            // Taking the stash returns the stashed nodes; the role selector matches both links
+            "POST /session/fake/execute/sync" if body["script"].as_str().unwrap_or("").contains("Symbol.for") => {
+                let ids = std::mem::take(&mut *stashed.lock().unwrap());
+                let elements: Vec<_> = ids
+                    .iter()
+                    .map(|id| element(if *id == json!("node-7") { "close" } else { "docs" }))
+                    .collect();
+                Some(FakeResponse::ok(json!(elements)))
+            }
+            "POST /session/fake/elements" => Some(FakeResponse::ok(json!([element("close"), element("docs")]))),
+            _ => None,
+        })
+        .await;
+        let by_role = |name: &str, exact: bool| {
+            let options = crate::core::GetByRoleOptionsBuilder::default()
+                .name(name)
+                .exact(exact)
+                .build()
+                .unwrap();
+            Locator::new(Arc::clone(&adapter), role_selector("link", &options))
+                .strict(true)
+                .with_accessible_name("link", &options)
+        };
+
+        // Found by its aria-label, not by its text
+        let close = by_role("Close dialog", false).resolve_element().await.unwrap();
+        assert_eq!(close.element_id().to_string(), "close");
+        let cdp: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command.ends_with("/goog/cdp/execute"))
+            .map(|(_, _, body)| body.clone())
+            .collect();
+        assert_eq!(cdp[2]["cmd"], "DOM.pushNodesByBackendIdsToFrontend");
+        assert_eq!(cdp[2]["params"]["backendNodeIds"], json!([10]));
+        assert_eq!(cdp[3]["cmd"], "DOM.resolveNode");
+        assert_eq!(cdp[3]["params"]["nodeId"], 7);
+        // The objects are released and the DOM is never written to
+        let group = cdp[3]["params"]["objectGroup"].clone();
+        assert_eq!(cdp[5]["cmd"], "Runtime.releaseObjectGroup");
+        assert_eq!(cdp[5]["params"]["objectGroup"], group);
+        assert!(cdp.iter().all(|body| !body["cmd"].as_str().unwrap().starts_with("DOM.set")));
+
+        // The text "X" only names the link without an aria-label
+        assert_eq!(by_role("X", true).count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_click_taps_in_touch_contexts() {
+        use crate::driver::fake_webdriver::{fake_adapter, CommandLog, FakeResponse};
+        use serde_json::json;
+
+        // The tap target's rect, and navigator.maxTouchPoints for every other script
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            "POST /session/fake/element" => Some(FakeResponse::ok(json!({ "element-6066-11e4-a52e-4f735466cecf": "a" }))),
+            "POST /session/fake/execute/async" => Some(FakeResponse::ok(json!("clickable"))),
+            "POST /session/fake/execute/sync" if body["script"].as_str().unwrap_or("").contains("getBoundingClientRect") => {
+                Some(FakeResponse::ok(json!({ "x": 10.0, "y": 20.0, "width": 100.0, "height": 40.0 })))
+            }
+            "POST /session/fake/execute/sync" => Some(FakeResponse::ok(json!(5))),
+            _ => None,
+        })
+        .await;
+        let touch_commands = |commands: &CommandLog| -> Vec<serde_json::Value> {
+            commands
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, command, body)| {
+                    command.ends_with("/goog/cdp/execute") && body["cmd"] == "Input.dispatchTouchEvent"
+                })
+                .map(|(_, _, body)| body["params"].clone())
+                .collect()
+        };
+
+        let button = Locator::new(Arc::clone(&adapter), "#menu").with_touch(true);
+        button.click(Default::default()).await.unwrap();
+        let touches = touch_commands(&commands);
+        assert_eq!(touches.len(), 2);
+        assert_eq!(touches[0]["type"], "touchStart");
+        assert_eq!(touches[0]["touchPoints"], json!([{ "x": 60.0, "y": 40.0 }]));
+        assert_eq!(touches[1]["type"], "touchEnd");
+        let clicks = |commands: &CommandLog| {
+            commands
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, command, _)| command == "POST /session/fake/element/a/click")
+                .count()
+        };
+        assert_eq!(clicks(&commands), 0, "no mouse click should be sent");
+
+        // A right click has no touch equivalent, and other contexts keep the mouse
+        let right = ClickOptions {
+            button: Some(MouseButton::Right),
+            ..Default::default()
+        };
+        button.click(right).await.unwrap();
+        assert_eq!(touch_commands(&commands).len(), 2);
+        assert_eq!(clicks(&commands), 0, "element.click() would drop the right button");
+        let mouse: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, _, body)| body["cmd"] == "Input.dispatchMouseEvent")
+            .map(|(_, _, body)| body["params"].clone())
+            .collect();
+        assert_eq!(mouse.len(), 3);
+        assert_eq!(mouse[1]["type"], "mousePressed");
+        assert_eq!(mouse[1]["button"], "right");
+        assert_eq!(mouse[2]["type"], "mouseReleased");
+        assert_eq!(mouse[2]["button"], "right");
+
+        Locator::new(adapter, "#menu").click(Default::default()).await.unwrap();
+        assert_eq!(touch_commands(&commands).len(), 2);
+        assert_eq!(clicks(&commands), 1);
     }
 }
@@ -6,18 +6,30 @@
 pub mod browser;
 pub mod browser_type;
 pub mod cdp_session;
+pub mod console;
+pub mod download;
 pub mod element_handle;
+pub mod file_chooser;
 pub mod frame_locator;
 pub mod locator;
 pub mod mouse;
+pub mod network;
 pub mod playwright;
+pub mod touchscreen;
+pub mod web_socket;
 
 // Re-export main types
 pub use browser::{Browser, BrowserContext, Page};
 pub use browser_type::{BrowserName, BrowserType};
 pub use cdp_session::CDPSession;
+pub use console::ConsoleMessage;
+pub use download::Download;
 pub use element_handle::ElementHandle;
+pub use file_chooser::FileChooser;
 pub use frame_locator::{FrameLocator, ElementInFrame};
-pub use locator::Locator;
+pub use locator::{FilterOptions, FilterOptionsBuilder, Locator, TextMatch};
 pub use mouse::{Mouse, MouseClickOptions, MoveOptions, MouseTarget};
+pub use network::{Request, Response};
 pub use playwright::Playwright;
+pub use touchscreen::Touchscreen;
+pub use web_socket::{WebSocket, WebSocketFrame};
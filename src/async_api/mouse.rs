@@ -6,6 +6,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rand::Rng;
 use serde_json::json;
 use thirtyfour::common::types::ElementRect;
 use thirtyfour::prelude::*;
@@ -35,10 +36,19 @@ impl MouseTarget for ElementInFrame {
     }
 }
 
+/// Largest per-step offset, in pixels, added to each point when jitter is on
+const MAX_JITTER: i64 = 1;
+
+/// Largest vertical offset, in pixels, of the Bezier control points
+const CONTROL_POINT_OFFSET: i64 = 10;
+
+/// Range of the default click delay and mousedown duration
+const CLICK_DELAY_MS: std::ops::RangeInclusive<u64> = 50..=150;
+
 /// Mouse emulation for human-like interactions
 pub struct Mouse {
     adapter: Arc<WebDriverAdapter>,
-    position: Arc<RwLock<(i64, i64)>>,
+    position: Arc<RwLock<Option<(i64, i64)>>>,
 }
 
 /// Options for mouse movement
@@ -90,23 +100,38 @@ impl Default for MouseClickOptions {
 }
 
 impl Mouse {
-    /// Create a new Mouse instance
-    pub(crate) fn new(adapter: Arc<WebDriverAdapter>) -> Self {
-        Self {
-            adapter,
-            position: Arc::new(RwLock::new((0, 0))),
+    /// Create a new Mouse instance sharing the page's cursor position
+    pub(crate) fn new(adapter: Arc<WebDriverAdapter>, position: Arc<RwLock<Option<(i64, i64)>>>) -> Self {
+        Self { adapter, position }
+    }
+
+    /// Best-effort current cursor position in CSS pixels
+    ///
+    /// Browsers don't report where the pointer is, so this is the end point
+    /// of the last move dispatched through any `Mouse` of this page. Before
+    /// the first move it is the center of the viewport, which is where a
+    /// resting cursor is most plausibly found. Pointer movements made by
+    /// locator actions are not tracked.
+    pub async fn position(&self) -> (i64, i64) {
+        if let Some(position) = *self.position.read().await {
+            return position;
         }
+
+        let center = self.viewport_center().await;
+        *self.position.write().await.get_or_insert(center)
     }
 
     /// Move mouse to specific coordinates with human-like motion
     ///
+    /// The path starts at the current [`position`](Mouse::position), so
+    /// successive moves trace one continuous trail.
+    ///
     /// # Arguments
     /// * `x` - Target X coordinate
     /// * `y` - Target Y coordinate
     /// * `options` - Movement options
     pub async fn move_to(&self, x: i64, y: i64, options: MoveOptions) -> Result<()> {
-        // Get current mouse position (or assume 0, 0 if can't get)
-        let (start_x, start_y) = (0i64, 0i64);
+        let (start_x, start_y) = self.position().await;
 
         let points = if options.bezier_curve {
             self.generate_bezier_path(start_x, start_y, x, y, options.steps)
@@ -150,12 +175,12 @@ impl Mouse {
         
         // Add slight randomization to not always click exact center
         let offset_x = if options.jitter {
-            (rand::random::<f64>() - 0.5) * (rect.width * 0.3)
+            rand::rng().random_range(-0.5..0.5) * (rect.width * 0.3)
         } else {
             0.0
         };
         let offset_y = if options.jitter {
-            (rand::random::<f64>() - 0.5) * (rect.height * 0.3)
+            rand::rng().random_range(-0.5..0.5) * (rect.height * 0.3)
         } else {
             0.0
         };
@@ -172,18 +197,14 @@ impl Mouse {
     /// * `options` - Click options
     pub async fn click(&self, options: MouseClickOptions) -> Result<()> {
         // Random delay before clicking
-        let delay_before = options.delay_before_ms.unwrap_or_else(|| {
-            50 + (rand::random::<u64>() % 100) // 50-150ms
-        });
+        let delay_before = options.delay_before_ms.unwrap_or_else(random_click_delay_ms);
         sleep(Duration::from_millis(delay_before)).await;
 
         // Perform mousedown
         self.mouse_down().await?;
 
         // Hold mousedown for realistic duration
-        let mousedown_duration = options.mousedown_duration_ms.unwrap_or_else(|| {
-            50 + (rand::random::<u64>() % 100) // 50-150ms
-        });
+        let mousedown_duration = options.mousedown_duration_ms.unwrap_or_else(random_click_delay_ms);
         sleep(Duration::from_millis(mousedown_duration)).await;
 
         // Perform mouseup
@@ -207,6 +228,69 @@ impl Mouse {
         self.click(options).await
     }
 
+    /// Dispatch a mouse wheel event at the current cursor position
+    ///
+    /// Positive `delta_y` scrolls down, positive `delta_x` scrolls right.
+    /// Falls back to `window.scrollBy` when CDP input dispatch is unavailable.
+    ///
+    /// # Arguments
+    /// * `delta_x` - Horizontal scroll amount in pixels
+    /// * `delta_y` - Vertical scroll amount in pixels
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use sparkle::async_api::Page;
+    /// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+    /// // Scroll down by one screen to trigger lazy loading
+    /// page.mouse().wheel(0.0, 800.0).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wheel(&self, delta_x: f64, delta_y: f64) -> Result<()> {
+        let (x, y) = self.position().await;
+        match self
+            .adapter
+            .execute_cdp_with_params(
+                "Input.dispatchMouseEvent",
+                json!({
+                    "type": "mouseWheel",
+                    "x": x,
+                    "y": y,
+                    "deltaX": delta_x,
+                    "deltaY": delta_y
+                }),
+            )
+            .await
+        {
+            Ok(_) => return Ok(()),
+            Err(error) => {
+                tracing::debug!("CDP mouse wheel failed, falling back to JS: {}", error);
+            }
+        }
+
+        let script = format!("window.scrollBy({}, {});", delta_x, delta_y);
+        self.adapter.execute_script(&script).await?;
+        Ok(())
+    }
+
+    /// Center of the viewport, or `(0, 0)` when it can't be measured
+    async fn viewport_center(&self) -> (i64, i64) {
+        let size = self
+            .adapter
+            .execute_script("return [Math.floor(window.innerWidth / 2), Math.floor(window.innerHeight / 2)];")
+            .await;
+        match size.as_ref().ok().and_then(|value| value.as_array()) {
+            Some(center) if center.len() == 2 => (
+                center[0].as_i64().unwrap_or_default(),
+                center[1].as_i64().unwrap_or_default(),
+            ),
+            _ => {
+                tracing::debug!("Could not measure the viewport, starting the cursor at (0, 0)");
+                (0, 0)
+            }
+        }
+    }
+
     /// Generate a Bezier curve path for smooth mouse movement
     fn generate_bezier_path(&self, start_x: i64, start_y: i64, end_x: i64, end_y: i64, steps: usize) -> Vec<(i64, i64)> {
         let mut points = Vec::with_capacity(steps);
@@ -217,9 +301,9 @@ impl Mouse {
         
         // Control points create a natural curve
         let cp1_x = start_x + dx / 4;
-        let cp1_y = start_y + dy / 4 + (rand::random::<i64>() % 20 - 10);
+        let cp1_y = start_y + dy / 4 + rand::rng().random_range(-CONTROL_POINT_OFFSET..=CONTROL_POINT_OFFSET);
         let cp2_x = start_x + 3 * dx / 4;
-        let cp2_y = start_y + 3 * dy / 4 + (rand::random::<i64>() % 20 - 10);
+        let cp2_y = start_y + 3 * dy / 4 + rand::rng().random_range(-CONTROL_POINT_OFFSET..=CONTROL_POINT_OFFSET);
 
         for i in 0..steps {
             let t = i as f64 / steps as f64;
@@ -259,9 +343,11 @@ impl Mouse {
 
     /// Add small random jitter to coordinates
     fn add_jitter(&self, x: i64, y: i64) -> (i64, i64) {
-        let jitter_x = rand::random::<i64>() % 3 - 1; // -1, 0, or 1
-        let jitter_y = rand::random::<i64>() % 3 - 1;
-        (x + jitter_x, y + jitter_y)
+        let mut rng = rand::rng();
+        (
+            x + rng.random_range(-MAX_JITTER..=MAX_JITTER),
+            y + rng.random_range(-MAX_JITTER..=MAX_JITTER),
+        )
     }
 
     /// Low-level mouse move to coordinates
@@ -271,7 +357,7 @@ impl Mouse {
             let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
             match driver.action_chain().move_to(x, y).perform().await {
                 Ok(()) => {
-                    *self.position.write().await = (x, y);
+                    *self.position.write().await = Some((x, y));
                     return Ok(());
                 }
                 Err(error) => {
@@ -295,7 +381,7 @@ impl Mouse {
             .await
         {
             Ok(_) => {
-                *self.position.write().await = (x, y);
+                *self.position.write().await = Some((x, y));
                 return Ok(());
             }
             Err(error) => {
@@ -317,7 +403,7 @@ impl Mouse {
             x, y
         );
         self.adapter.execute_script(&script).await?;
-        *self.position.write().await = (x, y);
+        *self.position.write().await = Some((x, y));
         Ok(())
     }
 
@@ -334,7 +420,7 @@ impl Mouse {
             }
         }
 
-        let (x, y) = *self.position.read().await;
+        let (x, y) = self.position().await;
         match self
             .adapter
             .execute_cdp_with_params(
@@ -382,7 +468,7 @@ impl Mouse {
             }
         }
 
-        let (x, y) = *self.position.read().await;
+        let (x, y) = self.position().await;
         match self
             .adapter
             .execute_cdp_with_params(
@@ -425,57 +511,60 @@ impl Mouse {
     }
 }
 
-// Simple random number generator (using getrandom crate already in dependencies)
-mod rand {
-    use std::sync::atomic::{AtomicU64, Ordering};
-    
-    static SEED: AtomicU64 = AtomicU64::new(0);
-    
-    fn next_u64() -> u64 {
-        let mut seed = SEED.load(Ordering::Relaxed);
-        if seed == 0 {
-            seed = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_nanos() as u64;
-        }
-        
-        // Simple LCG
-        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
-        SEED.store(seed, Ordering::Relaxed);
-        seed
-    }
-    
-    pub fn random<T: 'static>() -> T {
-        let val = next_u64();
-        
-        // Type-specific conversions
-        if std::any::TypeId::of::<T>() == std::any::TypeId::of::<u64>() {
-            unsafe { std::mem::transmute_copy(&val) }
-        } else if std::any::TypeId::of::<T>() == std::any::TypeId::of::<i64>() {
-            let i_val = val as i64;
-            unsafe { std::mem::transmute_copy(&i_val) }
-        } else if std::any::TypeId::of::<T>() == std::any::TypeId::of::<f64>() {
-            // Convert to f64 in range [0.0, 1.0)
-            let f_val = (val >> 11) as f64 * (1.0 / 9007199254740992.0);
-            unsafe { std::mem::transmute_copy(&f_val) }
-        } else {
-            panic!("Unsupported type for random()")
-        }
-    }
+/// Random click delay or mousedown hold in milliseconds
+fn random_click_delay_ms() -> u64 {
+    rand::rng().random_range(CLICK_DELAY_MS)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::driver::fake_webdriver::fake_adapter;
 
-    #[test]
-    fn test_bezier_path_generation() {
-        // This would require a mock adapter
+    async fn fake_mouse() -> Mouse {
+        let (adapter, _) = fake_adapter(|_, _| None).await;
+        Mouse::new(adapter, Arc::new(RwLock::new(None)))
+    }
+
+    #[tokio::test]
+    async fn test_bezier_path_generation() {
+        let mouse = fake_mouse().await;
+        for _ in 0..200 {
+            // A zero-length move isolates the control point offsets
+            let path = mouse.generate_bezier_path(100, 100, 100, 100, 10);
+            assert_eq!(path.len(), 11);
+            assert_eq!(path.last(), Some(&(100, 100)));
+            for (x, y) in path {
+                // Points are truncated, so allow a pixel for float error
+                assert!((x - 100).abs() <= 1, "x should stay on the line: {}", x);
+                assert!((y - 100).abs() <= CONTROL_POINT_OFFSET + 1, "control point offset out of range: {}", y);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_linear_path_generation() {
+        let mouse = fake_mouse().await;
+        let path = mouse.generate_linear_path(0, 0, 100, 50, 4);
+        assert_eq!(path, vec![(0, 0), (25, 12), (50, 25), (75, 37), (100, 50)]);
+    }
+
+    #[tokio::test]
+    async fn test_jitter_stays_within_bounds() {
+        let mouse = fake_mouse().await;
+        let mut offsets = std::collections::HashSet::new();
+        for _ in 0..1000 {
+            let (x, y) = mouse.add_jitter(500, -20);
+            assert!((x - 500).abs() <= MAX_JITTER && (y + 20).abs() <= MAX_JITTER, "jitter out of range: {:?}", (x, y));
+            offsets.insert(x - 500);
+        }
+        assert_eq!(offsets.len(), 3, "jitter should use every offset in -1..=1 without bias");
     }
 
     #[test]
-    fn test_linear_path_generation() {
-        // This would require a mock adapter
+    fn test_click_delays_fall_in_documented_range() {
+        let delays: Vec<u64> = (0..1000).map(|_| random_click_delay_ms()).collect();
+        assert!(delays.iter().all(|delay| (50..=150).contains(delay)), "{:?}", delays);
+        assert!(delays.iter().any(|delay| *delay < 75) && delays.iter().any(|delay| *delay > 125));
     }
 }
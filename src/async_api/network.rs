@@ -0,0 +1,672 @@
+//! Network observation API
+//!
+//! Requests made by a page are observed over a CDP websocket with the Network
+//! domain: `Network.requestWillBeSent` becomes a [`Request`] and
+//! `Network.responseReceived` a [`Response`]. Response bodies are fetched
+//! with `Network.getResponseBody` on the same websocket, since Chrome only
+//! keeps them for the session that enabled the domain.
+//!
+//! On sessions launched with [`Protocol::Bidi`](crate::core::Protocol::Bidi)
+//! the WebDriver BiDi `network.*` events are subscribed to instead and
+//! translated into their CDP counterparts. Response bodies are not available
+//! over BiDi yet.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use base64::prelude::*;
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::core::{Error, Result};
+use crate::driver::WebDriverAdapter;
+
+pub(crate) type RequestHandler = Arc<dyn Fn(Request) + Send + Sync>;
+pub(crate) type ResponseHandler = Arc<dyn Fn(Response) + Send + Sync>;
+
+/// A CDP command for the watcher's websocket and where to send its result
+type Command = (&'static str, Value, oneshot::Sender<Result<Value>>);
+
+/// BiDi network events listened to instead of the CDP Network domain
+const BIDI_NETWORK_EVENTS: [&str; 3] = ["network.beforeRequestSent", "network.responseCompleted", "network.fetchError"];
+
+/// Translate a BiDi network event into the CDP events the registry handles
+fn cdp_events_from_bidi(event: &Value) -> Vec<Value> {
+    let params = &event["params"];
+    let request = &params["request"];
+    let request_id = &request["request"];
+    // Only navigations carry a navigation id
+    let resource_type = if params["navigation"].is_string() { "Document" } else { "Other" };
+    let headers = |headers: &Value| -> Value {
+        headers
+            .as_array()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .filter_map(|header| Some((header["name"].as_str()?.to_string(), header["value"]["value"].clone())))
+                    .collect::<serde_json::Map<_, _>>()
+            })
+            .unwrap_or_default()
+            .into()
+    };
+
+    match event["method"].as_str() {
+        Some("network.beforeRequestSent") => vec![json!({
+            "method": "Network.requestWillBeSent",
+            "params": {
+                "requestId": request_id,
+                "frameId": params["context"],
+                "type": resource_type,
+                "request": { "url": request["url"], "method": request["method"], "headers": headers(&request["headers"]) },
+            },
+        })],
+        Some("network.responseCompleted") => {
+            let response = &params["response"];
+            vec![
+                json!({
+                    "method": "Network.responseReceived",
+                    "params": {
+                        "requestId": request_id,
+                        "frameId": params["context"],
+                        "type": resource_type,
+                        "response": {
+                            "url": response["url"],
+                            "status": response["status"],
+                            "statusText": response["statusText"],
+                            "headers": headers(&response["headers"]),
+                        },
+                    },
+                }),
+                json!({ "method": "Network.loadingFinished", "params": { "requestId": request_id } }),
+            ]
+        }
+        Some("network.fetchError") => {
+            vec![json!({ "method": "Network.loadingFailed", "params": { "requestId": request_id } })]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Header names are lowercased, matching Playwright
+fn headers_from(value: &Value) -> HashMap<String, String> {
+    value
+        .as_object()
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string());
+                    (name.to_lowercase(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+struct RequestData {
+    url: String,
+    method: String,
+    headers: HashMap<String, String>,
+    post_data: Option<String>,
+    resource_type: String,
+}
+
+/// A request made by a page
+///
+/// Passed to the handlers registered with
+/// [`Page::on_request`](crate::async_api::Page::on_request) and available from
+/// [`Response::request`].
+#[derive(Debug, Clone)]
+pub struct Request {
+    inner: Arc<RequestData>,
+}
+
+impl Request {
+    fn from_params(params: &Value) -> Self {
+        let request = &params["request"];
+        Self {
+            inner: Arc::new(RequestData {
+                url: request["url"].as_str().unwrap_or_default().to_string(),
+                method: request["method"].as_str().unwrap_or("GET").to_string(),
+                headers: headers_from(&request["headers"]),
+                post_data: request["postData"].as_str().map(str::to_string),
+                resource_type: params["type"].as_str().unwrap_or("Other").to_lowercase(),
+            }),
+        }
+    }
+
+    /// URL of the request
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    /// HTTP method, e.g. `GET` or `POST`
+    pub fn method(&self) -> &str {
+        &self.inner.method
+    }
+
+    /// Request headers, with lowercase names
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.inner.headers
+    }
+
+    /// Request body, if any
+    pub fn post_data(&self) -> Option<&str> {
+        self.inner.post_data.as_deref()
+    }
+
+    /// What the request loads, e.g. `document`, `script`, `xhr` or `fetch`
+    pub fn resource_type(&self) -> &str {
+        &self.inner.resource_type
+    }
+}
+
+#[derive(Debug)]
+struct ResponseData {
+    request_id: String,
+    url: String,
+    status: u16,
+    status_text: String,
+    headers: HashMap<String, String>,
+    request: Request,
+    /// `Some(true)` once the body has loaded, `Some(false)` if loading failed
+    finished: watch::Receiver<Option<bool>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+/// A response received by a page
+///
+/// Passed to the handlers registered with
+/// [`Page::on_response`](crate::async_api::Page::on_response).
+///
+/// # Example
+/// ```no_run
+/// # use sparkle::async_api::Page;
+/// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+/// page.on_response(|response| {
+///     if response.request().resource_type() == "fetch" {
+///         tokio::spawn(async move {
+///             if let Ok(body) = response.text().await {
+///                 println!("{}: {}", response.url(), body);
+///             }
+///         });
+///     }
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Response {
+    inner: Arc<ResponseData>,
+}
+
+impl Response {
+    /// URL of the response
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    /// HTTP status code
+    pub fn status(&self) -> u16 {
+        self.inner.status
+    }
+
+    /// HTTP status text, e.g. `OK`
+    pub fn status_text(&self) -> &str {
+        &self.inner.status_text
+    }
+
+    /// Whether the status is in the 200-299 range
+    pub fn ok(&self) -> bool {
+        (200..300).contains(&self.inner.status)
+    }
+
+    /// Response headers, with lowercase names
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.inner.headers
+    }
+
+    /// The request this response answers
+    pub fn request(&self) -> &Request {
+        &self.inner.request
+    }
+
+    /// The response body
+    ///
+    /// Waits for the body to finish loading. Fails if loading failed, the
+    /// page was closed, or Chrome no longer holds the body (for example after
+    /// the page navigated away).
+    pub async fn body(&self) -> Result<Vec<u8>> {
+        let mut finished = self.inner.finished.clone();
+        let loaded = finished
+            .wait_for(Option::is_some)
+            .await
+            .map(|state| *state == Some(true))
+            .map_err(|_| Error::PageClosed)?;
+        if !loaded {
+            return Err(Error::ActionFailed(format!("Loading {} failed", self.inner.url)));
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        self.inner
+            .commands
+            .send(("Network.getResponseBody", json!({ "requestId": self.inner.request_id }), sender))
+            .map_err(|_| Error::PageClosed)?;
+        let result = receiver.await.map_err(|_| Error::PageClosed)??;
+
+        let body = result["body"].as_str().unwrap_or_default();
+        if result["base64Encoded"].as_bool() == Some(true) {
+            BASE64_STANDARD
+                .decode(body)
+                .map_err(|e| Error::internal(format!("Invalid response body encoding: {}", e)))
+        } else {
+            Ok(body.as_bytes().to_vec())
+        }
+    }
+
+    /// The response body as text
+    pub async fn text(&self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.body().await?).into_owned())
+    }
+}
+
+#[derive(Default)]
+struct Handlers {
+    request: Vec<RequestHandler>,
+    response: Vec<ResponseHandler>,
+    /// Frame id and receiver of the next document response in that frame
+    navigation: Option<(String, oneshot::Sender<Response>)>,
+}
+
+/// In-flight requests by CDP request id, and the page's handlers
+struct Registry {
+    requests: HashMap<String, Request>,
+    loading: HashMap<String, watch::Sender<Option<bool>>>,
+    handlers: Arc<Mutex<Handlers>>,
+    commands: mpsc::UnboundedSender<Command>,
+}
+
+impl Registry {
+    fn new(commands: mpsc::UnboundedSender<Command>) -> Self {
+        Self {
+            requests: HashMap::new(),
+            loading: HashMap::new(),
+            handlers: Arc::new(Mutex::new(Handlers::default())),
+            commands,
+        }
+    }
+
+    /// Route one CDP event to the handlers
+    fn dispatch(&mut self, event: &Value) {
+        let params = &event["params"];
+        let Some(request_id) = params["requestId"].as_str() else { return };
+
+        match event["method"].as_str() {
+            Some("Network.requestWillBeSent") => {
+                // Redirects reuse the request id, so this replaces the previous hop
+                let request = Request::from_params(params);
+                self.requests.insert(request_id.to_string(), request.clone());
+                let handlers = self.handlers.lock().unwrap().request.clone();
+                for handler in handlers {
+                    handler(request.clone());
+                }
+            }
+            Some("Network.responseReceived") => {
+                let request = match self.requests.get(request_id) {
+                    Some(request) => request.clone(),
+                    // Requests sent before listening started
+                    None => Request::from_params(&json!({
+                        "request": { "url": params["response"]["url"] },
+                        "type": params["type"],
+                    })),
+                };
+                let (sender, finished) = watch::channel(None);
+                self.loading.insert(request_id.to_string(), sender);
+
+                let response = &params["response"];
+                let response = Response {
+                    inner: Arc::new(ResponseData {
+                        request_id: request_id.to_string(),
+                        url: response["url"].as_str().unwrap_or_default().to_string(),
+                        status: response["status"].as_u64().unwrap_or_default() as u16,
+                        status_text: response["statusText"].as_str().unwrap_or_default().to_string(),
+                        headers: headers_from(&response["headers"]),
+                        request,
+                        finished,
+                        commands: self.commands.clone(),
+                    }),
+                };
+                let handlers = {
+                    let mut handlers = self.handlers.lock().unwrap();
+                    let navigated = matches!(
+                        &handlers.navigation,
+                        Some((frame_id, _)) if params["type"] == "Document" && params["frameId"] == frame_id.as_str()
+                    );
+                    if navigated {
+                        if let Some((_, navigation)) = handlers.navigation.take() {
+                            let _ = navigation.send(response.clone());
+                        }
+                    }
+                    handlers.response.clone()
+                };
+                for handler in handlers {
+                    handler(response.clone());
+                }
+            }
+            Some(method @ ("Network.loadingFinished" | "Network.loadingFailed")) => {
+                self.requests.remove(request_id);
+                if let Some(loading) = self.loading.remove(request_id) {
+                    let _ = loading.send(Some(method == "Network.loadingFinished"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Background listener reporting the requests and responses of a page
+pub(crate) struct NetworkWatcher {
+    handlers: Arc<Mutex<Handlers>>,
+    task: JoinHandle<()>,
+}
+
+impl NetworkWatcher {
+    /// Enable the Network domain, or subscribe to BiDi network events if the
+    /// session has BiDi, and start listening
+    pub(crate) async fn start(adapter: &WebDriverAdapter) -> Result<Self> {
+        let bidi = adapter.has_bidi().await;
+        let events = if bidi {
+            adapter.connect_bidi_events(&BIDI_NETWORK_EVENTS).await?
+        } else {
+            adapter.connect_cdp_events(&[("Network.enable", json!({}))]).await?
+        };
+        let (mut sink, mut events) = events.split();
+
+        let (commands, mut command_receiver) = mpsc::unbounded_channel::<Command>();
+        let mut registry = Registry::new(commands);
+        let handlers = Arc::clone(&registry.handlers);
+        let task = tokio::spawn(async move {
+            // Ids below this were used by `connect_cdp_events`
+            let mut next_id = 1000u64;
+            let mut pending: HashMap<u64, oneshot::Sender<Result<Value>>> = HashMap::new();
+            loop {
+                tokio::select! {
+                    message = events.next() => {
+                        let Some(Ok(message)) = message else { break };
+                        let Message::Text(text) = message else { continue };
+                        let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+                        match event["id"].as_u64().and_then(|id| pending.remove(&id)) {
+                            Some(reply) => {
+                                let _ = reply.send(match event.get("error") {
+                                    Some(error) => Err(Error::ActionFailed(format!("CDP command failed: {}", error))),
+                                    None => Ok(event["result"].clone()),
+                                });
+                            }
+                            None if bidi => {
+                                for event in cdp_events_from_bidi(&event) {
+                                    registry.dispatch(&event);
+                                }
+                            }
+                            None => registry.dispatch(&event),
+                        }
+                    }
+                    Some((method, params, reply)) = command_receiver.recv() => {
+                        if bidi {
+                            let _ = reply.send(Err(Error::not_implemented(format!("{} over WebDriver BiDi", method))));
+                            continue;
+                        }
+                        let message = json!({ "id": next_id, "method": method, "params": params });
+                        if sink.send(Message::Text(message.to_string().into())).await.is_err() {
+                            let _ = reply.send(Err(Error::connection_failed("CDP websocket closed")));
+                            continue;
+                        }
+                        pending.insert(next_id, reply);
+                        next_id += 1;
+                    }
+                }
+            }
+        });
+
+        Ok(Self { handlers, task })
+    }
+
+    /// Call `handler` for every request the page makes from now on
+    pub(crate) fn add_request_handler(&self, handler: RequestHandler) {
+        self.handlers.lock().unwrap().request.push(handler);
+    }
+
+    /// Call `handler` for every response the page receives from now on
+    pub(crate) fn add_response_handler(&self, handler: ResponseHandler) {
+        self.handlers.lock().unwrap().response.push(handler);
+    }
+
+    /// Receive the next document response in frame `frame_id`
+    ///
+    /// Redirects only report their final hop, so this is the response a
+    /// navigation of that frame ends with. Replaces any earlier expectation.
+    pub(crate) fn expect_document_response(&self, frame_id: &str) -> oneshot::Receiver<Response> {
+        let (sender, receiver) = oneshot::channel();
+        self.handlers.lock().unwrap().navigation = Some((frame_id.to_string(), sender));
+        receiver
+    }
+
+    /// Drop the handlers and stop listening
+    pub(crate) fn stop(&self) {
+        *self.handlers.lock().unwrap() = Handlers::default();
+        self.task.abort();
+    }
+}
+
+impl Drop for NetworkWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: Value) -> Value {
+        json!({ "method": method, "params": params })
+    }
+
+    fn document_request(registry: &mut Registry) {
+        registry.dispatch(&event(
+            "Network.requestWillBeSent",
+            json!({
+                "requestId": "42",
+                "type": "Document",
+                "request": { "url": "https://example.com/", "method": "GET", "headers": { "Upgrade-Insecure-Requests": "1" } },
+            }),
+        ));
+        registry.dispatch(&event(
+            "Network.responseReceived",
+            json!({
+                "requestId": "42",
+                "type": "Document",
+                "response": {
+                    "url": "https://example.com/",
+                    "status": 200,
+                    "statusText": "OK",
+                    "headers": { "Content-Type": "text/html" },
+                },
+            }),
+        ));
+    }
+
+    #[test]
+    fn test_requests_and_responses_reach_handlers() {
+        let (commands, _) = mpsc::unbounded_channel();
+        let mut registry = Registry::new(commands);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        {
+            let requests = Arc::clone(&requests);
+            let responses = Arc::clone(&responses);
+            let mut handlers = registry.handlers.lock().unwrap();
+            handlers.request.push(Arc::new(move |request: Request| requests.lock().unwrap().push(request)));
+            handlers.response.push(Arc::new(move |response: Response| responses.lock().unwrap().push(response)));
+        }
+
+        document_request(&mut registry);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method(), "GET");
+        assert_eq!(requests[0].resource_type(), "document");
+        assert_eq!(requests[0].headers().get("upgrade-insecure-requests").map(String::as_str), Some("1"));
+
+        let responses = responses.lock().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].status(), 200);
+        assert!(responses[0].ok());
+        assert_eq!(responses[0].headers().get("content-type").map(String::as_str), Some("text/html"));
+        assert!(Arc::ptr_eq(&responses[0].request().inner, &requests[0].inner));
+    }
+
+    #[test]
+    fn test_navigation_receives_main_frame_document_response() {
+        let (commands, _) = mpsc::unbounded_channel();
+        let mut registry = Registry::new(commands);
+        let (sender, mut navigation) = oneshot::channel();
+        registry.handlers.lock().unwrap().navigation = Some(("main".to_string(), sender));
+
+        // An iframe document doesn't complete the main frame's navigation
+        registry.dispatch(&event(
+            "Network.responseReceived",
+            json!({
+                "requestId": "7",
+                "frameId": "child",
+                "type": "Document",
+                "response": { "url": "https://ads.example.com/", "status": 200, "headers": {} },
+            }),
+        ));
+        assert!(navigation.try_recv().is_err());
+
+        registry.dispatch(&event(
+            "Network.responseReceived",
+            json!({
+                "requestId": "8",
+                "frameId": "main",
+                "type": "Document",
+                "response": { "url": "https://example.com/missing", "status": 404, "statusText": "Not Found", "headers": {} },
+            }),
+        ));
+        let response = navigation.try_recv().unwrap();
+        assert_eq!(response.status(), 404);
+        assert!(!response.ok());
+        assert_eq!(response.url(), "https://example.com/missing");
+        assert!(registry.handlers.lock().unwrap().navigation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_body_waits_for_loading_to_finish() {
+        let (commands, mut command_receiver) = mpsc::unbounded_channel();
+        let mut registry = Registry::new(commands);
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        let handler_responses = Arc::clone(&responses);
+        registry
+            .handlers
+            .lock()
+            .unwrap()
+            .response
+            .push(Arc::new(move |response: Response| handler_responses.lock().unwrap().push(response)));
+        document_request(&mut registry);
+        let response = responses.lock().unwrap()[0].clone();
+
+        let body = tokio::spawn(async move { response.text().await });
+        tokio::task::yield_now().await;
+        assert!(command_receiver.try_recv().is_err(), "the body should not be fetched before it has loaded");
+
+        registry.dispatch(&event("Network.loadingFinished", json!({ "requestId": "42" })));
+        let (method, params, reply) = command_receiver.recv().await.unwrap();
+        assert_eq!(method, "Network.getResponseBody");
+        assert_eq!(params["requestId"], "42");
+        reply
+            .send(Ok(json!({ "body": BASE64_STANDARD.encode("<h1>Hi</h1>"), "base64Encoded": true })))
+            .unwrap();
+        assert_eq!(body.await.unwrap().unwrap(), "<h1>Hi</h1>");
+        assert!(registry.requests.is_empty() && registry.loading.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_body_fails_when_loading_failed() {
+        let (commands, _commands) = mpsc::unbounded_channel();
+        let mut registry = Registry::new(commands);
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        let handler_responses = Arc::clone(&responses);
+        registry
+            .handlers
+            .lock()
+            .unwrap()
+            .response
+            .push(Arc::new(move |response: Response| handler_responses.lock().unwrap().push(response)));
+        document_request(&mut registry);
+
+        registry.dispatch(&event("Network.loadingFailed", json!({ "requestId": "42" })));
+        let response = responses.lock().unwrap()[0].clone();
+        assert!(matches!(response.body().await, Err(Error::ActionFailed(_))));
+    }
+
+    #[test]
+    fn test_bidi_events_translate_to_cdp() {
+        let (commands, _) = mpsc::unbounded_channel();
+        let mut registry = Registry::new(commands);
+        let responses = Arc::new(Mutex::new(Vec::new()));
+        let (sender, mut navigation) = oneshot::channel();
+        {
+            let responses = Arc::clone(&responses);
+            let mut handlers = registry.handlers.lock().unwrap();
+            handlers.response.push(Arc::new(move |response: Response| responses.lock().unwrap().push(response)));
+            handlers.navigation = Some(("CONTEXT1".to_string(), sender));
+        }
+
+        let request = json!({
+            "request": "77",
+            "url": "https://example.com/",
+            "method": "GET",
+            "headers": [{ "name": "Accept", "value": { "type": "string", "value": "text/html" } }],
+        });
+        let events = [
+            json!({
+                "type": "event",
+                "method": "network.beforeRequestSent",
+                "params": { "context": "CONTEXT1", "navigation": "nav-1", "request": request },
+            }),
+            json!({
+                "type": "event",
+                "method": "network.responseCompleted",
+                "params": {
+                    "context": "CONTEXT1",
+                    "navigation": "nav-1",
+                    "request": request,
+                    "response": {
+                        "url": "https://example.com/",
+                        "status": 200,
+                        "statusText": "OK",
+                        "headers": [{ "name": "Content-Type", "value": { "type": "string", "value": "text/html" } }],
+                    },
+                },
+            }),
+        ];
+        for event in events.iter().flat_map(cdp_events_from_bidi) {
+            registry.dispatch(&event);
+        }
+
+        let responses = responses.lock().unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].status(), 200);
+        assert_eq!(responses[0].headers().get("content-type").map(String::as_str), Some("text/html"));
+        assert_eq!(responses[0].request().resource_type(), "document");
+        assert_eq!(responses[0].request().headers().get("accept").map(String::as_str), Some("text/html"));
+        assert_eq!(navigation.try_recv().unwrap().url(), "https://example.com/");
+        // The response completed event also finishes loading
+        assert!(registry.requests.is_empty() && registry.loading.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+//! Touchscreen emulation for mobile interactions
+//!
+//! This module dispatches touch events through CDP. The page must belong to
+//! a context created with `has_touch` enabled.
+
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::core::{Error, Result};
+use crate::driver::WebDriverAdapter;
+
+/// Touchscreen input for touch-enabled pages
+///
+/// # Example
+/// ```no_run
+/// # use sparkle::async_api::Page;
+/// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+/// page.touchscreen().tap(120.0, 240.0).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Touchscreen {
+    adapter: Arc<WebDriverAdapter>,
+}
+
+impl Touchscreen {
+    /// Create a new Touchscreen instance
+    pub(crate) fn new(adapter: Arc<WebDriverAdapter>) -> Self {
+        Self { adapter }
+    }
+
+    /// Tap at the given viewport coordinates
+    ///
+    /// Dispatches `touchStart` followed by `touchEnd` via CDP `Input.dispatchTouchEvent`.
+    ///
+    /// # Arguments
+    /// * `x` - X coordinate in CSS pixels relative to the viewport
+    /// * `y` - Y coordinate in CSS pixels relative to the viewport
+    pub async fn tap(&self, x: f64, y: f64) -> Result<()> {
+        self.ensure_touch_enabled().await?;
+
+        for event_type in ["touchStart", "touchEnd"] {
+            let touch_points = if event_type == "touchStart" {
+                json!([{ "x": x, "y": y }])
+            } else {
+                json!([])
+            };
+
+            self.adapter
+                .execute_cdp_with_params(
+                    "Input.dispatchTouchEvent",
+                    json!({
+                        "type": event_type,
+                        "touchPoints": touch_points,
+                    }),
+                )
+                .await
+                .map_err(|e| Error::ActionFailed(format!("Failed to dispatch {}: {}", event_type, e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Fail with a clear error when the page has no touch support
+    async fn ensure_touch_enabled(&self) -> Result<()> {
+        let touch_points = self
+            .adapter
+            .execute_script("return navigator.maxTouchPoints || 0;")
+            .await?;
+
+        if touch_points.as_u64().unwrap_or(0) == 0 {
+            return Err(Error::ActionFailed(
+                "Touch is not enabled for this context; create it with has_touch(true) to use tap".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+//! WebSocket API
+//!
+//! WebSockets opened by a page are observed over a CDP websocket with the
+//! Network domain: `Network.webSocketCreated` becomes a [`WebSocket`], and
+//! the `webSocketFrameSent`/`webSocketFrameReceived`/`webSocketClosed`
+//! events for its request id are delivered to the handlers registered on it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use base64::prelude::*;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::core::Result;
+use crate::driver::WebDriverAdapter;
+
+type FrameHandler = Arc<dyn Fn(&WebSocketFrame) + Send + Sync>;
+type CloseHandler = Arc<dyn Fn() + Send + Sync>;
+pub(crate) type WebSocketHandler = Arc<dyn Fn(WebSocket) + Send + Sync>;
+
+/// A WebSocket frame sent or received by the page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketFrame {
+    opcode: u8,
+    payload: String,
+}
+
+impl WebSocketFrame {
+    /// The frame's text, or its base64-encoded bytes for binary frames
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    /// Whether this is a binary frame
+    pub fn is_binary(&self) -> bool {
+        self.opcode == 2
+    }
+
+    /// The decoded bytes of a binary frame
+    pub fn binary_payload(&self) -> Option<Vec<u8>> {
+        self.is_binary()
+            .then(|| BASE64_STANDARD.decode(&self.payload).ok())
+            .flatten()
+    }
+
+    /// The WebSocket opcode, e.g. 1 for text and 2 for binary
+    pub fn opcode(&self) -> u8 {
+        self.opcode
+    }
+}
+
+#[derive(Default)]
+struct Handlers {
+    frame_sent: Vec<FrameHandler>,
+    frame_received: Vec<FrameHandler>,
+    close: Vec<CloseHandler>,
+}
+
+struct Inner {
+    url: String,
+    closed: AtomicBool,
+    handlers: Mutex<Handlers>,
+}
+
+/// A WebSocket opened by a page
+///
+/// Passed to the handlers registered with
+/// [`Page::on_web_socket`](crate::async_api::Page::on_web_socket). Register
+/// frame handlers from inside that handler to see every frame.
+///
+/// # Example
+/// ```no_run
+/// # use sparkle::async_api::Page;
+/// # async fn example(page: &Page) -> sparkle::core::Result<()> {
+/// page.on_web_socket(|ws| {
+///     let url = ws.url().to_string();
+///     ws.on_frame_received(move |frame| println!("{} <- {}", url, frame.payload()));
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebSocket {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for WebSocket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocket")
+            .field("url", &self.inner.url)
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl WebSocket {
+    fn new(url: String) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                url,
+                closed: AtomicBool::new(false),
+                handlers: Mutex::new(Handlers::default()),
+            }),
+        }
+    }
+
+    /// URL the WebSocket connected to
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    /// Whether the WebSocket has been closed
+    pub fn is_closed(&self) -> bool {
+        self.inner.closed.load(Ordering::SeqCst)
+    }
+
+    /// Call `handler` for every frame the page sends
+    pub fn on_frame_sent(&self, handler: impl Fn(&WebSocketFrame) + Send + Sync + 'static) {
+        self.inner.handlers.lock().unwrap().frame_sent.push(Arc::new(handler));
+    }
+
+    /// Call `handler` for every frame the page receives
+    pub fn on_frame_received(&self, handler: impl Fn(&WebSocketFrame) + Send + Sync + 'static) {
+        self.inner.handlers.lock().unwrap().frame_received.push(Arc::new(handler));
+    }
+
+    /// Call `handler` when the WebSocket closes
+    pub fn on_close(&self, handler: impl Fn() + Send + Sync + 'static) {
+        self.inner.handlers.lock().unwrap().close.push(Arc::new(handler));
+    }
+
+    fn frame(&self, sent: bool, frame: &WebSocketFrame) {
+        // Cloned out of the lock so handlers may register more handlers
+        let handlers = {
+            let handlers = self.inner.handlers.lock().unwrap();
+            if sent { handlers.frame_sent.clone() } else { handlers.frame_received.clone() }
+        };
+        for handler in handlers {
+            handler(frame);
+        }
+    }
+
+    fn close(&self) {
+        if self.inner.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let handlers = self.inner.handlers.lock().unwrap().close.clone();
+        for handler in handlers {
+            handler();
+        }
+    }
+}
+
+/// Open WebSockets by CDP request id, and the page's `on_web_socket` handlers
+#[derive(Default)]
+struct Registry {
+    sockets: HashMap<String, WebSocket>,
+    handlers: Arc<Mutex<Vec<WebSocketHandler>>>,
+}
+
+impl Registry {
+    /// Route one CDP event to the socket it belongs to
+    fn dispatch(&mut self, event: &Value) {
+        let params = &event["params"];
+        let Some(request_id) = params["requestId"].as_str() else { return };
+
+        match event["method"].as_str() {
+            Some("Network.webSocketCreated") => {
+                let socket = WebSocket::new(params["url"].as_str().unwrap_or_default().to_string());
+                self.sockets.insert(request_id.to_string(), socket.clone());
+                let handlers = self.handlers.lock().unwrap().clone();
+                for handler in handlers {
+                    handler(socket.clone());
+                }
+            }
+            Some(method @ ("Network.webSocketFrameSent" | "Network.webSocketFrameReceived")) => {
+                let Some(socket) = self.sockets.get(request_id) else { return };
+                let response = &params["response"];
+                let frame = WebSocketFrame {
+                    opcode: response["opcode"].as_u64().unwrap_or(1) as u8,
+                    payload: response["payloadData"].as_str().unwrap_or_default().to_string(),
+                };
+                socket.frame(method == "Network.webSocketFrameSent", &frame);
+            }
+            Some("Network.webSocketClosed") => {
+                if let Some(socket) = self.sockets.remove(request_id) {
+                    socket.close();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Background listener reporting the WebSockets opened by a page
+pub(crate) struct WebSocketWatcher {
+    handlers: Arc<Mutex<Vec<WebSocketHandler>>>,
+    task: JoinHandle<()>,
+}
+
+impl WebSocketWatcher {
+    /// Enable the Network domain and start listening
+    pub(crate) async fn start(adapter: &WebDriverAdapter) -> Result<Self> {
+        let mut events = adapter.connect_cdp_events(&[("Network.enable", json!({}))]).await?;
+
+        let mut registry = Registry::default();
+        let handlers = Arc::clone(&registry.handlers);
+        let task = tokio::spawn(async move {
+            while let Some(Ok(message)) = events.next().await {
+                let Message::Text(text) = message else { continue };
+                let Ok(event) = serde_json::from_str::<Value>(&text) else { continue };
+                registry.dispatch(&event);
+            }
+        });
+
+        Ok(Self { handlers, task })
+    }
+
+    /// Call `handler` for every WebSocket the page opens from now on
+    pub(crate) fn add_handler(&self, handler: WebSocketHandler) {
+        self.handlers.lock().unwrap().push(handler);
+    }
+}
+
+impl Drop for WebSocketWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: Value) -> Value {
+        json!({ "method": method, "params": params })
+    }
+
+    #[test]
+    fn test_frames_and_close_reach_socket_handlers() {
+        let mut registry = Registry::default();
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let handler_log = Arc::clone(&log);
+        registry.handlers.lock().unwrap().push(Arc::new(move |ws: WebSocket| {
+            handler_log.lock().unwrap().push(format!("open {}", ws.url()));
+            let sent = Arc::clone(&handler_log);
+            ws.on_frame_sent(move |frame| sent.lock().unwrap().push(format!("sent {}", frame.payload())));
+            let received = Arc::clone(&handler_log);
+            ws.on_frame_received(move |frame| {
+                received.lock().unwrap().push(format!("received {:?}", frame.binary_payload()))
+            });
+            let closed = Arc::clone(&handler_log);
+            ws.on_close(move || closed.lock().unwrap().push("close".to_string()));
+        }));
+
+        let url = "ws://127.0.0.1:9000/echo";
+        registry.dispatch(&event("Network.webSocketCreated", json!({ "requestId": "1.1", "url": url })));
+        registry.dispatch(&event(
+            "Network.webSocketFrameSent",
+            json!({ "requestId": "1.1", "response": { "opcode": 1, "mask": true, "payloadData": "hello" } }),
+        ));
+        // Frames of sockets opened before listening started are ignored
+        registry.dispatch(&event(
+            "Network.webSocketFrameSent",
+            json!({ "requestId": "0.9", "response": { "opcode": 1, "payloadData": "stale" } }),
+        ));
+        registry.dispatch(&event(
+            "Network.webSocketFrameReceived",
+            json!({ "requestId": "1.1", "response": { "opcode": 2, "mask": false, "payloadData": "AQID" } }),
+        ));
+        registry.dispatch(&event("Network.webSocketClosed", json!({ "requestId": "1.1" })));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                format!("open {}", url),
+                "sent hello".to_string(),
+                "received Some([1, 2, 3])".to_string(),
+                "close".to_string(),
+            ]
+        );
+        assert!(registry.sockets.is_empty());
+    }
+
+    #[test]
+    fn test_text_frame_has_no_binary_payload() {
+        let frame = WebSocketFrame { opcode: 1, payload: "AQID".to_string() };
+        assert!(!frame.is_binary());
+        assert_eq!(frame.binary_payload(), None);
+    }
+}
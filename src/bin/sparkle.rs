@@ -21,6 +21,7 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Install browsers and drivers
+    #[command(disable_version_flag = true)]
     Install {
         /// Browser to install (chromium, chrome, all)
         #[arg(default_value = "chromium")]
@@ -33,6 +34,20 @@ enum Commands {
         /// Force reinstall even if already installed
         #[arg(long)]
         force: bool,
+
+        /// Download for another platform (e.g. linux-x64, macos-arm64) instead of the host.
+        /// Defaults to SPARKLE_PLATFORM when set
+        #[arg(long)]
+        platform: Option<String>,
+
+        /// Install a specific Chrome for Testing version (e.g. 120.0.6099.109, or 120 for
+        /// the newest 120 release) instead of Playwright's default Chromium
+        #[arg(long, conflicts_with = "revision")]
+        version: Option<String>,
+
+        /// Install the Chrome for Testing release with this Chromium revision
+        #[arg(long)]
+        revision: Option<String>,
     },
 
     /// List installed browsers
@@ -57,7 +72,20 @@ async fn main() {
             browser,
             skip_driver,
             force,
-        } => install::run(&browser, skip_driver, force).await,
+            platform,
+            version,
+            revision,
+        } => {
+            install::run(
+                &browser,
+                skip_driver,
+                force,
+                platform.as_deref(),
+                version.as_deref(),
+                revision.as_deref(),
+            )
+            .await
+        }
 
         Commands::List => list::run().await,
 
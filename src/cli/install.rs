@@ -3,7 +3,10 @@
 use super::{Downloader, Platform};
 use anyhow::Result;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Parallel range requests per browser archive, on servers that support them
+const DOWNLOAD_CONNECTIONS: usize = 4;
 
 /// Playwright's browsers.json structure
 #[derive(Debug, Deserialize)]
@@ -20,13 +23,80 @@ struct PlaywrightBrowser {
     install_by_default: bool,
 }
 
+/// Chrome for Testing versions with downloads for every platform
+const KNOWN_GOOD_VERSIONS_URL: &str =
+    "https://googlechromelabs.github.io/chrome-for-testing/known-good-versions.json";
+
+/// Chrome for Testing's known-good-versions.json structure
+#[derive(Debug, Deserialize)]
+struct KnownGoodVersionsJson {
+    versions: Vec<KnownGoodVersion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct KnownGoodVersion {
+    version: String,
+    revision: String,
+}
+
+/// Find the Chrome for Testing release to pin
+///
+/// `version` matches a full version (`120.0.6099.109`) or a prefix of one
+/// (`120`, `120.0.6099`), picking the newest match; `revision` matches the
+/// Chromium revision exactly.
+fn find_known_good<'a>(
+    versions: &'a [KnownGoodVersion],
+    version: Option<&str>,
+    revision: Option<&str>,
+) -> Result<&'a KnownGoodVersion> {
+    let found = match (version, revision) {
+        (Some(version), _) => versions.iter().rev().find(|known| {
+            known.version == version
+                || known.version.strip_prefix(version).is_some_and(|rest| rest.starts_with('.'))
+        }),
+        (None, Some(revision)) => versions.iter().rev().find(|known| known.revision == revision),
+        (None, None) => None,
+    };
+    found.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No Chrome for Testing release matches {}",
+            version.map_or_else(|| format!("revision {}", revision.unwrap_or_default()), |v| format!("version {}", v))
+        )
+    })
+}
+
+/// Resolve a pinned Chrome version or revision through the known-good-versions endpoint
+async fn get_pinned_chrome(
+    downloader: &Downloader,
+    version: Option<&str>,
+    revision: Option<&str>,
+) -> Result<KnownGoodVersion> {
+    let response = downloader.client().get(KNOWN_GOOD_VERSIONS_URL).send().await?;
+    let known: KnownGoodVersionsJson = response.error_for_status()?.json().await?;
+    find_known_good(&known.versions, version, revision).cloned()
+}
+
+/// Installation directory name for a Chromium revision, Playwright-style
+///
+/// Pinned releases are named by their full Chrome version instead: Chrome
+/// for Testing releases can share a revision (`120.0.6099.71` and
+/// `120.0.6099.109` are both `1217362`), and their revisions are not
+/// Playwright build numbers, so the default lookup must not rank them
+/// against `chromium-{revision}` installs.
+fn chromium_dir_name(revision: &str, pinned_version: Option<&str>) -> String {
+    match pinned_version {
+        Some(version) => format!("chromium-pinned-{}", version),
+        None => format!("chromium-{}", revision),
+    }
+}
+
 /// Get the latest Playwright Chromium browser info
 /// This fetches the default chromium version that Playwright uses
-async fn get_latest_playwright_chromium() -> Result<PlaywrightBrowser> {
+async fn get_latest_playwright_chromium(downloader: &Downloader) -> Result<PlaywrightBrowser> {
     // Fetch Playwright's browsers.json from GitHub
     let url = "https://raw.githubusercontent.com/microsoft/playwright/main/packages/playwright-core/browsers.json";
     
-    let response = reqwest::get(url).await?;
+    let response = downloader.client().get(url).send().await?;
     let browsers_json: PlaywrightBrowsersJson = response.json().await?;
     
     // Find the default chromium installation (installByDefault = true)
@@ -49,7 +119,7 @@ fn create_marker_file(browser_dir: &std::path::Path) -> Result<()> {
 
 /// Create Playwright link file and browsers.json to register this installation
 /// This prevents Playwright from removing our browsers during cleanup
-fn create_playwright_link(install_dir: &PathBuf, revision: &str, version: &str) -> Result<()> {
+fn create_playwright_link(install_dir: &Path, revision: &str, version: &str) -> Result<()> {
     // Create .links directory
     let links_dir = install_dir.join(".links");
     std::fs::create_dir_all(&links_dir)?;
@@ -86,40 +156,64 @@ fn create_playwright_link(install_dir: &PathBuf, revision: &str, version: &str)
     Ok(())
 }
 
-pub async fn run(browser: &str, skip_driver: bool, force: bool) -> Result<()> {
+/// Install `browser`, pinned to a Chrome for Testing `version` or `revision`
+/// when given, otherwise Playwright's current default Chromium
+pub async fn run(
+    browser: &str,
+    skip_driver: bool,
+    force: bool,
+    platform: Option<&str>,
+    version: Option<&str>,
+    revision: Option<&str>,
+) -> Result<()> {
     println!("Sparkle Browser Installer");
     println!("=========================\n");
 
-    let platform = Platform::detect()?;
-    println!("Detected platform: {}", platform);
+    let platform = Platform::resolve(platform)?;
+    println!("Target platform: {}", platform);
 
     let install_dir = get_install_dir()?;
     println!("Install directory: {:?}\n", install_dir);
 
-    let downloader = Downloader::new();
+    let downloader = Downloader::new().with_connections(DOWNLOAD_CONNECTIONS);
+    let download_host = super::platform::download_host();
+    if download_host != super::platform::DEFAULT_DOWNLOAD_HOST {
+        println!("Download mirror: {}", download_host);
+    }
 
-    // Fetch Playwright's latest Chromium version directly
-    println!("Fetching latest Playwright Chromium version...");
-    let playwright_browser = get_latest_playwright_chromium().await?;
-    let revision = playwright_browser.revision;
-    let version = playwright_browser.browser_version
-        .ok_or_else(|| anyhow::anyhow!("No browser version found in Playwright's browsers.json"))?;
-    
-    println!("Latest Playwright Chromium:");
-    println!("  Revision: {}", revision);
-    println!("  Chrome version: {}\n", version);
+    let pinned = version.is_some() || revision.is_some();
+    let (version, revision) = if pinned {
+        println!("Resolving pinned Chrome version...");
+        let pinned = get_pinned_chrome(&downloader, version, revision).await?;
+        println!("Pinned Chrome for Testing:");
+        println!("  Revision: {}", pinned.revision);
+        println!("  Chrome version: {}\n", pinned.version);
+        (pinned.version, pinned.revision)
+    } else {
+        // Fetch Playwright's latest Chromium version directly
+        println!("Fetching latest Playwright Chromium version...");
+        let playwright_browser = get_latest_playwright_chromium(&downloader).await?;
+        let version = playwright_browser.browser_version
+            .ok_or_else(|| anyhow::anyhow!("No browser version found in Playwright's browsers.json"))?;
+
+        println!("Latest Playwright Chromium:");
+        println!("  Revision: {}", playwright_browser.revision);
+        println!("  Chrome version: {}\n", version);
+        (version, playwright_browser.revision)
+    };
+    let dir_name = chromium_dir_name(&revision, pinned.then_some(version.as_str()));
 
     match browser.to_lowercase().as_str() {
         "chromium" | "chrome" => {
-            install_chrome(&downloader, &platform, &version, &revision, &install_dir, force).await?;
+            install_chrome(&downloader, &platform, &version, &revision, &dir_name, &install_dir, force).await?;
             // Install ChromeDriver by default unless --skip-driver is specified
             if !skip_driver {
-                install_chromedriver(&downloader, &platform, &version, &revision, &install_dir, force).await?;
+                install_chromedriver(&downloader, &platform, &version, &revision, &dir_name, &install_dir, force).await?;
             }
         }
         "all" => {
-            install_chrome(&downloader, &platform, &version, &revision, &install_dir, force).await?;
-            install_chromedriver(&downloader, &platform, &version, &revision, &install_dir, force).await?;
+            install_chrome(&downloader, &platform, &version, &revision, &dir_name, &install_dir, force).await?;
+            install_chromedriver(&downloader, &platform, &version, &revision, &dir_name, &install_dir, force).await?;
         }
         _ => {
             return Err(anyhow::anyhow!("Unknown browser: {}", browser));
@@ -128,9 +222,9 @@ pub async fn run(browser: &str, skip_driver: bool, force: bool) -> Result<()> {
 
     println!("\nInstallation complete!");
     println!("\nInstalled:");
-    println!("  Chromium revision {} (Chrome {}): {:?}", revision, version, install_dir.join(format!("chromium-{}", revision)));
+    println!("  Chromium revision {} (Chrome {}): {:?}", revision, version, install_dir.join(&dir_name));
     if !skip_driver {
-        println!("  ChromeDriver: {:?}", install_dir.join(format!("chromium-{}", revision)).join("chromedriver"));
+        println!("  ChromeDriver: {:?}", install_dir.join(&dir_name).join("chromedriver"));
     }
 
     Ok(())
@@ -141,11 +235,11 @@ async fn install_chrome(
     platform: &Platform,
     version: &str,
     revision: &str,
-    install_dir: &PathBuf,
+    dir_name: &str,
+    install_dir: &Path,
     force: bool,
 ) -> Result<()> {
-    // Use Playwright-style naming: chromium-{revision}
-    let chrome_dir = install_dir.join(format!("chromium-{}", revision));
+    let chrome_dir = install_dir.join(dir_name);
     
     if chrome_dir.exists() && !force {
         println!("Chromium {} (Chrome {}) is already installed. Use --force to reinstall.", revision, version);
@@ -174,11 +268,12 @@ async fn install_chromedriver(
     platform: &Platform,
     version: &str,
     revision: &str,
-    install_dir: &PathBuf,
+    dir_name: &str,
+    install_dir: &Path,
     force: bool,
 ) -> Result<()> {
-    // ChromeDriver uses same revision as chromium
-    let driver_dir = install_dir.join(format!("chromium-{}", revision)).join("chromedriver");
+    // ChromeDriver goes inside the Chromium directory it belongs to
+    let driver_dir = install_dir.join(dir_name).join("chromedriver");
     
     if driver_dir.exists() && !force {
         println!("ChromeDriver {} (Chrome {}) is already installed. Use --force to reinstall.", revision, version);
@@ -227,3 +322,43 @@ fn get_install_dir() -> Result<PathBuf> {
     // Append ms-playwright to match Playwright's structure
     Ok(cache_base.join("ms-playwright"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_version_resolves_to_its_archive_and_revision_dir() {
+        let known: KnownGoodVersionsJson = serde_json::from_value(serde_json::json!({
+            "timestamp": "2024-01-01T00:00:00.000Z",
+            "versions": [
+                { "version": "119.0.6045.105", "revision": "1204232" },
+                { "version": "120.0.6099.71", "revision": "1217362" },
+                { "version": "120.0.6099.109", "revision": "1217362" },
+                { "version": "121.0.6167.85", "revision": "1233107" },
+            ],
+        }))
+        .unwrap();
+
+        let pinned = find_known_good(&known.versions, Some("120.0.6099.71"), None).unwrap();
+        let url = Platform::LinuxX64.chrome_download_url(&pinned.version);
+        assert!(url.ends_with("/120.0.6099.71/linux64/chrome-linux64.zip"), "{}", url);
+        assert_eq!(
+            chromium_dir_name(&pinned.revision, Some(&pinned.version)),
+            "chromium-pinned-120.0.6099.71"
+        );
+        assert_eq!(chromium_dir_name("1200", None), "chromium-1200");
+
+        // A milestone picks its newest release
+        let milestone = find_known_good(&known.versions, Some("120"), None).unwrap();
+        assert_eq!(milestone.version, "120.0.6099.109");
+        // Releases sharing a revision get their own directories
+        assert_ne!(
+            chromium_dir_name(&milestone.revision, Some(&milestone.version)),
+            chromium_dir_name(&pinned.revision, Some(&pinned.version))
+        );
+        let by_revision = find_known_good(&known.versions, None, Some("1233107")).unwrap();
+        assert_eq!(by_revision.version, "121.0.6167.85");
+        assert!(find_known_good(&known.versions, Some("12"), None).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! List command implementation
 
+use crate::driver::chromedriver_process::chromium_install_key;
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -14,17 +15,19 @@ pub async fn run() -> Result<()> {
         return Ok(());
     }
 
-    // Find all chromium-{revision} installations
+    // Find all chromium-{revision} and chromium-pinned-{version} installations
     let mut chromium_versions = Vec::new();
     if let Ok(entries) = std::fs::read_dir(&install_dir) {
         for entry in entries.flatten() {
             if let Ok(file_name) = entry.file_name().into_string() {
-                if file_name.starts_with("chromium-") {
-                    if let Some(revision) = file_name.strip_prefix("chromium-") {
-                        // Check if chromedriver subdirectory exists
-                        let has_driver = entry.path().join("chromedriver").exists();
-                        chromium_versions.push((revision.to_string(), entry.path(), has_driver));
-                    }
+                if let Some(key) = chromium_install_key(&file_name) {
+                    let label = match file_name.strip_prefix("chromium-pinned-") {
+                        Some(version) => format!("Chrome {} (pinned)", version),
+                        None => format!("Revision {}", file_name.trim_start_matches("chromium-")),
+                    };
+                    // Check if chromedriver subdirectory exists
+                    let has_driver = entry.path().join("chromedriver").exists();
+                    chromium_versions.push((key, label, entry.path(), has_driver));
                 }
             }
         }
@@ -35,9 +38,10 @@ pub async fn run() -> Result<()> {
     } else {
         chromium_versions.sort_by(|a, b| b.0.cmp(&a.0));
         println!("Chromium ({} revision(s) installed)", chromium_versions.len());
-        for (revision, path, has_driver) in &chromium_versions {
+        for (key, label, path, has_driver) in &chromium_versions {
             let driver_status = if *has_driver { " [with ChromeDriver]" } else { "" };
-            println!("  - Revision {}{}: {:?}", revision, driver_status, path);
+            let used = if chromium_versions[0].0 == *key { " [default]" } else { "" };
+            println!("  - {}{}{}: {:?}", label, driver_status, used, path);
         }
     }
 
@@ -5,6 +5,28 @@
 
 use std::env;
 
+/// Where Chrome for Testing archives are downloaded from by default
+pub const DEFAULT_DOWNLOAD_HOST: &str = "https://storage.googleapis.com/chrome-for-testing-public";
+
+/// Base URL to download Chrome and ChromeDriver archives from
+///
+/// `SPARKLE_DOWNLOAD_HOST`, or else `PLAYWRIGHT_DOWNLOAD_HOST`, replaces
+/// [`DEFAULT_DOWNLOAD_HOST`] so installs can go through a mirror with the
+/// same `{version}/{platform}/` layout.
+pub fn download_host() -> String {
+    download_host_from(|name| env::var(name).ok())
+}
+
+/// `download_host` with environment variables looked up through `var`
+fn download_host_from(var: impl Fn(&str) -> Option<String>) -> String {
+    ["SPARKLE_DOWNLOAD_HOST", "PLAYWRIGHT_DOWNLOAD_HOST"]
+        .into_iter()
+        .filter_map(var)
+        .map(|host| host.trim().trim_end_matches('/').to_string())
+        .find(|host| !host.is_empty())
+        .unwrap_or_else(|| DEFAULT_DOWNLOAD_HOST.to_string())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Platform {
     WindowsX64,
@@ -32,63 +54,59 @@ impl Platform {
         }
     }
 
+    /// Pick the platform to download for
+    ///
+    /// An explicit `platform` (the `--platform` flag) wins, then the
+    /// `SPARKLE_PLATFORM` environment variable, then the host platform.
+    pub fn resolve(platform: Option<&str>) -> anyhow::Result<Self> {
+        Self::resolve_from(platform, env::var("SPARKLE_PLATFORM").ok())
+    }
+
+    /// `resolve` with the value of `SPARKLE_PLATFORM` passed in
+    fn resolve_from(platform: Option<&str>, env_platform: Option<String>) -> anyhow::Result<Self> {
+        if let Some(name) = platform {
+            return name.parse();
+        }
+        match env_platform {
+            Some(name) if !name.trim().is_empty() => name.parse(),
+            _ => Self::detect(),
+        }
+    }
+
     /// Get the Chrome download URL for this platform
+    ///
+    /// Uses the mirror from [`download_host`] when one is configured.
     pub fn chrome_download_url(&self, version: &str) -> String {
-        match self {
-            Platform::WindowsX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/win64/chrome-win64.zip",
-                version
-            ),
-            Platform::WindowsArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/win-arm64/chrome-win-arm64.zip",
-                version
-            ),
-            Platform::LinuxX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/linux64/chrome-linux64.zip",
-                version
-            ),
-            Platform::LinuxArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/linux-arm64/chrome-linux-arm64.zip",
-                version
-            ),
-            Platform::MacOsX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/mac-x64/chrome-mac-x64.zip",
-                version
-            ),
-            Platform::MacOsArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/mac-arm64/chrome-mac-arm64.zip",
-                version
-            ),
-        }
+        self.archive_url("chrome", version)
     }
 
     /// Get the ChromeDriver download URL for this platform
+    ///
+    /// Uses the mirror from [`download_host`] when one is configured.
     pub fn chromedriver_download_url(&self, version: &str) -> String {
+        self.archive_url("chromedriver", version)
+    }
+
+    /// `{host}/{version}/{platform}/{name}-{platform}.zip`, the Chrome for Testing layout
+    fn archive_url(&self, name: &str, version: &str) -> String {
+        self.archive_url_on(&download_host(), name, version)
+    }
+
+    /// `archive_url` on a given download host
+    fn archive_url_on(&self, host: &str, name: &str, version: &str) -> String {
+        let platform = self.chrome_for_testing_name();
+        format!("{}/{}/{}/{}-{}.zip", host, version, platform, name, platform)
+    }
+
+    /// The platform's directory name in Chrome for Testing downloads
+    fn chrome_for_testing_name(&self) -> &str {
         match self {
-            Platform::WindowsX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/win64/chromedriver-win64.zip",
-                version
-            ),
-            Platform::WindowsArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/win-arm64/chromedriver-win-arm64.zip",
-                version
-            ),
-            Platform::LinuxX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/linux64/chromedriver-linux64.zip",
-                version
-            ),
-            Platform::LinuxArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/linux-arm64/chromedriver-linux-arm64.zip",
-                version
-            ),
-            Platform::MacOsX64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/mac-x64/chromedriver-mac-x64.zip",
-                version
-            ),
-            Platform::MacOsArm64 => format!(
-                "https://storage.googleapis.com/chrome-for-testing-public/{}/mac-arm64/chromedriver-mac-arm64.zip",
-                version
-            ),
+            Platform::WindowsX64 => "win64",
+            Platform::WindowsArm64 => "win-arm64",
+            Platform::LinuxX64 => "linux64",
+            Platform::LinuxArm64 => "linux-arm64",
+            Platform::MacOsX64 => "mac-x64",
+            Platform::MacOsArm64 => "mac-arm64",
         }
     }
 
@@ -113,6 +131,27 @@ impl Platform {
     }
 }
 
+impl std::str::FromStr for Platform {
+    type Err = anyhow::Error;
+
+    /// Parse a platform name such as `linux-x64`, also accepting the
+    /// Chrome for Testing names (`linux64`, `mac-arm64`, `win64`, ...)
+    fn from_str(name: &str) -> anyhow::Result<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "windows-x64" | "win64" | "win-x64" => Ok(Platform::WindowsX64),
+            "windows-arm64" | "win-arm64" => Ok(Platform::WindowsArm64),
+            "linux-x64" | "linux64" => Ok(Platform::LinuxX64),
+            "linux-arm64" => Ok(Platform::LinuxArm64),
+            "macos-x64" | "mac-x64" => Ok(Platform::MacOsX64),
+            "macos-arm64" | "mac-arm64" => Ok(Platform::MacOsArm64),
+            _ => Err(anyhow::anyhow!(
+                "Unknown platform '{}' (expected one of: windows-x64, windows-arm64, linux-x64, linux-arm64, macos-x64, macos-arm64)",
+                name
+            )),
+        }
+    }
+}
+
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
@@ -144,4 +183,48 @@ mod tests {
         assert!(url.contains("linux64"));
         assert!(url.contains("chromedriver"));
     }
+
+    #[test]
+    fn test_platform_from_str() {
+        assert_eq!("linux-arm64".parse::<Platform>().unwrap(), Platform::LinuxArm64);
+        assert_eq!("mac-arm64".parse::<Platform>().unwrap(), Platform::MacOsArm64);
+        assert_eq!("WIN64".parse::<Platform>().unwrap(), Platform::WindowsX64);
+        assert!("solaris-sparc".parse::<Platform>().is_err());
+    }
+
+    #[test]
+    fn test_platform_override_targets_download_urls() {
+        let from_env = Platform::resolve_from(None, Some("linux-arm64".to_string()));
+        let from_flag = Platform::resolve_from(Some("mac-x64"), Some("linux-arm64".to_string()));
+
+        let platform = from_env.unwrap();
+        assert_eq!(platform, Platform::LinuxArm64);
+        assert!(platform.chrome_download_url("120.0.6099.109").ends_with("/linux-arm64/chrome-linux-arm64.zip"));
+        assert!(platform
+            .chromedriver_download_url("120.0.6099.109")
+            .ends_with("/linux-arm64/chromedriver-linux-arm64.zip"));
+
+        // The --platform flag takes precedence over the environment
+        let platform = from_flag.unwrap();
+        assert_eq!(platform, Platform::MacOsX64);
+        assert!(platform.chrome_download_url("120.0.6099.109").contains("/mac-x64/"));
+    }
+
+    #[test]
+    fn test_download_host_mirror_replaces_default() {
+        let host = download_host_from(|name| {
+            (name == "SPARKLE_DOWNLOAD_HOST").then(|| "https://mirror.example.com/chrome-for-testing/".to_string())
+        });
+        let chrome = Platform::LinuxX64.archive_url_on(&host, "chrome", "120.0.6099.109");
+        let driver = Platform::MacOsArm64.archive_url_on(&host, "chromedriver", "120.0.6099.109");
+
+        assert_eq!(
+            chrome,
+            "https://mirror.example.com/chrome-for-testing/120.0.6099.109/linux64/chrome-linux64.zip"
+        );
+        assert_eq!(
+            driver,
+            "https://mirror.example.com/chrome-for-testing/120.0.6099.109/mac-arm64/chromedriver-mac-arm64.zip"
+        );
+    }
 }
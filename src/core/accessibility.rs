@@ -0,0 +1,326 @@
+//! Accessibility tree snapshots
+//!
+//! Converts the flat node list returned by CDP `Accessibility.getFullAXTree`
+//! into a nested tree, optionally pruned the way Playwright prunes
+//! `accessibility.snapshot({ interestingOnly: true })`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A node in an accessibility tree snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct AxNode {
+    /// ARIA role, e.g. `button`, `textbox` or `RootWebArea`
+    pub role: String,
+
+    /// Accessible name
+    pub name: String,
+
+    /// Current value for controls such as text boxes and sliders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// Child nodes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<AxNode>,
+}
+
+impl AxNode {
+    /// Find the first node (depth-first, including this one) with the given role and name
+    pub fn find(&self, role: &str, name: &str) -> Option<&AxNode> {
+        if self.role == role && self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(role, name))
+    }
+
+    /// Build a tree from the `nodes` array of `Accessibility.getFullAXTree`
+    ///
+    /// With `interesting_only`, nodes that are not focusable, not controls and
+    /// not named leaves are dropped and their interesting descendants hoisted
+    /// to the nearest kept ancestor. The root is always kept.
+    pub(crate) fn from_cdp_nodes(nodes: &[Value], interesting_only: bool) -> Option<AxNode> {
+        let tree = RawTree::new(nodes);
+        let root = tree.root?;
+        let node = tree.nodes.get(root)?;
+        Some(AxNode {
+            role: node.role.clone(),
+            name: node.name.clone(),
+            value: node.value.clone(),
+            children: node
+                .child_ids
+                .iter()
+                .flat_map(|child| tree.serialize(child, false, interesting_only))
+                .collect(),
+        })
+    }
+}
+
+/// Backend DOM node ids of the nodes with `role` whose accessible name matches `name`
+///
+/// `nodes` is the array returned by `Accessibility.getFullAXTree`. Names are
+/// compared with whitespace collapsed, in full with `exact` and otherwise as a
+/// case-insensitive substring. Ignored nodes and nodes without a DOM node
+/// never match.
+pub(crate) fn backend_node_ids_by_role(nodes: &[Value], role: &str, name: &str, exact: bool) -> Vec<i64> {
+    let normalize = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    let expected = normalize(name);
+    nodes
+        .iter()
+        .filter(|node| !node["ignored"].as_bool().unwrap_or(false) && node["role"]["value"] == role)
+        .filter(|node| {
+            let actual = normalize(node["name"]["value"].as_str().unwrap_or_default());
+            if exact {
+                actual == expected
+            } else {
+                actual.to_lowercase().contains(&expected.to_lowercase())
+            }
+        })
+        .filter_map(|node| node["backendDOMNodeId"].as_i64())
+        .collect()
+}
+
+/// Roles Playwright treats as controls
+const CONTROL_ROLES: &[&str] = &[
+    "button",
+    "checkbox",
+    "ColorWell",
+    "combobox",
+    "DisclosureTriangle",
+    "listbox",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "radio",
+    "scrollbar",
+    "searchbox",
+    "slider",
+    "spinbutton",
+    "switch",
+    "tab",
+    "textbox",
+    "tree",
+    "treeitem",
+];
+
+/// Roles whose children are never part of the snapshot
+const LEAF_ROLES: &[&str] = &[
+    "doc-cover",
+    "graphics-symbol",
+    "img",
+    "image",
+    "Meter",
+    "meter",
+    "scrollbar",
+    "slider",
+    "separator",
+    "progressbar",
+];
+
+/// A node as reported by CDP, before nesting
+struct RawNode {
+    role: String,
+    name: String,
+    value: Option<String>,
+    ignored: bool,
+    hidden: bool,
+    focusable: bool,
+    rich_editable: bool,
+    child_ids: Vec<String>,
+}
+
+struct RawTree<'a> {
+    nodes: HashMap<&'a str, RawNode>,
+    root: Option<&'a str>,
+}
+
+impl<'a> RawTree<'a> {
+    fn new(nodes: &'a [Value]) -> Self {
+        let text = |value: &Value| match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            other => Some(other.to_string()),
+        };
+
+        let mut map = HashMap::new();
+        let mut root = None;
+        for node in nodes {
+            let Some(id) = node["nodeId"].as_str() else { continue };
+            if root.is_none() && node.get("parentId").is_none() {
+                root = Some(id);
+            }
+
+            let property = |name: &str| {
+                node["properties"]
+                    .as_array()
+                    .and_then(|props| props.iter().find(|p| p["name"] == name))
+                    .map(|p| p["value"]["value"].clone())
+                    .unwrap_or(Value::Null)
+            };
+
+            map.insert(
+                id,
+                RawNode {
+                    role: node["role"]["value"].as_str().unwrap_or_default().to_string(),
+                    name: node["name"]["value"].as_str().unwrap_or_default().to_string(),
+                    value: text(&node["value"]["value"]),
+                    ignored: node["ignored"].as_bool().unwrap_or(false),
+                    hidden: property("hidden") == Value::Bool(true),
+                    focusable: property("focusable") == Value::Bool(true),
+                    rich_editable: property("editable") == "richtext",
+                    child_ids: node["childIds"]
+                        .as_array()
+                        .map(|ids| ids.iter().filter_map(|id| id.as_str().map(String::from)).collect())
+                        .unwrap_or_default(),
+                },
+            );
+        }
+
+        Self { nodes: map, root }
+    }
+
+    fn has_focusable_child(&self, node: &RawNode) -> bool {
+        node.child_ids
+            .iter()
+            .filter_map(|id| self.nodes.get(id.as_str()))
+            .any(|child| child.focusable || self.has_focusable_child(child))
+    }
+
+    fn is_leaf(&self, node: &RawNode) -> bool {
+        if node.child_ids.is_empty() || LEAF_ROLES.contains(&node.role.as_str()) {
+            return true;
+        }
+        if self.has_focusable_child(node) {
+            return false;
+        }
+        (node.focusable && !node.name.is_empty()) || (node.role == "heading" && !node.name.is_empty())
+    }
+
+    fn is_interesting(&self, node: &RawNode, inside_control: bool) -> bool {
+        if node.ignored || node.hidden || node.role == "Ignored" {
+            return false;
+        }
+        if node.focusable || node.rich_editable || CONTROL_ROLES.contains(&node.role.as_str()) {
+            return true;
+        }
+        if inside_control {
+            return false;
+        }
+        self.is_leaf(node) && !node.name.is_empty()
+    }
+
+    /// Serialize a subtree, returning the node itself or its hoisted descendants
+    fn serialize(&self, id: &str, inside_control: bool, interesting_only: bool) -> Vec<AxNode> {
+        let Some(node) = self.nodes.get(id) else { return Vec::new() };
+
+        let keep = if interesting_only {
+            self.is_interesting(node, inside_control)
+        } else {
+            !node.ignored
+        };
+
+        let children = if interesting_only && self.is_leaf(node) {
+            Vec::new()
+        } else {
+            let inside_control = inside_control || CONTROL_ROLES.contains(&node.role.as_str());
+            node.child_ids
+                .iter()
+                .flat_map(|child| self.serialize(child, inside_control, interesting_only))
+                .collect()
+        };
+
+        if keep {
+            vec![AxNode {
+                role: node.role.clone(),
+                name: node.name.clone(),
+                value: node.value.clone(),
+                children,
+            }]
+        } else {
+            children
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn form_tree() -> Vec<Value> {
+        json!([
+            { "nodeId": "1", "role": { "value": "RootWebArea" }, "name": { "value": "Login" },
+              "childIds": ["2"] },
+            { "nodeId": "2", "parentId": "1", "role": { "value": "generic" }, "name": { "value": "" },
+              "childIds": ["3", "5", "7"] },
+            { "nodeId": "3", "parentId": "2", "role": { "value": "textbox" }, "name": { "value": "Email" },
+              "value": { "value": "me@example.com" },
+              "properties": [{ "name": "focusable", "value": { "value": true } }],
+              "childIds": ["4"] },
+            { "nodeId": "4", "parentId": "3", "role": { "value": "generic" }, "name": { "value": "" },
+              "childIds": [] },
+            { "nodeId": "5", "parentId": "2", "role": { "value": "button" }, "name": { "value": "Sign in" },
+              "properties": [{ "name": "focusable", "value": { "value": true } }],
+              "childIds": ["6"] },
+            { "nodeId": "6", "parentId": "5", "role": { "value": "StaticText" }, "name": { "value": "Sign in" },
+              "childIds": [] },
+            { "nodeId": "7", "parentId": "2", "ignored": true, "role": { "value": "none" },
+              "childIds": [] }
+        ])
+        .as_array()
+        .unwrap()
+        .clone()
+    }
+
+    #[test]
+    fn test_interesting_only_prunes_and_hoists() {
+        let root = AxNode::from_cdp_nodes(&form_tree(), true).unwrap();
+        assert_eq!(root.role, "RootWebArea");
+        // The generic wrapper is dropped and its controls hoisted to the root
+        let roles: Vec<_> = root.children.iter().map(|c| c.role.as_str()).collect();
+        assert_eq!(roles, vec!["textbox", "button"]);
+
+        let button = root.find("button", "Sign in").unwrap();
+        assert!(button.children.is_empty(), "named focusable nodes are leaves");
+        assert_eq!(
+            root.find("textbox", "Email").unwrap().value.as_deref(),
+            Some("me@example.com")
+        );
+    }
+
+    #[test]
+    fn test_full_tree_keeps_uninteresting_nodes() {
+        let root = AxNode::from_cdp_nodes(&form_tree(), false).unwrap();
+        assert_eq!(root.children.len(), 1);
+        assert_eq!(root.children[0].role, "generic");
+        // Ignored nodes are left out even in the full tree
+        assert_eq!(root.children[0].children.len(), 2);
+        assert!(root.find("StaticText", "Sign in").is_some());
+    }
+
+    #[test]
+    fn test_backend_node_ids_by_role_and_name() {
+        let nodes = json!([
+            { "nodeId": "1", "role": { "value": "link" }, "name": { "value": "Close  dialog" },
+              "backendDOMNodeId": 10 },
+            { "nodeId": "2", "role": { "value": "link" }, "name": { "value": "Close" },
+              "backendDOMNodeId": 11 },
+            { "nodeId": "3", "role": { "value": "button" }, "name": { "value": "Close dialog" },
+              "backendDOMNodeId": 12 },
+            { "nodeId": "4", "ignored": true, "role": { "value": "link" }, "name": { "value": "Close dialog" },
+              "backendDOMNodeId": 13 }
+        ]);
+        let nodes = nodes.as_array().unwrap();
+        assert_eq!(backend_node_ids_by_role(nodes, "link", "close", false), vec![10, 11]);
+        assert_eq!(backend_node_ids_by_role(nodes, "link", "Close dialog", true), vec![10]);
+        assert!(backend_node_ids_by_role(nodes, "link", "Open", false).is_empty());
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        assert!(AxNode::from_cdp_nodes(&[], true).is_none());
+    }
+}
@@ -62,9 +62,10 @@ impl DeviceDescriptor {
     /// ```no_run
     /// # use sparkle::core::devices::get_device;
     /// # async fn example() -> sparkle::core::Result<()> {
-    /// let iphone = get_device("iPhone 12").await?;
-    /// let options = iphone.to_context_options();
-    /// // Use options with browser.new_context()
+    /// if let Some(iphone) = get_device("iPhone 12").await? {
+    ///     let options = iphone.to_context_options();
+    ///     // Use options with browser.new_context()
+    /// }
     /// # Ok(())
     /// # }
     /// ```
@@ -45,9 +45,14 @@ pub enum Error {
     #[error("Network error: {0}")]
     Network(String),
 
-    /// Navigation failed
-    #[error("Navigation failed: {0}")]
-    Navigation(String),
+    /// Navigation to a URL failed
+    #[error("Navigation to {url} failed: {source}")]
+    Navigation {
+        /// The URL that was being navigated to
+        url: String,
+        /// The underlying failure
+        source: Box<Error>,
+    },
 
     /// JSON serialization/deserialization error
     #[error("Serialization error: {0}")]
@@ -77,25 +82,16 @@ pub enum Error {
     #[error("Element is not attached to the DOM")]
     ElementNotAttached,
 
-    /// Element is not visible
-    #[error("Element is not visible: {selector}")]
-    ElementNotVisible {
-        /// The selector for the element
-        selector: String,
-    },
-
-    /// Element is not enabled
-    #[error("Element is not enabled: {selector}")]
-    ElementNotEnabled {
-        /// The selector for the element
-        selector: String,
-    },
-
-    /// Element is not editable
-    #[error("Element is not editable: {selector}")]
-    ElementNotEditable {
+    /// Element failed an actionability check, so the action was not performed
+    ///
+    /// Replaces the former `ElementNotVisible`, `ElementNotEnabled` and
+    /// `ElementNotEditable` variants; match on `reason` instead.
+    #[error("Element is not actionable: {selector} ({reason})")]
+    NotActionable {
         /// The selector for the element
         selector: String,
+        /// The check that was still failing when the action gave up
+        reason: NotActionableReason,
     },
 
     /// JavaScript evaluation failed
@@ -151,6 +147,33 @@ pub enum Error {
     Internal(String),
 }
 
+/// Actionability check named by [`Error::NotActionable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotActionableReason {
+    /// The element has no size or is `visibility: hidden`
+    NotVisible,
+    /// The element kept moving between animation frames
+    NotStable,
+    /// Another element would receive the click at the element's center
+    Intercepted,
+    /// The element is disabled
+    NotEnabled,
+    /// The element is read-only or cannot be edited
+    NotEditable,
+}
+
+impl std::fmt::Display for NotActionableReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NotVisible => "not visible",
+            Self::NotStable => "not stable",
+            Self::Intercepted => "intercepted by another element",
+            Self::NotEnabled => "not enabled",
+            Self::NotEditable => "not editable",
+        })
+    }
+}
+
 /// Result type alias for Sparkle operations
 ///
 /// This is a convenience alias for Result<T, Error> used throughout the crate.
@@ -213,9 +236,20 @@ impl Error {
         Self::Network(message.into())
     }
 
-    /// Create a navigation error
-    pub fn navigation(message: impl Into<String>) -> Self {
-        Self::Navigation(message.into())
+    /// Create a navigation error for `url` caused by `source`
+    pub fn navigation(url: impl Into<String>, source: Error) -> Self {
+        Self::Navigation {
+            url: url.into(),
+            source: Box::new(source),
+        }
+    }
+
+    /// Create a not actionable error naming the failed check
+    pub fn not_actionable(selector: impl Into<String>, reason: NotActionableReason) -> Self {
+        Self::NotActionable {
+            selector: selector.into(),
+            reason,
+        }
     }
 
     /// Create an invalid argument error
@@ -237,6 +271,63 @@ impl Error {
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal(message.into())
     }
+
+    /// Whether the failure may be transient, so retrying the operation can succeed
+    ///
+    /// Timeouts, network and connection failures, and WebDriver transport
+    /// errors (including a Grid that could not create a session yet) are
+    /// retryable. A closed browser, context or page, an ended session and
+    /// errors caused by the call itself (bad arguments, missing elements,
+    /// script errors) are not.
+    ///
+    /// # Example
+    /// ```
+    /// use sparkle::core::Error;
+    ///
+    /// assert!(Error::connection_failed("connection refused").is_retryable());
+    /// assert!(!Error::BrowserClosed.is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        use thirtyfour::error::WebDriverErrorInner;
+
+        match self {
+            Self::Timeout { .. } | Self::Network(_) | Self::ConnectionFailed(_) => true,
+            Self::Navigation { source, .. } => source.is_retryable(),
+            Self::WebDriver(error) => matches!(
+                error.as_inner(),
+                WebDriverErrorInner::RequestFailed(_)
+                    | WebDriverErrorInner::HttpError(_)
+                    | WebDriverErrorInner::IoError(_)
+                    | WebDriverErrorInner::Timeout(_)
+                    | WebDriverErrorInner::WebDriverTimeout(_)
+                    | WebDriverErrorInner::SessionNotCreated(_)
+                    | WebDriverErrorInner::SessionCreateError(_)
+                    | WebDriverErrorInner::CommandSendError(_)
+                    | WebDriverErrorInner::CommandRecvError(_)
+            ) || matches!(error.as_inner(), WebDriverErrorInner::UnknownResponse(status, _) if *status >= 500),
+            _ => false,
+        }
+    }
+}
+
+impl Error {
+    /// Whether the connection to the WebDriver endpoint failed while sending
+    /// or receiving a command, as opposed to the command itself failing
+    pub(crate) fn is_transport_error(&self) -> bool {
+        use thirtyfour::error::WebDriverErrorInner;
+
+        matches!(
+            self,
+            Self::WebDriver(error) if matches!(
+                error.as_inner(),
+                WebDriverErrorInner::RequestFailed(_)
+                    | WebDriverErrorInner::HttpError(_)
+                    | WebDriverErrorInner::IoError(_)
+                    | WebDriverErrorInner::CommandSendError(_)
+                    | WebDriverErrorInner::CommandRecvError(_)
+            )
+        )
+    }
 }
 
 #[cfg(test)]
@@ -264,4 +355,77 @@ mod tests {
         assert!(matches!(err, Error::StrictModeViolation { .. }));
         assert!(err.to_string().contains("5 elements"));
     }
+
+    #[test]
+    fn test_navigation_error_keeps_source() {
+        let err = Error::navigation("https://example.invalid", Error::network("DNS lookup failed"));
+        assert!(matches!(&err, Error::Navigation { url, .. } if url == "https://example.invalid"));
+        assert!(err.to_string().contains("https://example.invalid"));
+        assert!(err.to_string().contains("DNS lookup failed"));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_retryable_classification() {
+        use thirtyfour::error::{WebDriverError, WebDriverErrorInfo, WebDriverErrorInner};
+
+        let webdriver = |inner: WebDriverErrorInner| Error::WebDriver(WebDriverError::from_inner(inner));
+        let info = || WebDriverErrorInfo::new("no free nodes".to_string());
+
+        let retryable = [
+            Error::timeout("Element did not appear", 5000),
+            Error::network("DNS lookup failed"),
+            Error::connection_failed("connection refused"),
+            Error::navigation("https://example.com", Error::network("connection reset")),
+            webdriver(WebDriverErrorInner::RequestFailed("connection refused".to_string())),
+            webdriver(WebDriverErrorInner::HttpError("connection reset".to_string())),
+            webdriver(WebDriverErrorInner::Timeout("request timed out".to_string())),
+            webdriver(WebDriverErrorInner::WebDriverTimeout(info())),
+            webdriver(WebDriverErrorInner::SessionNotCreated(info())),
+            webdriver(WebDriverErrorInner::SessionCreateError("no free nodes".to_string())),
+            webdriver(WebDriverErrorInner::UnknownResponse(503, "Service Unavailable".to_string())),
+        ];
+        for err in &retryable {
+            assert!(err.is_retryable(), "{} should be retryable", err);
+        }
+
+        let terminal = [
+            Error::BrowserClosed,
+            Error::ContextClosed,
+            Error::PageClosed,
+            Error::FrameDetached,
+            Error::element_not_found("#missing"),
+            Error::strict_mode_violation("div", 2),
+            Error::invalid_argument("bad capabilities"),
+            Error::JsEvaluation("ReferenceError".to_string()),
+            Error::not_implemented("CDP required"),
+            Error::ActionFailed("click failed".to_string()),
+            Error::internal("unexpected"),
+            Error::navigation("https://example.com", Error::PageClosed),
+            webdriver(WebDriverErrorInner::FatalError("session terminated".to_string())),
+            webdriver(WebDriverErrorInner::InvalidSessionId(info())),
+            webdriver(WebDriverErrorInner::InvalidArgument(info())),
+            webdriver(WebDriverErrorInner::NoSuchElement(info())),
+            webdriver(WebDriverErrorInner::UnknownResponse(404, "Not Found".to_string())),
+        ];
+        for err in &terminal {
+            assert!(!err.is_retryable(), "{} should not be retryable", err);
+        }
+    }
+
+    #[test]
+    fn test_not_actionable_names_the_check() {
+        let cases = [
+            (NotActionableReason::NotVisible, "not visible"),
+            (NotActionableReason::NotStable, "not stable"),
+            (NotActionableReason::Intercepted, "intercepted by another element"),
+            (NotActionableReason::NotEnabled, "not enabled"),
+            (NotActionableReason::NotEditable, "not editable"),
+        ];
+        for (reason, text) in cases {
+            let err = Error::not_actionable("#submit", reason);
+            assert!(matches!(&err, Error::NotActionable { selector, reason: r } if selector == "#submit" && *r == reason));
+            assert_eq!(err.to_string(), format!("Element is not actionable: #submit ({})", text));
+        }
+    }
 }
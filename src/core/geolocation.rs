@@ -0,0 +1,193 @@
+//! IP geolocation for stealth timezone and locale
+//!
+//! A timezone that doesn't match the exit IP's location is an easy detection
+//! signal. With [`StealthOptions::auto_timezone_from_ip`], the browser's public
+//! IP is looked up once at launch, through the launch proxy, and the result
+//! fills in whichever of `timezone_id` and `locale` the caller left unset.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::core::{Error, ProxySettings, Result, StealthOptions};
+
+/// Endpoint queried for the exit IP's location
+pub(crate) const GEOLOCATION_URL: &str = "http://ip-api.com/json/?fields=status,message,countryCode,timezone";
+
+/// Location of the IP a request was made from
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IpGeolocation {
+    /// IANA timezone, e.g. `Europe/Berlin`
+    pub timezone: String,
+    /// ISO 3166-1 alpha-2 country code, e.g. `DE`
+    pub country_code: String,
+}
+
+#[derive(Deserialize)]
+struct LookupResponse {
+    status: String,
+    message: Option<String>,
+    #[serde(flatten)]
+    location: Option<IpGeolocation>,
+}
+
+/// Look up where requests through `proxy` appear to come from
+pub(crate) async fn lookup(url: &str, proxy: Option<&ProxySettings>) -> Result<IpGeolocation> {
+    let mut client = reqwest::Client::builder().timeout(Duration::from_secs(10));
+    if let Some(proxy) = proxy {
+        let mut settings = reqwest::Proxy::all(&proxy.server)
+            .map_err(|e| Error::invalid_argument(format!("Invalid proxy server {}: {}", proxy.server, e)))?;
+        if let Some(username) = &proxy.username {
+            settings = settings.basic_auth(username, proxy.password.as_deref().unwrap_or_default());
+        }
+        client = client.proxy(settings);
+    }
+    let client = client
+        .build()
+        .map_err(|e| Error::ActionFailed(format!("Failed to create HTTP client: {}", e)))?;
+
+    let response: LookupResponse = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Error::network(format!("IP geolocation lookup failed: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::network(format!("Invalid IP geolocation response: {}", e)))?;
+
+    match response.location {
+        Some(location) if response.status == "success" => Ok(location),
+        _ => Err(Error::network(format!(
+            "IP geolocation lookup failed: {}",
+            response.message.unwrap_or(response.status)
+        ))),
+    }
+}
+
+/// Fill in the timezone and locale `stealth` leaves unset from `location`
+pub(crate) fn apply(stealth: &mut StealthOptions, location: &IpGeolocation) {
+    if stealth.timezone_id.is_none() {
+        stealth.timezone_id = Some(location.timezone.clone());
+    }
+    if stealth.locale.is_none() {
+        stealth.locale = locale_for_country(&location.country_code).map(str::to_string);
+    }
+}
+
+/// The most common browser locale in a country, for the countries proxies usually exit from
+fn locale_for_country(country_code: &str) -> Option<&'static str> {
+    Some(match country_code.to_ascii_uppercase().as_str() {
+        "US" => "en-US",
+        "GB" => "en-GB",
+        "CA" => "en-CA",
+        "AU" => "en-AU",
+        "IE" => "en-IE",
+        "IN" => "en-IN",
+        "SG" => "en-SG",
+        "DE" => "de-DE",
+        "AT" => "de-AT",
+        "CH" => "de-CH",
+        "FR" => "fr-FR",
+        "BE" => "fr-BE",
+        "ES" => "es-ES",
+        "MX" => "es-MX",
+        "AR" => "es-AR",
+        "IT" => "it-IT",
+        "NL" => "nl-NL",
+        "PT" => "pt-PT",
+        "BR" => "pt-BR",
+        "PL" => "pl-PL",
+        "SE" => "sv-SE",
+        "NO" => "nb-NO",
+        "DK" => "da-DK",
+        "FI" => "fi-FI",
+        "CZ" => "cs-CZ",
+        "RU" => "ru-RU",
+        "UA" => "uk-UA",
+        "TR" => "tr-TR",
+        "JP" => "ja-JP",
+        "KR" => "ko-KR",
+        "CN" => "zh-CN",
+        "TW" => "zh-TW",
+        "HK" => "zh-HK",
+        "VN" => "vi-VN",
+        "TH" => "th-TH",
+        "ID" => "id-ID",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one HTTP request with `body`, returning the request line
+    async fn serve_once(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0; 4096];
+            let read = stream.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..read]).lines().next().unwrap_or_default().to_string()
+        });
+        (address, server)
+    }
+
+    #[tokio::test]
+    async fn test_lookup_goes_through_proxy_and_derives_timezone() {
+        let (proxy_address, proxy) =
+            serve_once(r#"{"status":"success","countryCode":"JP","timezone":"Asia/Tokyo"}"#).await;
+        let proxy_settings = ProxySettings {
+            server: proxy_address,
+            bypass: None,
+            username: None,
+            password: None,
+        };
+
+        let location = lookup(GEOLOCATION_URL, Some(&proxy_settings)).await.unwrap();
+        let request_line = proxy.await.unwrap();
+        assert!(request_line.starts_with("GET http://ip-api.com/json/"), "{}", request_line);
+
+        let mut stealth = StealthOptions::default();
+        apply(&mut stealth, &location);
+        assert_eq!(stealth.timezone_id.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(stealth.locale.as_deref(), Some("ja-JP"));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_reports_failed_status() {
+        let (address, _server) = serve_once(r#"{"status":"fail","message":"reserved range"}"#).await;
+        let err = lookup(&address, None).await.unwrap_err();
+        assert!(matches!(&err, Error::Network(message) if message.contains("reserved range")));
+    }
+
+    #[test]
+    fn test_apply_keeps_explicit_settings() {
+        let location = IpGeolocation {
+            timezone: "Europe/Berlin".to_string(),
+            country_code: "DE".to_string(),
+        };
+        let mut stealth = StealthOptions {
+            timezone_id: Some("America/New_York".to_string()),
+            ..Default::default()
+        };
+        apply(&mut stealth, &location);
+        assert_eq!(stealth.timezone_id.as_deref(), Some("America/New_York"));
+        assert_eq!(stealth.locale.as_deref(), Some("de-DE"));
+
+        let mut stealth = StealthOptions::default();
+        apply(&mut stealth, &IpGeolocation { timezone: "Pacific/Fiji".to_string(), country_code: "FJ".to_string() });
+        assert_eq!(stealth.timezone_id.as_deref(), Some("Pacific/Fiji"));
+        assert_eq!(stealth.locale, None);
+    }
+}
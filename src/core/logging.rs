@@ -31,7 +31,7 @@ pub fn init_logging() {
         // Only initialize if log level is set to something other than "off"
         if log_level != "off" {
             let filter = EnvFilter::try_from_default_env()
-                .or_else(|_| EnvFilter::try_new(&format!("sparkle={}", log_level)))
+                .or_else(|_| EnvFilter::try_new(format!("sparkle={}", log_level)))
                 .unwrap_or_else(|_| EnvFilter::new("info"));
 
             fmt()
@@ -61,7 +61,7 @@ pub fn init_logging() {
 /// ```
 pub fn init_logging_with_level(level: &str) {
     INIT.call_once(|| {
-        let filter = EnvFilter::try_new(&format!("sparkle={}", level))
+        let filter = EnvFilter::try_new(format!("sparkle={}", level))
             .unwrap_or_else(|_| EnvFilter::new("info"));
 
         fmt()
@@ -1,18 +1,26 @@
 //! Core types and utilities for Sparkle
 
+pub mod accessibility;
 pub mod devices;
 pub mod error;
+pub(crate) mod geolocation;
 pub mod logging;
 pub mod options;
+pub mod screenshot;
 pub mod stealth;
 pub mod stealth_headers;
 pub mod storage;
 
 // Re-export commonly used types
+pub use accessibility::AxNode;
 pub use devices::{get_all_devices, get_device, list_devices, DeviceDescriptor};
-pub use error::{Error, Result};
+pub use error::{Error, NotActionableReason, Result};
 pub use logging::{init_logging, init_logging_with_level};
 pub use options::*;
+pub use screenshot::ScreenshotImage;
 pub use stealth::{get_minimal_stealth_script, get_stealth_script};
 pub use stealth_headers::HeadersConfig;
-pub use storage::{CookieState, NameValue, OriginState, SameSite, StorageState, StorageStateSource};
+pub use storage::{
+    CookiePriority, CookieState, IndexedDbDatabase, IndexedDbIndex, IndexedDbRecord, IndexedDbStore, NameValue, OriginState,
+    SameSite, StorageState, StorageStateSource,
+};
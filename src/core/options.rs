@@ -53,6 +53,13 @@ pub struct StealthOptions {
     /// Geolocation coordinates for emulation (latitude, longitude, accuracy).
     /// If None, geolocation is not emulated.
     pub geolocation: Option<(f64, f64, f64)>,
+
+    /// Derive `timezone_id` and `locale`, when unset, from the location of the
+    /// browser's public IP. The IP is looked up through the launch proxy once
+    /// per browser; if the lookup fails, a warning is logged and both are left
+    /// unset. Defaults to false.
+    #[serde(default)]
+    pub auto_timezone_from_ip: bool,
 }
 
 impl Default for StealthOptions {
@@ -68,6 +75,7 @@ impl Default for StealthOptions {
             locale: None,
             timezone_id: None,
             geolocation: None,
+            auto_timezone_from_ip: false,
         }
     }
 }
@@ -77,11 +85,19 @@ impl Default for StealthOptions {
 #[builder(default, setter(into, strip_option))]
 pub struct LaunchOptions {
     /// Whether to run browser in headless mode. Defaults to true.
+    ///
+    /// `true` maps to [`HeadlessMode::New`]; ignored when `headless_mode` is set.
     pub headless: Option<bool>,
 
+    /// Which headless implementation to use. Takes precedence over `headless`.
+    pub headless_mode: Option<HeadlessMode>,
+
     /// Slows down operations by the specified duration. Useful for debugging.
     pub slow_mo: Option<Duration>,
 
+    /// Scoped slow motion settings. Takes precedence over `slow_mo`.
+    pub slow_mo_config: Option<SlowMoConfig>,
+
     /// Maximum time to wait for browser to start. Defaults to 30 seconds.
     pub timeout: Option<Duration>,
 
@@ -89,6 +105,10 @@ pub struct LaunchOptions {
     #[builder(default)]
     pub args: Vec<String>,
 
+    /// Initial browser window size as `(width, height)`, passed as `--window-size`.
+    /// A `--window-size` in `args` takes precedence.
+    pub window_size: Option<(u32, u32)>,
+
     /// Path to a browser executable to run instead of bundled browser.
     pub executable_path: Option<PathBuf>,
 
@@ -127,16 +147,136 @@ pub struct LaunchOptions {
     pub ignore_all_default_args: Option<bool>,
 
     /// List of default arguments to filter out.
+    /// Entries without a value match by name, so `--disable-blink-features`
+    /// drops `--disable-blink-features=AutomationControlled`.
     #[builder(default)]
     pub ignore_default_args: Vec<String>,
 
+    /// Maximum number of pages open at once across the browser.
+    /// `new_page` waits for a page to close once the limit is reached.
+    /// `launch` rejects 0.
+    pub max_concurrent_pages: Option<usize>,
+
+    /// Share one ChromeDriver process across launches instead of spawning one
+    /// per browser. Each launch still gets its own WebDriver session.
+    /// Can also be enabled with `SPARKLE_REUSE_CHROMEDRIVER=1`. The driver
+    /// stops when the last browser using it is closed or dropped, or when
+    /// `ChromeDriverProcess::shutdown_shared()` is called.
+    pub reuse_driver: Option<bool>,
+
     /// Stealth mode configuration (Chromium-only).
     /// Defaults to enabled for undetectable automation.
     pub stealth: Option<StealthOptions>,
+
+    /// Protocol used for page events such as console messages and network
+    /// traffic. Defaults to [`Protocol::Cdp`].
+    pub protocol: Option<Protocol>,
+}
+
+/// Category of operations that slow motion can be applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlowMoScope {
+    /// Element lookups, frame switches and other input actions
+    Input,
+    /// Page navigations
+    Navigation,
+    /// JavaScript evaluation
+    Script,
+}
+
+/// Slow motion configuration scoped to operation categories
+///
+/// A plain `Duration` converts into a config that applies to every category.
+///
+/// # Example
+/// ```
+/// # use sparkle::core::{SlowMoConfig, SlowMoScope};
+/// # use std::time::Duration;
+/// // Only slow down clicks/typing, keep navigations fast
+/// let config = SlowMoConfig::input_only(Duration::from_millis(500));
+/// assert_eq!(config.delay_for(SlowMoScope::Navigation), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowMoConfig {
+    /// Delay applied before each operation in an enabled category
+    pub delay: Duration,
+    /// Apply to input actions
+    pub input: bool,
+    /// Apply to navigations
+    pub navigation: bool,
+    /// Apply to script evaluation
+    pub script: bool,
+}
+
+impl SlowMoConfig {
+    /// Apply the delay to every category
+    pub fn all(delay: Duration) -> Self {
+        Self {
+            delay,
+            input: true,
+            navigation: true,
+            script: true,
+        }
+    }
+
+    /// Apply the delay to input actions only
+    pub fn input_only(delay: Duration) -> Self {
+        Self {
+            delay,
+            input: true,
+            navigation: false,
+            script: false,
+        }
+    }
+
+    /// Delay to apply for the given category, if any
+    pub fn delay_for(&self, scope: SlowMoScope) -> Option<Duration> {
+        let enabled = match scope {
+            SlowMoScope::Input => self.input,
+            SlowMoScope::Navigation => self.navigation,
+            SlowMoScope::Script => self.script,
+        };
+        enabled.then_some(self.delay)
+    }
+}
+
+impl From<Duration> for SlowMoConfig {
+    fn from(delay: Duration) -> Self {
+        Self::all(delay)
+    }
+}
+
+/// Chromium headless implementation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadlessMode {
+    /// Legacy headless shell (`--headless=old`). Deprecated and easier to detect.
+    Old,
+    /// New headless mode (`--headless=new`) that renders like headful Chrome
+    #[default]
+    New,
+    /// Run with a visible browser window
+    Off,
+}
+
+/// Protocol a browser session listens for events on
+///
+/// CDP is Chromium-only. WebDriver BiDi is the cross-browser standard; with
+/// [`Protocol::Bidi`] the session is created with `webSocketUrl: true` and
+/// console and network events are subscribed to over the BiDi websocket.
+/// Commands that have no BiDi counterpart yet still use CDP where available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// Chrome DevTools Protocol
+    #[default]
+    Cdp,
+    /// WebDriver BiDi
+    Bidi,
 }
 
 /// Network proxy configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProxySettings {
     /// Proxy server URL (e.g., "http://myproxy.com:3128")
     pub server: String,
@@ -164,10 +304,19 @@ pub struct ConnectOptions {
     /// Slows down operations by the specified duration. Useful for debugging.
     pub slow_mo: Option<Duration>,
 
+    /// Scoped slow motion settings. Takes precedence over `slow_mo`.
+    pub slow_mo_config: Option<SlowMoConfig>,
+
     /// Additional HTTP headers to send with WebDriver requests
     #[builder(default)]
     pub headers: HashMap<String, String>,
 
+    /// Extra W3C capabilities (e.g. `se:name` for Selenium Grid).
+    /// These override the built-in Chromium capabilities; object values such as
+    /// `goog:chromeOptions` are merged key by key.
+    #[builder(default)]
+    pub capabilities: serde_json::Map<String, serde_json::Value>,
+
     /// Additional arguments to pass to the browser instance
     #[builder(default)]
     pub args: Vec<String>,
@@ -181,6 +330,69 @@ pub struct ConnectOptions {
 
     /// Browser distribution channel (e.g., "chrome", "chrome-beta")
     pub channel: Option<String>,
+
+    /// Backoff between connection attempts. Defaults to [`RetryOptions::default`].
+    /// Only failures that [`Error::is_retryable`](crate::core::Error::is_retryable)
+    /// are retried; others are returned immediately.
+    pub retry: Option<RetryOptions>,
+
+    /// Ping the session after this long without commands, so the grid's idle
+    /// timeout doesn't end it during long waits. Disabled by default.
+    pub keepalive_interval: Option<Duration>,
+}
+
+/// Exponential backoff between connection attempts
+///
+/// The delay starts at `initial_delay` and is multiplied by `multiplier`
+/// after every failed attempt, capped at `max_delay`. With `jitter`, each
+/// delay is randomly shortened by up to that fraction so that many clients
+/// waiting on the same Grid don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+#[builder(default)]
+pub struct RetryOptions {
+    /// Delay before the first retry. Defaults to 100ms.
+    pub initial_delay: Duration,
+
+    /// Upper bound for a single delay. Defaults to 5 seconds.
+    pub max_delay: Duration,
+
+    /// Growth factor applied after each failed attempt. Defaults to 2.0.
+    pub multiplier: f64,
+
+    /// Fraction (0.0-1.0) of each delay that may be randomly removed. Defaults to none.
+    #[builder(setter(strip_option))]
+    pub jitter: Option<f64>,
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: None,
+        }
+    }
+}
+
+impl RetryOptions {
+    /// Delay before retry number `attempt` (starting at 0), before jitter
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(attempt.min(i32::MAX as u32) as i32);
+        let delay = self.initial_delay.as_secs_f64() * factor;
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// Delay before retry number `attempt` with jitter applied
+    ///
+    /// `random` is a sample in `[0, 1)`.
+    pub(crate) fn jittered_delay_for(&self, attempt: u32, random: f64) -> Duration {
+        let delay = self.delay_for(attempt);
+        match self.jitter {
+            Some(jitter) => delay.mul_f64(1.0 - jitter.clamp(0.0, 1.0) * random),
+            None => delay,
+        }
+    }
 }
 
 /// Options for connecting to a browser via Chrome DevTools Protocol
@@ -200,6 +412,9 @@ pub struct ConnectOverCdpOptions {
     /// Slows down operations by the specified duration. Useful for debugging.
     pub slow_mo: Option<Duration>,
 
+    /// Scoped slow motion settings. Takes precedence over `slow_mo`.
+    pub slow_mo_config: Option<SlowMoConfig>,
+
     /// Additional HTTP headers to send with CDP requests
     #[builder(default)]
     pub headers: HashMap<String, String>,
@@ -218,10 +433,16 @@ pub struct BrowserContextOptions {
     /// Emulates prefers-colors-scheme media feature
     pub color_scheme: Option<ColorScheme>,
 
+    /// Emulates prefers-reduced-motion media feature
+    pub reduced_motion: Option<ReducedMotion>,
+
     /// Specify device scale factor (DPR). Defaults to 1.
     pub device_scale_factor: Option<f64>,
 
     /// Additional HTTP headers to send with every request
+    ///
+    /// These take precedence over the client hint headers sent for stealth
+    /// header alignment; names are compared case-insensitively.
     #[builder(default)]
     pub extra_http_headers: HashMap<String, String>,
 
@@ -254,6 +475,11 @@ pub struct BrowserContextOptions {
     pub permissions: Vec<String>,
 
     /// Proxy settings for this context
+    ///
+    /// Contexts share the browser process, whose proxy is fixed when it
+    /// starts, so this must match `LaunchOptions::proxy`; any other proxy makes
+    /// `Browser::new_context` fail with [`Error::NotImplemented`](crate::core::Error::NotImplemented).
+    /// Launch a separate browser to use a different proxy.
     pub proxy: Option<ProxySettings>,
 
     /// Specific user agent to use
@@ -286,6 +512,11 @@ pub struct BrowserContextOptions {
     /// Control focus behavior (Patchright extension). Defaults to true.
     pub focus_control: Option<bool>,
 
+    /// Maximum number of pages open at once in this context.
+    /// `new_page` waits for a page to close once the limit is reached.
+    /// `new_context` rejects 0.
+    pub max_concurrent_pages: Option<usize>,
+
     /// Stealth mode configuration (Chromium-only).
     /// Defaults to enabled for undetectable automation.
     pub stealth: Option<StealthOptions>,
@@ -297,7 +528,7 @@ pub struct BrowserContextOptions {
 }
 
 /// Color scheme preference
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ColorScheme {
     Light,
@@ -305,6 +536,69 @@ pub enum ColorScheme {
     NoPreference,
 }
 
+impl ColorScheme {
+    /// Value of the `prefers-color-scheme` media feature
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+            ColorScheme::NoPreference => "no-preference",
+        }
+    }
+}
+
+/// Emulated CSS media type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Media {
+    Screen,
+    Print,
+}
+
+impl Media {
+    /// CSS media type name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Media::Screen => "screen",
+            Media::Print => "print",
+        }
+    }
+}
+
+/// Reduced motion preference
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReducedMotion {
+    Reduce,
+    NoPreference,
+}
+
+impl ReducedMotion {
+    /// Value of the `prefers-reduced-motion` media feature
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReducedMotion::Reduce => "reduce",
+            ReducedMotion::NoPreference => "no-preference",
+        }
+    }
+}
+
+/// Options for `Page::emulate_media`
+///
+/// Fields left as `None` keep their previously emulated value.
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct EmulateMediaOptions {
+    /// Emulates the `prefers-color-scheme` media feature
+    pub color_scheme: Option<ColorScheme>,
+
+    /// Emulates the CSS media type
+    pub media: Option<Media>,
+
+    /// Emulates the `prefers-reduced-motion` media feature
+    pub reduced_motion: Option<ReducedMotion>,
+}
+
 /// Geolocation coordinates
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Geolocation {
@@ -366,10 +660,11 @@ pub struct NavigationOptions {
 }
 
 /// Navigation wait state
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum WaitUntilState {
     /// Wait for the load event
+    #[default]
     Load,
     /// Wait for DOMContentLoaded event
     DomContentLoaded,
@@ -379,12 +674,75 @@ pub enum WaitUntilState {
     Commit,
 }
 
-impl Default for WaitUntilState {
-    fn default() -> Self {
-        Self::Load
+/// Options for page.wait_for_selector()
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct WaitForSelectorOptions {
+    /// State to wait for. Defaults to `Visible`.
+    pub state: Option<WaitForSelectorState>,
+
+    /// Maximum time to wait. Defaults to 30 seconds.
+    pub timeout: Option<Duration>,
+}
+
+/// Element state awaited by `wait_for_selector`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WaitForSelectorState {
+    /// Wait for the element to be in the DOM
+    Attached,
+    /// Wait for the element to be absent from the DOM
+    Detached,
+    /// Wait for the element to be in the DOM with a non-empty box and no
+    /// `visibility: hidden` or `display: none`
+    #[default]
+    Visible,
+    /// Wait for the element to be absent or not visible
+    Hidden,
+}
+
+impl WaitForSelectorState {
+    /// State name as used by Playwright
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WaitForSelectorState::Attached => "attached",
+            WaitForSelectorState::Detached => "detached",
+            WaitForSelectorState::Visible => "visible",
+            WaitForSelectorState::Hidden => "hidden",
+        }
     }
 }
 
+/// Options for `get_by_role` locators
+///
+/// Each state option, when set, keeps only elements whose ARIA attribute or
+/// native state matches, like Playwright's `getByRole` options.
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct GetByRoleOptions {
+    /// Match `aria-checked` or the native checked state of checkboxes/radios
+    pub checked: Option<bool>,
+
+    /// Match `aria-disabled` or the native disabled state
+    pub disabled: Option<bool>,
+
+    /// Match `aria-expanded`
+    pub expanded: Option<bool>,
+
+    /// Match `aria-pressed`
+    pub pressed: Option<bool>,
+
+    /// Match `aria-selected` or the native selected state of options
+    pub selected: Option<bool>,
+
+    /// Match the accessible name (aria-labelledby, aria-label, labels, alt or text).
+    /// Case-insensitive substring match unless `exact` is set.
+    pub name: Option<String>,
+
+    /// Match `name` exactly (case-sensitive, whitespace-normalized)
+    pub exact: Option<bool>,
+}
+
 /// Options for element click actions
 #[derive(Debug, Clone, Builder, Default)]
 #[builder(default, setter(into, strip_option))]
@@ -392,7 +750,9 @@ pub struct ClickOptions {
     /// Maximum time to wait. Defaults to 30 seconds.
     pub timeout: Option<Duration>,
 
-    /// Whether to bypass actionability checks
+    /// Whether to bypass actionability checks. Elements that can't receive
+    /// pointer events (e.g. `pointer-events: none`) get the click events
+    /// dispatched on them directly.
     pub force: Option<bool>,
 
     /// Mouse button to use
@@ -428,7 +788,7 @@ pub enum MouseButton {
 }
 
 /// Keyboard modifier keys
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum KeyboardModifier {
     Alt,
     Control,
@@ -436,6 +796,47 @@ pub enum KeyboardModifier {
     Shift,
 }
 
+impl KeyboardModifier {
+    /// Parse a `+`-separated modifier list such as `"Control+Shift"`
+    ///
+    /// Names are case-insensitive; `Ctrl`, `Cmd`/`Command` and `Option` are
+    /// accepted as aliases, and `ControlOrMeta` picks Meta on macOS.
+    ///
+    /// # Example
+    /// ```
+    /// # use sparkle::core::KeyboardModifier;
+    /// let mods = KeyboardModifier::parse_list("Control+Shift").unwrap();
+    /// assert_eq!(mods, vec![KeyboardModifier::Control, KeyboardModifier::Shift]);
+    /// ```
+    pub fn parse_list(modifiers: &str) -> crate::core::Result<Vec<Self>> {
+        modifiers
+            .split('+')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+}
+
+impl std::str::FromStr for KeyboardModifier {
+    type Err = crate::core::Error;
+
+    fn from_str(name: &str) -> crate::core::Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "alt" | "option" => Ok(Self::Alt),
+            "control" | "ctrl" => Ok(Self::Control),
+            "meta" | "cmd" | "command" => Ok(Self::Meta),
+            "shift" => Ok(Self::Shift),
+            "controlormeta" if cfg!(target_os = "macos") => Ok(Self::Meta),
+            "controlormeta" => Ok(Self::Control),
+            _ => Err(crate::core::Error::invalid_argument(format!(
+                "Unknown keyboard modifier '{}'",
+                name
+            ))),
+        }
+    }
+}
+
 /// Position coordinates
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Position {
@@ -443,6 +844,20 @@ pub struct Position {
     pub y: f64,
 }
 
+/// Options for tap actions
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct TapOptions {
+    /// Maximum time to wait for the element. Defaults to the locator timeout.
+    pub timeout: Option<Duration>,
+
+    /// Point to tap relative to the element's top-left corner. Defaults to the center.
+    pub position: Option<Position>,
+
+    /// Dispatch the tap in-page when the element can't receive pointer events
+    pub force: Option<bool>,
+}
+
 /// Options for typing text
 #[derive(Debug, Clone, Builder, Default)]
 #[builder(default, setter(into, strip_option))]
@@ -457,6 +872,25 @@ pub struct TypeOptions {
     pub no_wait_after: Option<bool>,
 }
 
+/// Source of a tag added with `Page::add_script_tag` or `Page::add_style_tag`
+///
+/// Set exactly one of `url`, `path` and `content`.
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct AddTagOptions {
+    /// URL of the script or stylesheet to load
+    pub url: Option<String>,
+
+    /// Local file whose contents are inlined into the tag
+    pub path: Option<PathBuf>,
+
+    /// Inline JavaScript or CSS
+    pub content: Option<String>,
+
+    /// Script `type` attribute, e.g. `module`. Ignored for style tags.
+    pub r#type: Option<String>,
+}
+
 /// Screenshot options
 #[derive(Debug, Clone, Builder, Default)]
 #[builder(default, setter(into, strip_option))]
@@ -476,10 +910,32 @@ pub struct ScreenshotOptions {
     /// Hides default white background
     pub omit_background: Option<bool>,
 
+    /// Capture only this region of the page. Cannot be combined with `full_page`.
+    pub clip: Option<Clip>,
+
+    /// Whether CSS animations and transitions keep running. `Disabled`
+    /// finishes finite animations and rewinds infinite ones for the capture.
+    pub animations: Option<ScreenshotAnimations>,
+
+    /// CSS selectors of elements to cover with a solid box
+    pub mask: Option<Vec<String>>,
+
+    /// CSS color of the `mask` boxes. Defaults to `#FF00FF`.
+    pub mask_color: Option<String>,
+
     /// Maximum time to wait
     pub timeout: Option<Duration>,
 }
 
+/// Rectangular screenshot region in CSS pixels, relative to the page's top-left corner
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Clip {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
 /// Screenshot format type
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -488,6 +944,82 @@ pub enum ScreenshotType {
     Jpeg,
 }
 
+impl ScreenshotType {
+    /// Format name as used by CDP `Page.captureScreenshot`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScreenshotType::Png => "png",
+            ScreenshotType::Jpeg => "jpeg",
+        }
+    }
+}
+
+/// Animation handling for screenshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotAnimations {
+    /// Capture animations as they are
+    Allow,
+    /// Finish finite animations and cancel infinite ones while capturing
+    Disabled,
+}
+
+/// Options for `BrowserContext::storage_state`
+///
+/// `Option<path>` converts into these options, so
+/// `context.storage_state(Some("auth.json"))` keeps working.
+#[derive(Debug, Clone, Builder, Default)]
+#[builder(default, setter(into, strip_option))]
+pub struct StorageStateOptions {
+    /// File to also write the storage state to, as JSON
+    pub path: Option<PathBuf>,
+
+    /// Include each origin's IndexedDB databases. Defaults to false.
+    pub indexed_db: Option<bool>,
+
+    /// Only capture storage for these origins, e.g. `https://example.com`.
+    /// Cookies are always captured. Defaults to every open page's origin.
+    pub origins: Option<Vec<String>>,
+}
+
+impl<P: Into<PathBuf>> From<Option<P>> for StorageStateOptions {
+    fn from(path: Option<P>) -> Self {
+        Self {
+            path: path.map(Into::into),
+            ..Default::default()
+        }
+    }
+}
+
+/// Value for one field of `Page::fill_form`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormValue {
+    /// Text for an input or textarea, applied with `fill`
+    Text(String),
+    /// State for a checkbox or radio button, applied with `set_checked`
+    Checked(bool),
+    /// Value or label of a `<select>` option, applied with `select_option`
+    Select(String),
+}
+
+impl From<&str> for FormValue {
+    fn from(text: &str) -> Self {
+        FormValue::Text(text.to_string())
+    }
+}
+
+impl From<String> for FormValue {
+    fn from(text: String) -> Self {
+        FormValue::Text(text)
+    }
+}
+
+impl From<bool> for FormValue {
+    fn from(checked: bool) -> Self {
+        FormValue::Checked(checked)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -545,6 +1077,74 @@ mod tests {
     }
 
     #[test]
+    fn test_keyboard_modifier_parse_list() {
+        let mods = KeyboardModifier::parse_list("ctrl + Shift+ALT").unwrap();
+        assert_eq!(
+            mods,
+            vec![KeyboardModifier::Control, KeyboardModifier::Shift, KeyboardModifier::Alt]
+        );
+        assert!(KeyboardModifier::parse_list("").unwrap().is_empty());
+        assert!(KeyboardModifier::parse_list("Control+Hyper").is_err());
+    }
+
+    #[test]
+    fn test_slow_mo_scoped_to_input_skips_navigation() {
+        let config = SlowMoConfig::input_only(Duration::from_millis(250));
+        assert_eq!(config.delay_for(SlowMoScope::Input), Some(Duration::from_millis(250)));
+        assert_eq!(config.delay_for(SlowMoScope::Navigation), None);
+        assert_eq!(config.delay_for(SlowMoScope::Script), None);
+
+        // A bare duration keeps the old behavior of delaying everything
+        let config = SlowMoConfig::from(Duration::from_millis(100));
+        assert_eq!(config.delay_for(SlowMoScope::Navigation), Some(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_retry_backoff_grows_to_max_delay() {
+        let retry = RetryOptionsBuilder::default()
+            .initial_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500))
+            .multiplier(2.0)
+            .build()
+            .unwrap();
+
+        let delays: Vec<_> = (0..5).map(|attempt| retry.delay_for(attempt)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 500, 500].map(Duration::from_millis).to_vec()
+        );
+        // Without jitter the random sample is ignored
+        assert_eq!(retry.jittered_delay_for(1, 0.9), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_retry_jitter_shortens_delay() {
+        let retry = RetryOptionsBuilder::default().jitter(0.5).build().unwrap();
+        assert_eq!(retry.initial_delay, Duration::from_millis(100));
+        assert_eq!(retry.jittered_delay_for(0, 0.0), Duration::from_millis(100));
+        assert_eq!(retry.jittered_delay_for(0, 0.5), Duration::from_millis(75));
+        assert_eq!(retry.jittered_delay_for(0, 0.999), retry.delay_for(0).mul_f64(1.0 - 0.5 * 0.999));
+    }
+
+    #[test]
+    fn test_form_value_conversions() {
+        assert_eq!(FormValue::from("Ada"), FormValue::Text("Ada".to_string()));
+        assert_eq!(FormValue::from(String::from("Ada")), FormValue::Text("Ada".to_string()));
+        assert_eq!(FormValue::from(true), FormValue::Checked(true));
+    }
+
+    #[test]
+    fn test_storage_state_options_from_path() {
+        let options = StorageStateOptions::from(Some("auth.json"));
+        assert_eq!(options.path, Some(PathBuf::from("auth.json")));
+        assert_eq!(options.indexed_db, None);
+
+        let options = StorageStateOptions::from(None::<&str>);
+        assert!(options.path.is_none() && options.origins.is_none());
+    }
+
+    #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_wait_until_state_clone() {
         // Test that WaitUntilState is Clone
         let state = WaitUntilState::NetworkIdle;
@@ -0,0 +1,113 @@
+//! Decoded screenshots
+//!
+//! Wraps the encoded bytes returned by `Page.captureScreenshot` together with
+//! the image decoded from them, so image diffs don't have to parse the PNG
+//! or JPEG again.
+
+use std::path::Path;
+
+use image::DynamicImage;
+
+use crate::core::{Error, Result, ScreenshotType};
+
+/// A screenshot with its encoded bytes and decoded pixels
+///
+/// Returned by [`Page::screenshot_image`](crate::async_api::Page::screenshot_image).
+/// Dimensions are in device pixels, i.e. the CSS size times the device scale
+/// factor.
+#[derive(Debug, Clone)]
+pub struct ScreenshotImage {
+    bytes: Vec<u8>,
+    format: ScreenshotType,
+    image: DynamicImage,
+}
+
+impl ScreenshotImage {
+    /// Decode encoded screenshot bytes
+    pub(crate) fn decode(bytes: Vec<u8>, format: ScreenshotType) -> Result<Self> {
+        let image_format = match format {
+            ScreenshotType::Png => image::ImageFormat::Png,
+            ScreenshotType::Jpeg => image::ImageFormat::Jpeg,
+        };
+        let image = image::load_from_memory_with_format(&bytes, image_format).map_err(|e| {
+            Error::ScreenshotFailed(format!("Failed to decode {} screenshot: {}", format.as_str(), e))
+        })?;
+        Ok(Self { bytes, format, image })
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Encoding of [`ScreenshotImage::bytes`]
+    pub fn format(&self) -> ScreenshotType {
+        self.format
+    }
+
+    /// The encoded PNG or JPEG bytes, as captured
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consume the screenshot, returning the encoded bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The decoded image
+    pub fn image(&self) -> &DynamicImage {
+        &self.image
+    }
+
+    /// Write the encoded bytes to `path`
+    ///
+    /// Parent directories are created as needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, &self.bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::new_rgba8(width, height)
+            .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_decode_reports_dimensions() {
+        let bytes = png(640, 360);
+        let screenshot = ScreenshotImage::decode(bytes.clone(), ScreenshotType::Png).unwrap();
+        assert_eq!((screenshot.width(), screenshot.height()), (640, 360));
+        assert_eq!(screenshot.bytes(), bytes.as_slice());
+
+        let path = std::env::temp_dir()
+            .join(format!("sparkle-screenshot-{}", std::process::id()))
+            .join("shot.png");
+        screenshot.save(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_decode_rejects_mismatched_format() {
+        let result = ScreenshotImage::decode(png(1, 1), ScreenshotType::Jpeg);
+        assert!(matches!(result, Err(Error::ScreenshotFailed(_))));
+    }
+}
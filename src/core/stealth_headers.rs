@@ -3,6 +3,8 @@
 //! This module generates realistic browser headers that match the Chrome version
 //! and avoid detection by anti-bot systems.
 
+use std::collections::HashMap;
+
 use crate::core::StealthOptions;
 
 /// Generate User-Agent string for Chrome
@@ -40,17 +42,30 @@ pub fn generate_user_agent(chrome_version: &str, platform: &str) -> String {
     }
 }
 
+/// Brands Chrome reports in client hints, with their versions
+///
+/// `full` selects the full version instead of the major one, as in
+/// `Sec-CH-UA-Full-Version-List`.
+fn brands(chrome_version: &str, full: bool) -> Vec<(&'static str, String)> {
+    let major_version = chrome_version.split('.').next().unwrap_or("120");
+    let version = if full { chrome_version } else { major_version };
+    vec![
+        ("Not_A Brand", if full { "8.0.0.0" } else { "8" }.to_string()),
+        ("Chromium", version.to_string()),
+        ("Google Chrome", version.to_string()),
+    ]
+}
+
 /// Generate sec-ch-ua header for Chrome
 ///
 /// # Arguments
 /// * `chrome_version` - Chrome version (e.g., "120.0.6099.129")
 pub fn generate_sec_ch_ua(chrome_version: &str) -> String {
-    let major_version = chrome_version.split('.').next().unwrap_or("120");
-
-    format!(
-        r#""Not_A Brand";v="8", "Chromium";v="{}", "Google Chrome";v="{}""#,
-        major_version, major_version
-    )
+    brands(chrome_version, false)
+        .iter()
+        .map(|(brand, version)| format!(r#""{}";v="{}""#, brand, version))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Generate sec-ch-ua-mobile header (always "?0" for desktop)
@@ -71,6 +86,40 @@ pub fn generate_sec_ch_ua_platform(platform: &str) -> String {
     }
 }
 
+/// Generate `userAgentMetadata` for CDP `Network.setUserAgentOverride`
+///
+/// Chrome derives its client hint headers and `navigator.userAgentData`
+/// from this, so it uses the same brands as [`generate_sec_ch_ua`].
+///
+/// # Arguments
+/// * `chrome_version` - Chrome version (e.g., "120.0.6099.129")
+/// * `platform` - Platform string (e.g., "Win32", "MacIntel", "Linux x86_64")
+pub fn generate_user_agent_metadata(chrome_version: &str, platform: &str) -> serde_json::Value {
+    let brand_list = |full| {
+        brands(chrome_version, full)
+            .into_iter()
+            .map(|(brand, version)| serde_json::json!({ "brand": brand, "version": version }))
+            .collect::<Vec<_>>()
+    };
+    let platform_version = match platform {
+        "MacIntel" => "10.15.7",
+        "Linux x86_64" => "",
+        _ => "10.0.0",
+    };
+    serde_json::json!({
+        "brands": brand_list(false),
+        "fullVersionList": brand_list(true),
+        "fullVersion": chrome_version,
+        "platform": generate_sec_ch_ua_platform(platform).trim_matches('"'),
+        "platformVersion": platform_version,
+        "architecture": "x86",
+        "model": "",
+        "mobile": false,
+        "bitness": "64",
+        "wow64": false,
+    })
+}
+
 /// Generate Accept-Language header from locale
 ///
 /// # Arguments
@@ -102,12 +151,19 @@ pub fn get_platform_string() -> String {
 }
 
 /// Headers configuration for CDP Network.setUserAgentOverride
+///
+/// Serializes to the command's parameters; `client_hints` are sent
+/// separately with `Network.setExtraHTTPHeaders`.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HeadersConfig {
     pub user_agent: String,
     pub accept_language: String,
     pub platform: String,
+    pub user_agent_metadata: serde_json::Value,
+    /// `sec-ch-ua`, `sec-ch-ua-mobile` and `sec-ch-ua-platform` matching the user agent
+    #[serde(skip)]
+    pub client_hints: HashMap<String, String>,
 }
 
 impl HeadersConfig {
@@ -117,11 +173,18 @@ impl HeadersConfig {
         let user_agent = generate_user_agent(chrome_version, &platform);
         let locale = stealth_options.locale.as_deref().unwrap_or("en-US");
         let accept_language = generate_accept_language(locale);
+        let client_hints = HashMap::from([
+            ("sec-ch-ua".to_string(), generate_sec_ch_ua(chrome_version)),
+            ("sec-ch-ua-mobile".to_string(), generate_sec_ch_ua_mobile()),
+            ("sec-ch-ua-platform".to_string(), generate_sec_ch_ua_platform(&platform)),
+        ]);
 
         Self {
+            user_agent_metadata: generate_user_agent_metadata(chrome_version, &platform),
             user_agent,
             accept_language,
             platform,
+            client_hints,
         }
     }
 }
@@ -152,6 +215,33 @@ mod tests {
         assert!(header.contains(r#""Google Chrome";v="120""#));
     }
 
+    #[test]
+    fn test_client_hints_match_user_agent_version() {
+        let config = HeadersConfig::from_stealth_options(&StealthOptions::default(), "131.0.6778.85");
+        assert!(config.user_agent.contains("Chrome/131.0.6778.85"));
+        assert_eq!(
+            config.client_hints["sec-ch-ua"],
+            r#""Not_A Brand";v="8", "Chromium";v="131", "Google Chrome";v="131""#
+        );
+        assert_eq!(config.client_hints["sec-ch-ua-mobile"], "?0");
+        assert_eq!(
+            config.client_hints["sec-ch-ua-platform"],
+            generate_sec_ch_ua_platform(&config.platform)
+        );
+
+        let metadata = &config.user_agent_metadata;
+        assert_eq!(metadata["brands"][2], serde_json::json!({ "brand": "Google Chrome", "version": "131" }));
+        assert_eq!(metadata["fullVersionList"][1]["version"], "131.0.6778.85");
+        assert_eq!(
+            format!(r#""{}""#, metadata["platform"].as_str().unwrap()),
+            config.client_hints["sec-ch-ua-platform"]
+        );
+
+        let params = serde_json::to_value(&config).unwrap();
+        assert!(params.get("userAgentMetadata").is_some());
+        assert!(params.get("clientHints").is_none(), "client hints are not a setUserAgentOverride parameter");
+    }
+
     #[test]
     fn test_generate_accept_language() {
         let lang = generate_accept_language("en-US");
@@ -19,7 +19,7 @@ use std::path::PathBuf;
 /// let state = context.storage_state(Some("auth.json")).await?;
 ///
 /// // Or get storage state without saving to file
-/// let state = context.storage_state(None).await?;
+/// let state = context.storage_state(None::<&str>).await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -107,7 +107,20 @@ impl StorageState {
 
 /// Represents a single cookie in the storage state
 ///
-/// This matches Playwright's cookie format.
+/// This matches Playwright's cookie format. When setting cookies, give either
+/// a `url` or both `domain` and `path`.
+///
+/// # Example
+/// ```
+/// # use sparkle::core::CookieState;
+/// // Scoped by URL: host-only for app.example.com, path "/", secure
+/// let cookie = CookieState {
+///     name: "session".to_string(),
+///     value: "abc123".to_string(),
+///     url: Some("https://app.example.com/".to_string()),
+///     ..Default::default()
+/// };
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CookieState {
@@ -117,10 +130,20 @@ pub struct CookieState {
     /// Cookie value
     pub value: String,
 
+    /// URL the cookie is set for, instead of `domain` and `path`
+    ///
+    /// The cookie is host-only for the URL's host, its path is the URL's
+    /// directory, and it is secure for `https` URLs. Only used when setting
+    /// cookies; cookies read back report `domain` and `path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
     /// Cookie domain (e.g., ".example.com")
+    #[serde(default)]
     pub domain: String,
 
     /// Cookie path (e.g., "/")
+    #[serde(default)]
     pub path: String,
 
     /// Expiration time in Unix seconds. Use -1 for session cookies.
@@ -134,25 +157,70 @@ pub struct CookieState {
 
     /// SameSite attribute
     pub same_site: SameSite,
+
+    /// Chromium cookie priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<CookiePriority>,
+
+    /// Top-level site of a partitioned (CHIPS) cookie, e.g. "https://example.com"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_key: Option<String>,
+
+    /// Chromium `SameParty` attribute for First-Party Sets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_party: Option<bool>,
 }
 
-/// SameSite cookie attribute
+impl Default for CookieState {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            value: String::new(),
+            url: None,
+            domain: String::new(),
+            path: String::new(),
+            expires: -1.0,
+            http_only: false,
+            secure: false,
+            same_site: SameSite::default(),
+            priority: None,
+            partition_key: None,
+            same_party: None,
+        }
+    }
+}
+
+/// Chromium cookie priority
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CookiePriority {
+    Low,
+    Medium,
+    High,
+}
+
+impl CookiePriority {
+    /// Name used by CDP
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CookiePriority::Low => "Low",
+            CookiePriority::Medium => "Medium",
+            CookiePriority::High => "High",
+        }
+    }
+}
+
+/// SameSite cookie attribute
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum SameSite {
     /// Strict mode - cookie only sent in first-party context
     Strict,
     /// Lax mode - cookie sent with top-level navigation
+    #[default]
     Lax,
     /// None mode - cookie sent in all contexts (requires Secure)
     None,
 }
 
-impl Default for SameSite {
-    fn default() -> Self {
-        Self::Lax
-    }
-}
-
 /// Represents storage (localStorage and sessionStorage) for a single origin
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -167,6 +235,91 @@ pub struct OriginState {
     /// sessionStorage entries (note: sessionStorage is typically ephemeral)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub session_storage: Vec<NameValue>,
+
+    /// IndexedDB databases, captured only when requested
+    #[serde(default, rename = "indexedDB", skip_serializing_if = "Vec::is_empty")]
+    pub indexed_db: Vec<IndexedDbDatabase>,
+}
+
+/// An IndexedDB database in the storage state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedDbDatabase {
+    /// Database name
+    pub name: String,
+
+    /// Database version
+    pub version: u64,
+
+    /// Object stores with their records
+    #[serde(default)]
+    pub stores: Vec<IndexedDbStore>,
+}
+
+/// An IndexedDB object store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedDbStore {
+    /// Store name
+    pub name: String,
+
+    /// Whether keys are generated by the store
+    #[serde(default)]
+    pub auto_increment: bool,
+
+    /// In-line key path, when it is a single path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path: Option<String>,
+
+    /// In-line key path, when it is a compound path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path_array: Option<Vec<String>>,
+
+    /// Stored records
+    #[serde(default)]
+    pub records: Vec<IndexedDbRecord>,
+
+    /// Indexes on the store
+    #[serde(default)]
+    pub indexes: Vec<IndexedDbIndex>,
+}
+
+/// A record in an IndexedDB object store
+///
+/// Values are kept as JSON, so types without a JSON form (such as `Date` or
+/// `Blob`) are not preserved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedDbRecord {
+    /// Out-of-line key; `None` for stores with a key path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<serde_json::Value>,
+
+    /// Record value
+    pub value: serde_json::Value,
+}
+
+/// An index on an IndexedDB object store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedDbIndex {
+    /// Index name
+    pub name: String,
+
+    /// Indexed key path, when it is a single path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path: Option<String>,
+
+    /// Indexed key path, when it is a compound path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_path_array: Option<Vec<String>>,
+
+    /// Whether array values add one index entry per element
+    #[serde(default)]
+    pub multi_entry: bool,
+
+    /// Whether the index rejects duplicate keys
+    #[serde(default)]
+    pub unique: bool,
 }
 
 /// A name-value pair for storage entries
@@ -235,6 +388,10 @@ mod tests {
                 http_only: true,
                 secure: true,
                 same_site: SameSite::Lax,
+                priority: None,
+                partition_key: None,
+                url: None,
+                same_party: None,
             }],
             origins: vec![OriginState {
                 origin: "https://example.com".to_string(),
@@ -243,6 +400,7 @@ mod tests {
                     value: "john".to_string(),
                 }],
                 session_storage: vec![],
+                indexed_db: vec![],
             }],
         };
 
@@ -259,6 +417,49 @@ mod tests {
         assert_eq!(parsed.origins[0].origin, "https://example.com");
     }
 
+    #[test]
+    fn test_indexed_db_section_only_when_captured() {
+        let origin = |indexed_db| OriginState {
+            origin: "https://example.com".to_string(),
+            local_storage: vec![],
+            session_storage: vec![],
+            indexed_db,
+        };
+
+        let json = serde_json::to_value(origin(vec![])).unwrap();
+        assert!(json.get("indexedDB").is_none());
+
+        let databases = vec![IndexedDbDatabase {
+            name: "app".to_string(),
+            version: 2,
+            stores: vec![IndexedDbStore {
+                name: "todos".to_string(),
+                auto_increment: false,
+                key_path: Some("id".to_string()),
+                key_path_array: None,
+                records: vec![IndexedDbRecord {
+                    key: None,
+                    value: serde_json::json!({ "id": 1, "title": "Write tests" }),
+                }],
+                indexes: vec![IndexedDbIndex {
+                    name: "by_title".to_string(),
+                    key_path: Some("title".to_string()),
+                    key_path_array: None,
+                    multi_entry: false,
+                    unique: true,
+                }],
+            }],
+        }];
+        let json = serde_json::to_value(origin(databases)).unwrap();
+        assert_eq!(json["indexedDB"][0]["stores"][0]["keyPath"], "id");
+        assert_eq!(json["indexedDB"][0]["stores"][0]["records"][0]["value"]["title"], "Write tests");
+        assert_eq!(json["indexedDB"][0]["stores"][0]["indexes"][0]["unique"], true);
+
+        let parsed: OriginState = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.indexed_db[0].version, 2);
+        assert_eq!(parsed.indexed_db[0].stores[0].records.len(), 1);
+    }
+
     #[test]
     fn test_same_site_serialization() {
         let json = serde_json::to_string(&SameSite::Strict).unwrap();
@@ -304,6 +505,10 @@ mod tests {
                     http_only: true,
                     secure: true,
                     same_site: SameSite::Lax,
+                    priority: None,
+                    partition_key: None,
+                    url: None,
+                    same_party: None,
                 },
                 CookieState {
                     name: "session".to_string(),
@@ -314,6 +519,10 @@ mod tests {
                     http_only: false,
                     secure: true,
                     same_site: SameSite::Strict,
+                    priority: None,
+                    partition_key: None,
+                    url: None,
+                    same_party: None,
                 },
             ],
             origins: vec![OriginState {
@@ -332,6 +541,7 @@ mod tests {
                     name: "temp_data".to_string(),
                     value: "temporary".to_string(),
                 }],
+                indexed_db: vec![],
             }],
         };
 
@@ -368,6 +578,10 @@ mod tests {
                     http_only: false,
                     secure: false,
                     same_site: SameSite::Strict,
+                    priority: None,
+                    partition_key: None,
+                    url: None,
+                    same_party: None,
                 },
                 CookieState {
                     name: "cookie2".to_string(),
@@ -378,6 +592,10 @@ mod tests {
                     http_only: false,
                     secure: false,
                     same_site: SameSite::Lax,
+                    priority: None,
+                    partition_key: None,
+                    url: None,
+                    same_party: None,
                 },
                 CookieState {
                     name: "cookie3".to_string(),
@@ -388,6 +606,10 @@ mod tests {
                     http_only: false,
                     secure: true,
                     same_site: SameSite::None,
+                    priority: None,
+                    partition_key: None,
+                    url: None,
+                    same_party: None,
                 },
             ],
             origins: vec![],
@@ -415,6 +637,7 @@ mod tests {
                     name: "temporary".to_string(),
                     value: "session_data".to_string(),
                 }],
+                indexed_db: vec![],
             }],
         };
 
@@ -436,6 +659,7 @@ mod tests {
                 origin: "https://example.com".to_string(),
                 local_storage: vec![],
                 session_storage: vec![],
+                indexed_db: vec![],
             }],
         };
 
@@ -443,4 +667,58 @@ mod tests {
         let parsed = StorageState::from_json(&json).unwrap();
         assert_eq!(parsed.origins[0].session_storage.len(), 0);
     }
+
+    #[test]
+    fn test_partitioned_cookie_round_trip() {
+        let state = StorageState {
+            cookies: vec![CookieState {
+                name: "embed_session".to_string(),
+                value: "xyz".to_string(),
+                domain: "widget.example".to_string(),
+                path: "/".to_string(),
+                expires: -1.0,
+                http_only: true,
+                secure: true,
+                same_site: SameSite::None,
+                priority: Some(CookiePriority::High),
+                partition_key: Some("https://news.example".to_string()),
+                same_party: Some(true),
+                url: None,
+            }],
+            origins: vec![],
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains("\"partitionKey\":\"https://news.example\""));
+        assert!(json.contains("\"priority\":\"High\""));
+        assert!(json.contains("\"sameParty\":true"));
+
+        let parsed: StorageState = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.cookies[0].partition_key.as_deref(), Some("https://news.example"));
+        assert_eq!(parsed.cookies[0].priority, Some(CookiePriority::High));
+    }
+
+    #[test]
+    fn test_cookie_without_modern_fields_deserializes() {
+        let json = r#"{"cookies":[{"name":"a","value":"b","domain":"example.com","path":"/","expires":-1,"httpOnly":false,"secure":false,"sameSite":"Lax"}],"origins":[]}"#;
+        let parsed: StorageState = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.cookies[0].priority, None);
+        assert_eq!(parsed.cookies[0].partition_key, None);
+
+        // Absent fields are not written back out
+        let json = serde_json::to_string(&parsed).unwrap();
+        assert!(!json.contains("partitionKey"));
+        assert!(!json.contains("priority"));
+        assert!(!json.contains("sameParty"));
+        assert!(!json.contains("\"url\""));
+    }
+
+    #[test]
+    fn test_url_scoped_cookie_deserializes() {
+        let json = r#"{"cookies":[{"name":"a","value":"b","url":"https://example.com/app/","expires":-1,"httpOnly":false,"secure":false,"sameSite":"Lax"}],"origins":[]}"#;
+        let parsed: StorageState = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.cookies[0].url.as_deref(), Some("https://example.com/app/"));
+        assert!(parsed.cookies[0].domain.is_empty());
+        assert!(parsed.cookies[0].path.is_empty());
+    }
 }
@@ -7,11 +7,11 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::core::StealthOptions;
+use crate::core::{HeadlessMode, StealthOptions};
 
 /// Builder for Chromium capabilities
 pub struct ChromiumCapabilities {
-    headless: bool,
+    headless: HeadlessMode,
     args: Vec<String>,
     binary: Option<PathBuf>,
     env: HashMap<String, String>,
@@ -19,13 +19,37 @@ pub struct ChromiumCapabilities {
     downloads_path: Option<PathBuf>,
     stealth: Option<StealthOptions>,
     w3c: bool,
+    edge: bool,
+    extra: serde_json::Map<String, serde_json::Value>,
+    ignored_default_args: Vec<String>,
+    window_size: Option<(u32, u32)>,
+}
+
+/// Whether a browser channel (`msedge`, `msedge-beta`, ...) is Microsoft Edge
+pub(crate) fn is_edge_channel(channel: &str) -> bool {
+    channel.starts_with("msedge")
+}
+
+/// Whether command-line argument `arg` matches the ignore entry `pattern`
+///
+/// An entry without a value, like `--disable-blink-features`, matches the
+/// argument by name whatever its value; an entry with one, like
+/// `--headless=new`, only matches that exact argument. Leading dashes are
+/// optional on both sides.
+pub(crate) fn matches_arg(arg: &str, pattern: &str) -> bool {
+    let arg = arg.trim_start_matches('-');
+    let pattern = pattern.trim_start_matches('-');
+    match pattern.split_once('=') {
+        Some(_) => arg == pattern,
+        None => arg.split_once('=').map_or(arg, |(name, _)| name) == pattern,
+    }
 }
 
 impl ChromiumCapabilities {
     /// Create a new capabilities builder
     pub fn new() -> Self {
         Self {
-            headless: true,
+            headless: HeadlessMode::New,
             args: Vec::new(),
             binary: None,
             env: HashMap::new(),
@@ -33,12 +57,26 @@ impl ChromiumCapabilities {
             downloads_path: None,
             stealth: None,
             w3c: false,
+            edge: false,
+            extra: serde_json::Map::new(),
+            ignored_default_args: Vec::new(),
+            window_size: None,
         }
     }
 
-    /// Set headless mode
+    /// Set headless mode (`true` uses the new headless implementation)
     pub fn headless(mut self, headless: bool) -> Self {
-        self.headless = headless;
+        self.headless = if headless {
+            HeadlessMode::New
+        } else {
+            HeadlessMode::Off
+        };
+        self
+    }
+
+    /// Set the headless implementation explicitly
+    pub fn headless_mode(mut self, mode: HeadlessMode) -> Self {
+        self.headless = mode;
         self
     }
 
@@ -107,6 +145,42 @@ impl ChromiumCapabilities {
         self
     }
 
+    /// Request a WebDriver BiDi websocket for the session (`webSocketUrl`)
+    ///
+    /// BiDi needs a W3C session, so this also turns on W3C mode.
+    pub fn web_socket_url(mut self, enabled: bool) -> Self {
+        self.extra.insert("webSocketUrl".to_string(), json!(enabled));
+        if enabled {
+            self.w3c = true;
+        }
+        self
+    }
+
+    /// Target Microsoft Edge, emitting `ms:edgeOptions` instead of `goog:chromeOptions`
+    pub fn edge(mut self, edge: bool) -> Self {
+        self.edge = edge;
+        self
+    }
+
+    /// Leave out built-in arguments matching any of `args` (see `LaunchOptions::ignore_default_args`)
+    pub fn ignore_default_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.ignored_default_args.extend(args.into_iter().map(|a| a.into()));
+        self
+    }
+
+    /// Configure for a browser distribution channel; `msedge*` channels enable Edge mode
+    pub fn channel(self, channel: &str) -> Self {
+        self.edge(is_edge_channel(channel))
+    }
+
+    /// Open the browser window at `width` x `height` (`--window-size`)
+    ///
+    /// Ignored if the arguments already contain a `--window-size`.
+    pub fn window_size(mut self, width: u32, height: u32) -> Self {
+        self.window_size = Some((width, height));
+        self
+    }
+
     /// Add proxy configuration via command-line arguments
     pub fn proxy(mut self, server: &str, bypass: Option<&str>) -> Self {
         self.args.push(format!("--proxy-server={}", server));
@@ -117,16 +191,27 @@ impl ChromiumCapabilities {
         self
     }
 
+    /// Merge arbitrary W3C capabilities (e.g. `se:name`, `platformName`)
+    ///
+    /// User-provided keys take precedence over the built-in ones. When both
+    /// values are objects (such as `goog:chromeOptions`), their keys are merged
+    /// one level deep so the built-in `args` survive unless overridden.
+    pub fn capabilities(mut self, capabilities: serde_json::Map<String, serde_json::Value>) -> Self {
+        self.extra.extend(capabilities);
+        self
+    }
+
     /// Build the capabilities as a HashMap
     pub fn build(self) -> HashMap<String, serde_json::Value> {
         let mut args = self.args;
+        let mut default_args = Vec::new();
 
         // Apply stealth mode switches (Patchright-style)
         let stealth_enabled = self.stealth.as_ref().map(|s| s.enabled).unwrap_or(true);
 
         if stealth_enabled {
             // Add stealth-specific arguments
-            args.push("--disable-blink-features=AutomationControlled".to_string());
+            default_args.push("--disable-blink-features=AutomationControlled".to_string());
 
             // Remove automation-revealing arguments that might be added elsewhere
             // Patchright removes these to avoid detection:
@@ -146,9 +231,29 @@ impl ChromiumCapabilities {
             });
         }
 
-        if self.headless {
-            args.push("--headless".to_string());
-            args.push("--disable-gpu".to_string());
+        match self.headless {
+            HeadlessMode::New => {
+                default_args.push("--headless=new".to_string());
+                default_args.push("--disable-gpu".to_string());
+            }
+            HeadlessMode::Old => {
+                default_args.push("--headless=old".to_string());
+                default_args.push("--disable-gpu".to_string());
+            }
+            HeadlessMode::Off => {}
+        }
+
+        let ignored = &self.ignored_default_args;
+        args.extend(
+            default_args
+                .into_iter()
+                .filter(|arg| !ignored.iter().any(|pattern| matches_arg(arg, pattern))),
+        );
+
+        if let Some((width, height)) = self.window_size {
+            if !args.iter().any(|arg| matches_arg(arg, "--window-size")) {
+                args.push(format!("--window-size={},{}", width, height));
+            }
         }
 
         let mut chrome_options = json!({
@@ -190,8 +295,24 @@ impl ChromiumCapabilities {
         }
 
         let mut caps = HashMap::new();
-        caps.insert("browserName".to_string(), json!("chrome"));
-        caps.insert("goog:chromeOptions".to_string(), chrome_options);
+        if self.edge {
+            caps.insert("browserName".to_string(), json!("MicrosoftEdge"));
+            caps.insert("ms:edgeOptions".to_string(), chrome_options);
+        } else {
+            caps.insert("browserName".to_string(), json!("chrome"));
+            caps.insert("goog:chromeOptions".to_string(), chrome_options);
+        }
+
+        for (key, value) in self.extra {
+            match (caps.get_mut(&key), value) {
+                (Some(serde_json::Value::Object(existing)), serde_json::Value::Object(value)) => {
+                    existing.extend(value);
+                }
+                (_, value) => {
+                    caps.insert(key, value);
+                }
+            }
+        }
 
         caps
     }
@@ -227,4 +348,152 @@ mod tests {
 
         assert!(caps.contains_key("goog:chromeOptions"));
     }
+
+    #[test]
+    fn test_custom_capabilities_are_merged() {
+        let mut extra = serde_json::Map::new();
+        extra.insert("se:name".to_string(), json!("checkout flow"));
+        extra.insert("platformName".to_string(), json!("linux"));
+        extra.insert("goog:chromeOptions".to_string(), json!({ "prefs": { "intl.accept_languages": "de" } }));
+
+        let caps = ChromiumCapabilities::new()
+            .arg("--lang=de")
+            .capabilities(extra)
+            .build();
+
+        // Built-in defaults are kept
+        assert_eq!(caps["browserName"], json!("chrome"));
+        assert!(chrome_args(&caps).contains(&"--lang=de".to_string()));
+        // User-provided keys are added
+        assert_eq!(caps["se:name"], json!("checkout flow"));
+        assert_eq!(caps["platformName"], json!("linux"));
+        assert_eq!(caps["goog:chromeOptions"]["prefs"]["intl.accept_languages"], json!("de"));
+    }
+
+    #[test]
+    fn test_custom_capabilities_take_precedence() {
+        let mut extra = serde_json::Map::new();
+        extra.insert("browserName".to_string(), json!("chromium"));
+
+        let caps = ChromiumCapabilities::new().capabilities(extra).build();
+        assert_eq!(caps["browserName"], json!("chromium"));
+    }
+
+    fn chrome_args(caps: &HashMap<String, serde_json::Value>) -> Vec<String> {
+        caps["goog:chromeOptions"]["args"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|a| a.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_headless_new_mode_arg() {
+        let caps = ChromiumCapabilities::new()
+            .headless_mode(HeadlessMode::New)
+            .build();
+        assert!(chrome_args(&caps).contains(&"--headless=new".to_string()));
+
+        // The legacy boolean maps to the new headless mode
+        let caps = ChromiumCapabilities::new().headless(true).build();
+        assert!(chrome_args(&caps).contains(&"--headless=new".to_string()));
+
+        let caps = ChromiumCapabilities::new()
+            .headless_mode(HeadlessMode::Old)
+            .build();
+        assert!(chrome_args(&caps).contains(&"--headless=old".to_string()));
+
+        let caps = ChromiumCapabilities::new().headless(false).build();
+        assert!(!chrome_args(&caps).iter().any(|a| a.starts_with("--headless")));
+    }
+
+    #[test]
+    fn test_matches_arg_by_name() {
+        assert!(matches_arg("--disable-blink-features=AutomationControlled", "--disable-blink-features"));
+        assert!(matches_arg("--no-sandbox", "no-sandbox"));
+        assert!(matches_arg("--headless=new", "--headless=new"));
+        assert!(!matches_arg("--headless=old", "--headless=new"));
+        assert!(!matches_arg("--disable-gpu-sandbox", "--disable-gpu"));
+    }
+
+    #[test]
+    fn test_ignore_default_args_by_name() {
+        let caps = ChromiumCapabilities::new()
+            .headless(true)
+            .arg("--disable-blink-features=CSSPaintAPI")
+            .ignore_default_args(["--disable-blink-features"])
+            .build();
+        let args = chrome_args(&caps);
+
+        assert!(!args.contains(&"--disable-blink-features=AutomationControlled".to_string()));
+        // Unrelated defaults and the caller's own arguments remain
+        assert!(args.contains(&"--headless=new".to_string()));
+        assert!(args.contains(&"--disable-gpu".to_string()));
+        assert!(args.contains(&"--disable-blink-features=CSSPaintAPI".to_string()));
+    }
+
+    #[test]
+    fn test_web_socket_url_requests_w3c_bidi_session() {
+        let caps = ChromiumCapabilities::new().web_socket_url(true).build();
+        assert_eq!(caps["webSocketUrl"], json!(true));
+        assert_eq!(caps["goog:chromeOptions"]["w3c"], json!(true));
+
+        let caps = ChromiumCapabilities::new().build();
+        assert!(!caps.contains_key("webSocketUrl"));
+    }
+
+    #[test]
+    fn test_window_size_added_once() {
+        let window_size_args = |caps: HashMap<String, serde_json::Value>| -> Vec<String> {
+            caps["goog:chromeOptions"]["args"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter_map(|arg| arg.as_str())
+                .filter(|arg| arg.starts_with("--window-size"))
+                .map(str::to_string)
+                .collect()
+        };
+
+        let caps = ChromiumCapabilities::new().window_size(1280, 720).build();
+        assert_eq!(window_size_args(caps), vec!["--window-size=1280,720"]);
+
+        // An explicit argument is kept instead of the option
+        let caps = ChromiumCapabilities::new()
+            .arg("--window-size=800,600")
+            .window_size(1280, 720)
+            .build();
+        assert_eq!(window_size_args(caps), vec!["--window-size=800,600"]);
+    }
+
+    #[test]
+    fn test_edge_channel_capabilities() {
+        for channel in ["msedge", "msedge-beta", "msedge-dev"] {
+            let caps = ChromiumCapabilities::new().channel(channel).build();
+            assert!(caps.contains_key("ms:edgeOptions"), "{} should use ms:edgeOptions", channel);
+        }
+
+        let caps = ChromiumCapabilities::new().channel("chrome-beta").build();
+        assert!(caps.contains_key("goog:chromeOptions"));
+        assert!(!caps.contains_key("ms:edgeOptions"));
+    }
+
+    #[test]
+    fn test_edge_capabilities() {
+        let caps = ChromiumCapabilities::new()
+            .edge(true)
+            .binary(PathBuf::from("/opt/microsoft/msedge/msedge"))
+            .arg("--disable-dev-shm-usage")
+            .build();
+
+        assert_eq!(caps.get("browserName").unwrap(), &json!("MicrosoftEdge"));
+        assert!(!caps.contains_key("goog:chromeOptions"));
+        let edge_options = caps.get("ms:edgeOptions").unwrap();
+        assert_eq!(edge_options["binary"], json!("/opt/microsoft/msedge/msedge"));
+        assert!(edge_options["args"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("--disable-dev-shm-usage")));
+    }
 }
@@ -3,12 +3,168 @@
 //! This module handles launching and managing the ChromeDriver process.
 
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
 use std::path::PathBuf;
-use std::process::{Child, Command};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Weak};
 use std::time::Duration;
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 
+/// ChromeDriver shared by every launch that opts into driver reuse
+static SHARED_DRIVER: Mutex<Option<SharedDriver>> = Mutex::const_new(None);
+
+/// The shared ChromeDriver and the settings it was launched with
+///
+/// Only the leases keep the process alive; this entry lets later launches
+/// find it while any lease is held.
+struct SharedDriver {
+    process: Weak<SharedProcess>,
+    executable_path: Option<PathBuf>,
+    env: HashMap<String, String>,
+}
+
+/// The shared ChromeDriver process, stopped when the last lease is dropped
+///
+/// `None` once [`ChromeDriverProcess::shutdown_shared`] stopped it early.
+type SharedProcess = std::sync::Mutex<Option<ChromeDriverProcess>>;
+
+/// A browser's hold on the shared ChromeDriver
+///
+/// Returned by [`ChromeDriverProcess::launch_shared`]. The driver keeps
+/// running while any lease on it is alive and stops when the last one is
+/// dropped.
+pub struct SharedDriverLease {
+    _process: Arc<SharedProcess>,
+    url: String,
+}
+
+impl SharedDriverLease {
+    /// Get the ChromeDriver URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl std::fmt::Debug for SharedDriverLease {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedDriverLease").field("url", &self.url).finish()
+    }
+}
+
+impl SharedDriver {
+    /// The running process, if a lease still holds it and it hasn't exited
+    fn running(&self) -> Option<Arc<SharedProcess>> {
+        let process = self.process.upgrade()?;
+        let alive = match process.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            Some(driver) => matches!(driver.process.try_wait(), Ok(None)),
+            None => false,
+        };
+        alive.then_some(process)
+    }
+}
+
+/// Longest a single `/status` attempt may take to connect
+const STATUS_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Longest a single `/status` attempt may take in total
+const STATUS_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Client for polling the local driver's `/status`
+///
+/// Drops every proxy `builder` has, including the `HTTP_PROXY`/`HTTPS_PROXY`
+/// ones it would pick up from the environment, which would otherwise route
+/// the loopback request through a proxy that can't reach it. Each attempt is
+/// bounded so a socket that never answers doesn't use up the launch timeout.
+fn status_client(builder: reqwest::ClientBuilder) -> reqwest::Client {
+    builder
+        .no_proxy()
+        .connect_timeout(STATUS_CONNECT_TIMEOUT)
+        .timeout(STATUS_REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Poll `{url}/status` until the driver reports success or `timeout` passes
+///
+/// Gives up early if `process` exits.
+async fn wait_for_status(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: Duration,
+    mut process: Option<&mut Child>,
+) -> Result<()> {
+    let start = std::time::Instant::now();
+
+    loop {
+        if let Some(status) = process.as_mut().and_then(|process| process.try_wait().ok().flatten()) {
+            tracing::error!("ChromeDriver exited during startup: {}", status);
+            return Err(anyhow::anyhow!("ChromeDriver exited during startup ({})", status));
+        }
+
+        let remaining = timeout.saturating_sub(start.elapsed());
+        let attempt = client
+            .get(format!("{}/status", url))
+            .timeout(remaining.min(STATUS_REQUEST_TIMEOUT))
+            .send()
+            .await;
+        match attempt {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => tracing::debug!("ChromeDriver status returned {}", response.status()),
+            Err(error) => tracing::debug!("ChromeDriver status check failed: {}", error),
+        }
+
+        if start.elapsed() >= timeout {
+            tracing::error!("ChromeDriver failed to start within {:?}", timeout);
+            return Err(anyhow::anyhow!(
+                "ChromeDriver failed to start within {:?}",
+                timeout
+            ));
+        }
+
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Lines of driver output kept to explain a failed start
+const OUTPUT_TAIL_LINES: usize = 20;
+
+/// The most recent stdout/stderr lines of a driver process
+#[derive(Clone, Default)]
+struct OutputTail(Arc<std::sync::Mutex<VecDeque<String>>>);
+
+impl OutputTail {
+    /// Read `pipe` on a thread until it closes, logging each line at debug
+    /// level and keeping the last [`OUTPUT_TAIL_LINES`]
+    ///
+    /// Reading continues after a successful start so the driver never
+    /// blocks on a full pipe.
+    fn capture(&self, pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<()> {
+        let lines = Arc::clone(&self.0);
+        std::thread::spawn(move || {
+            for line in BufReader::new(pipe).lines().map_while(|line| line.ok()) {
+                tracing::debug!("ChromeDriver: {}", line);
+                let mut lines = lines.lock().unwrap();
+                if lines.len() == OUTPUT_TAIL_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+        })
+    }
+
+    /// `error` followed by the captured output, if there is any
+    fn explain(&self, error: anyhow::Error) -> anyhow::Error {
+        let lines = self.0.lock().unwrap();
+        if lines.is_empty() {
+            return error;
+        }
+        let output: Vec<&str> = lines.iter().map(|line| line.as_str()).collect();
+        anyhow::anyhow!("{}\nChromeDriver output:\n  {}", error, output.join("\n  "))
+    }
+}
+
 /// ChromeDriver process manager
 pub struct ChromeDriverProcess {
     process: Child,
@@ -60,34 +216,34 @@ impl ChromeDriverProcess {
             cmd.envs(env);
         }
         
-        let process = cmd.spawn()
+        let mut process = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .context(format!("Failed to launch ChromeDriver from {:?}", driver_path))?;
+        let output = OutputTail::default();
+        let readers = [
+            process.stdout.take().map(|stdout| output.capture(stdout)),
+            process.stderr.take().map(|stderr| output.capture(stderr)),
+        ];
 
         tracing::debug!("ChromeDriver process spawned, waiting for readiness (timeout: {:?})", timeout);
 
         // Wait for ChromeDriver to be ready
-        let client = reqwest::Client::new();
         let start = std::time::Instant::now();
-        
-        loop {
-            if let Ok(response) = client.get(&format!("{}/status", url)).send().await {
-                if response.status().is_success() {
-                    tracing::info!("ChromeDriver ready at {} (took {:?})", url, start.elapsed());
-                    println!("ChromeDriver launched successfully on {}", url);
-                    return Ok(Self { process, url });
-                }
-            }
-            
-            if start.elapsed() >= timeout {
-                tracing::error!("ChromeDriver failed to start within {:?}", timeout);
-                return Err(anyhow::anyhow!(
-                    "ChromeDriver failed to start within {:?}",
-                    timeout
-                ));
+        let client = status_client(reqwest::Client::builder());
+        if let Err(error) = wait_for_status(&client, &url, timeout, Some(&mut process)).await {
+            // Stop the driver so its pipes close and every line has been read
+            let _ = process.kill();
+            let _ = process.wait();
+            for reader in readers.into_iter().flatten() {
+                let _ = reader.join();
             }
-            
-            sleep(Duration::from_millis(100)).await;
+            return Err(output.explain(error));
         }
+        tracing::info!("ChromeDriver ready at {} (took {:?})", url, start.elapsed());
+        println!("ChromeDriver launched successfully on {}", url);
+        Ok(Self { process, url })
     }
 
     /// Get the ChromeDriver URL
@@ -95,6 +251,79 @@ impl ChromeDriverProcess {
         &self.url
     }
 
+    /// Get a lease on a shared ChromeDriver, launching it on first use
+    ///
+    /// Every caller gets the same driver process and creates its own WebDriver
+    /// session on it, so sessions stay isolated. The driver is relaunched if it
+    /// has exited. It keeps running until the last [`SharedDriverLease`] is
+    /// dropped or [`ChromeDriverProcess::shutdown_shared`] is called.
+    ///
+    /// A running driver can't take a different `executable_path` or `env`,
+    /// so asking for them fails until the shared driver is shut down.
+    ///
+    /// # Arguments
+    /// * `executable_path` - Optional path to ChromeDriver executable. If None, uses the installed path.
+    /// * `port` - Port to run ChromeDriver on
+    /// * `env` - Environment variables to set for the ChromeDriver process
+    /// * `timeout` - Maximum time to wait for ChromeDriver to start
+    pub async fn launch_shared(
+        executable_path: Option<PathBuf>,
+        port: u16,
+        env: &HashMap<String, String>,
+        timeout: Duration,
+    ) -> Result<SharedDriverLease> {
+        let mut shared = SHARED_DRIVER.lock().await;
+
+        if let Some(driver) = shared.as_ref() {
+            if let Some(process) = driver.running() {
+                let url = shared_process_url(&process).unwrap_or_default();
+                if driver.executable_path != executable_path || &driver.env != env {
+                    return Err(anyhow::anyhow!(
+                        "The shared ChromeDriver at {} was launched with a different executable path or environment; \
+                         close the browsers using it first or launch without reuse_driver",
+                        url
+                    ));
+                }
+                tracing::debug!("Reusing shared ChromeDriver at {}", url);
+                return Ok(SharedDriverLease { _process: process, url });
+            }
+            if driver.process.strong_count() > 0 {
+                tracing::warn!("Shared ChromeDriver exited, relaunching");
+            }
+        }
+
+        let process = Self::launch(executable_path.clone(), port, env, timeout).await?;
+        let url = process.url.clone();
+        let process = Arc::new(std::sync::Mutex::new(Some(process)));
+        *shared = Some(SharedDriver {
+            process: Arc::downgrade(&process),
+            executable_path,
+            env: env.clone(),
+        });
+        Ok(SharedDriverLease { _process: process, url })
+    }
+
+    /// Get the URL of the shared ChromeDriver, if one is running
+    pub async fn shared_url() -> Option<String> {
+        let shared = SHARED_DRIVER.lock().await;
+        let process = shared.as_ref()?.running()?;
+        shared_process_url(&process)
+    }
+
+    /// Stop the shared ChromeDriver now, even if browsers still hold leases on it
+    ///
+    /// The driver already stops when the last browser using it is closed or
+    /// dropped; this is for stopping it earlier. Browsers still using it lose
+    /// their driver connection.
+    pub async fn shutdown_shared() {
+        let Some(driver) = SHARED_DRIVER.lock().await.take() else { return };
+        let Some(process) = driver.process.upgrade() else { return };
+        let stopped = process.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(stopped) = stopped {
+            tracing::debug!("Stopping shared ChromeDriver at {}", stopped.url);
+        }
+    }
+
     /// Find the installed Chrome binary path (latest version)
     pub fn find_installed_chrome() -> Result<PathBuf> {
         // First check CHROME_PATH environment variable
@@ -114,10 +343,8 @@ impl ChromeDriverProcess {
         if let Ok(entries) = std::fs::read_dir(&install_dir) {
             for entry in entries.flatten() {
                 if let Ok(file_name) = entry.file_name().into_string() {
-                    if file_name.starts_with("chromium-") {
-                        if let Some(revision) = file_name.strip_prefix("chromium-") {
-                            versions.push((revision.to_string(), entry.path()));
-                        }
+                    if let Some(key) = chromium_install_key(&file_name) {
+                        versions.push((key, entry.path()));
                     }
                 }
             }
@@ -191,13 +418,11 @@ impl ChromeDriverProcess {
         if let Ok(entries) = std::fs::read_dir(&install_dir) {
             for entry in entries.flatten() {
                 if let Ok(file_name) = entry.file_name().into_string() {
-                    if file_name.starts_with("chromium-") {
+                    if let Some(key) = chromium_install_key(&file_name) {
                         // Check if chromedriver subdirectory exists
                         let driver_subdir = entry.path().join("chromedriver");
                         if driver_subdir.exists() {
-                            if let Some(revision) = file_name.strip_prefix("chromium-") {
-                                versions.push((revision.to_string(), driver_subdir));
-                            }
+                            versions.push((key, driver_subdir));
                         }
                     }
                 }
@@ -211,11 +436,8 @@ impl ChromeDriverProcess {
             ));
         }
 
-        // Sort versions to get the latest (semantic versioning comparison)
-        versions.sort_by(|a, b| {
-            // Simple version comparison - you could use a proper semver crate for production
-            b.0.cmp(&a.0)
-        });
+        // Sort versions to get the latest
+        versions.sort_by(|a, b| b.0.cmp(&a.0));
 
         let latest_driver_dir = &versions[0].1;
         
@@ -241,6 +463,30 @@ impl ChromeDriverProcess {
         ))
     }
 
+    /// Find msedgedriver, the WebDriver server for Microsoft Edge
+    ///
+    /// Uses `MSEDGEDRIVER_PATH` if set, otherwise looks for `msedgedriver` on
+    /// `PATH`. Sparkle doesn't install it; it must match the installed Edge.
+    pub fn find_msedgedriver() -> Result<PathBuf> {
+        if let Ok(path) = std::env::var("MSEDGEDRIVER_PATH") {
+            let path = PathBuf::from(path);
+            if path.exists() {
+                return Ok(path);
+            }
+            return Err(anyhow::anyhow!("MSEDGEDRIVER_PATH points to a missing file: {:?}", path));
+        }
+
+        let executable_name = if cfg!(windows) { "msedgedriver.exe" } else { "msedgedriver" };
+        std::env::var_os("PATH")
+            .and_then(|path| find_in_path(executable_name, &path))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "msedgedriver not found on PATH\nDownload the version matching your Edge from \
+                     https://developer.microsoft.com/microsoft-edge/tools/webdriver/ or set MSEDGEDRIVER_PATH"
+                )
+            })
+    }
+
     /// Get the install directory (same as CLI install command)
     fn get_install_dir() -> Result<PathBuf> {
         // Use Playwright's cache directory structure for compatibility
@@ -266,9 +512,231 @@ impl ChromeDriverProcess {
     }
 }
 
+/// First `executable` in the directories of a `PATH`-style list
+fn find_in_path(executable: &str, path: &std::ffi::OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path)
+        .map(|dir| dir.join(executable))
+        .find(|candidate| candidate.is_file())
+}
+
+/// URL of the shared process, unless it was shut down
+fn shared_process_url(process: &SharedProcess) -> Option<String> {
+    process.lock().unwrap_or_else(|e| e.into_inner()).as_ref().map(|driver| driver.url.clone())
+}
+
 impl Drop for ChromeDriverProcess {
     fn drop(&mut self) {
         // Kill the ChromeDriver process when the manager is dropped
         let _ = self.process.kill();
     }
 }
+
+/// Sort key of a Chromium install directory, greater for the one to prefer
+///
+/// Playwright builds and Chrome for Testing revisions are numbered
+/// differently, so the two kinds never compare by number: pinned installs
+/// only come into play when there is no Playwright-style install.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ChromiumInstallKey {
+    /// `chromium-pinned-{version}`, compared by Chrome version
+    Pinned(Vec<u64>),
+    /// `chromium-{revision}`, compared by Playwright revision
+    Revision(u64),
+}
+
+/// Sort key for a `chromium-{revision}` or `chromium-pinned-{version}` install directory
+///
+/// Other names give `None`.
+pub(crate) fn chromium_install_key(dir_name: &str) -> Option<ChromiumInstallKey> {
+    let rest = dir_name.strip_prefix("chromium-")?;
+    match rest.strip_prefix("pinned-") {
+        Some(version) => version
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()
+            .map(ChromiumInstallKey::Pinned),
+        None => rest.parse().ok().map(ChromiumInstallKey::Revision),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_dirs_compare_revisions_as_numbers() {
+        let mut dirs = vec![
+            "chromium-999",
+            "chromium-pinned-120.0.6099.71",
+            "chromium-1200",
+            "chromium-pinned-120.0.6099.109",
+            "chromium-pinned-119.0.6045.105",
+        ];
+        dirs.sort_by_key(|dir| std::cmp::Reverse(chromium_install_key(dir)));
+
+        // A pinned build's revision (1217362) says nothing against Playwright's (1200)
+        assert_eq!(
+            dirs,
+            [
+                "chromium-1200",
+                "chromium-999",
+                "chromium-pinned-120.0.6099.109",
+                "chromium-pinned-120.0.6099.71",
+                "chromium-pinned-119.0.6045.105",
+            ]
+        );
+        assert_eq!(chromium_install_key("chromium_headless_shell-1200"), None);
+        assert_eq!(chromium_install_key("chromium-pinned-latest"), None);
+    }
+
+    #[tokio::test]
+    async fn test_status_poll_bypasses_http_proxy() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A driver that is ready, and a proxy address nothing listens on
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let _ = stream.read(&mut request).await;
+                let body = r#"{"value":{"ready":true}}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        // Like a proxy picked up from `HTTP_PROXY`
+        let proxied = reqwest::Client::builder().proxy(reqwest::Proxy::http("http://127.0.0.1:9").unwrap());
+        let client = status_client(proxied);
+
+        wait_for_status(&client, &url, Duration::from_secs(5), None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stuck_status_socket_does_not_outlast_timeout() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+        let _server = tokio::spawn(async move {
+            let mut open = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                open.push(stream);
+            }
+        });
+
+        let start = std::time::Instant::now();
+        let client = status_client(reqwest::Client::builder());
+        let result = wait_for_status(&client, &url, Duration::from_millis(800), None).await;
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(2), "waited {:?}", start.elapsed());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_start_reports_driver_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("sparkle-fake-driver-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let driver = dir.join("chromedriver");
+        std::fs::write(
+            &driver,
+            "#!/bin/sh\necho \"Starting ChromeDriver 120.0.6099.109\"\necho \"error while loading shared libraries: libnss3.so\" >&2\nexit 127\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&driver, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let start = std::time::Instant::now();
+        let error = ChromeDriverProcess::launch(Some(driver), 1, &HashMap::new(), Duration::from_secs(10))
+            .await
+            .err()
+            .expect("the fake driver never becomes ready");
+        let message = error.to_string();
+        assert!(message.contains("exited during startup"), "{}", message);
+        assert!(message.contains("Starting ChromeDriver 120.0.6099.109"), "{}", message);
+        assert!(message.contains("error while loading shared libraries: libnss3.so"), "{}", message);
+        assert!(start.elapsed() < Duration::from_secs(5), "waited {:?} for an exited driver", start.elapsed());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shared_driver_rejects_different_settings() {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // The fake driver only has to stay alive; the test answers its `/status`
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![0; 4096];
+                let _ = stream.read(&mut request).await;
+                let body = r#"{"value":{"ready":true}}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        let dir = std::env::temp_dir().join(format!("sparkle-shared-driver-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let driver = dir.join("chromedriver");
+        std::fs::write(&driver, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        std::fs::set_permissions(&driver, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let env = HashMap::new();
+        let timeout = Duration::from_secs(5);
+        let lease = ChromeDriverProcess::launch_shared(Some(driver.clone()), port, &env, timeout)
+            .await
+            .unwrap();
+        let again = ChromeDriverProcess::launch_shared(Some(driver.clone()), port, &env, timeout)
+            .await
+            .unwrap();
+        assert_eq!(lease.url(), again.url());
+
+        let other_env = HashMap::from([("DISPLAY".to_string(), ":1".to_string())]);
+        let error = ChromeDriverProcess::launch_shared(Some(driver.clone()), port, &other_env, timeout)
+            .await
+            .expect_err("a running shared driver can't take another environment");
+        assert!(error.to_string().contains("different executable path or environment"), "{}", error);
+        assert!(ChromeDriverProcess::launch_shared(None, port, &env, timeout).await.is_err());
+
+        // The driver outlives one lease and stops with the last
+        drop(again);
+        assert_eq!(ChromeDriverProcess::shared_url().await.as_deref(), Some(lease.url()));
+        drop(lease);
+        assert_eq!(ChromeDriverProcess::shared_url().await, None);
+
+        // An explicit shutdown stops it even while a lease is held
+        let lease = ChromeDriverProcess::launch_shared(Some(driver.clone()), port, &env, timeout)
+            .await
+            .unwrap();
+        ChromeDriverProcess::shutdown_shared().await;
+        assert_eq!(ChromeDriverProcess::shared_url().await, None);
+        drop(lease);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_in_path() {
+        let root = std::env::temp_dir().join(format!("sparkle-path-{}", std::process::id()));
+        let (empty, bin) = (root.join("empty"), root.join("bin"));
+        std::fs::create_dir_all(&empty).unwrap();
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("msedgedriver"), b"").unwrap();
+
+        let path = std::env::join_paths([&empty, &bin]).unwrap();
+        assert_eq!(find_in_path("msedgedriver", &path), Some(bin.join("msedgedriver")));
+        assert_eq!(find_in_path("chromedriver", &path), None);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! Minimal in-process WebDriver endpoint for unit tests
+//!
+//! Speaks just enough HTTP/1.1 for thirtyfour's client and answers each
+//! command through a closure, recording what was received and when.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use super::WebDriverAdapter;
+
+/// Reply to one WebDriver command
+pub(crate) struct FakeResponse {
+    pub status: u16,
+    pub value: Value,
+    pub delay: Duration,
+    /// Close the connection instead of replying
+    pub dropped: bool,
+}
+
+impl FakeResponse {
+    /// A successful reply carrying `value`
+    pub fn ok(value: Value) -> Self {
+        Self {
+            status: 200,
+            value,
+            delay: Duration::ZERO,
+            dropped: false,
+        }
+    }
+
+    /// A W3C error reply
+    pub fn error(status: u16, error: &str, message: &str) -> Self {
+        Self {
+            status,
+            value: json!({ "error": error, "message": message, "stacktrace": "" }),
+            delay: Duration::ZERO,
+            dropped: false,
+        }
+    }
+
+    /// Close the connection without replying, like a network blip
+    pub fn dropped() -> Self {
+        Self {
+            dropped: true,
+            ..Self::ok(Value::Null)
+        }
+    }
+
+    /// Delay the reply
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Commands received by a fake endpoint, as arrival time, `"METHOD /path"`
+/// and JSON body (`null` when empty)
+pub(crate) type CommandLog = Arc<Mutex<Vec<(Instant, String, Value)>>>;
+
+/// Start a fake WebDriver server, returning its URL and the command log
+///
+/// `respond` gets the command (`"POST /session"`) and how many times that
+/// command was received before.
+pub(crate) async fn start<F>(respond: F) -> (String, CommandLog)
+where
+    F: Fn(&str, usize) -> FakeResponse + Send + Sync + 'static,
+{
+    serve(move |command, _, seen| respond(command, seen)).await
+}
+
+/// Start a fake WebDriver server and create a `WebDriverAdapter` session on it
+///
+/// `respond` gets the command and its JSON body; `None` answers
+/// `POST /session` with session `"fake"` and anything else with a
+/// successful `null`. The adapter knows its endpoint, so it can reconnect,
+/// and the log is cleared once it is created.
+pub(crate) async fn fake_adapter<F>(respond: F) -> (Arc<WebDriverAdapter>, CommandLog)
+where
+    F: Fn(&str, &Value) -> Option<FakeResponse> + Send + Sync + 'static,
+{
+    let (url, log) = serve(move |command, body, _| {
+        respond(command, body).unwrap_or_else(|| match command {
+            "POST /session" => FakeResponse::ok(json!({ "sessionId": "fake", "capabilities": {} })),
+            _ => FakeResponse::ok(Value::Null),
+        })
+    })
+    .await;
+    let adapter = WebDriverAdapter::create(&url, HashMap::new(), None).await.unwrap();
+    log.lock().unwrap().clear();
+    (Arc::new(adapter), log)
+}
+
+/// The server behind `start` and `fake_adapter`; `respond` also gets the body
+async fn serve<F>(respond: F) -> (String, CommandLog)
+where
+    F: Fn(&str, &Value, usize) -> FakeResponse + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let log: CommandLog = Arc::new(Mutex::new(Vec::new()));
+    let respond = Arc::new(respond);
+
+    let commands = Arc::clone(&log);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let commands = Arc::clone(&commands);
+            let respond = Arc::clone(&respond);
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                loop {
+                    let mut request_line = String::new();
+                    if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let mut content_length = 0;
+                    loop {
+                        let mut header = String::new();
+                        stream.read_line(&mut header).await.unwrap();
+                        if header.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = header.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    stream.read_exact(&mut body).await.unwrap();
+
+                    let command = request_line.split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+                    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                    let seen = {
+                        let mut commands = commands.lock().unwrap();
+                        let seen = commands.iter().filter(|(_, c, _)| *c == command).count();
+                        commands.push((Instant::now(), command.clone(), body.clone()));
+                        seen
+                    };
+                    let response = respond(&command, &body, seen);
+                    // A zero sleep still waits for the next timer tick, which
+                    // never comes on a paused clock
+                    if !response.delay.is_zero() {
+                        tokio::time::sleep(response.delay).await;
+                    }
+                    if response.dropped {
+                        return;
+                    }
+
+                    let body = json!({ "value": response.value }).to_string();
+                    let reply = format!(
+                        "HTTP/1.1 {} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                        response.status,
+                        body.len(),
+                        body
+                    );
+                    stream.get_mut().write_all(reply.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+
+    (url, log)
+}
@@ -0,0 +1,113 @@
+//! HTTP client for WebDriver requests
+//!
+//! thirtyfour sends every WebDriver command through an `HttpClient`. This
+//! client adds user-supplied headers (e.g. auth tokens for Selenium Grid or
+//! cloud providers) to each request.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Response};
+use thirtyfour::error::{WebDriverError, WebDriverResult};
+use thirtyfour::session::http::{Body, HttpClient};
+
+use crate::core::{Error, Result};
+
+/// WebDriver HTTP client that attaches extra headers to every request
+#[derive(Clone, Debug)]
+pub struct HeaderHttpClient {
+    client: reqwest::Client,
+    headers: HeaderMap,
+}
+
+impl HeaderHttpClient {
+    /// Create a client sending `headers` with every request
+    ///
+    /// # Arguments
+    /// * `headers` - Header names and values
+    /// * `timeout` - Per-request timeout
+    pub fn new(headers: &HashMap<String, String>, timeout: Duration) -> Result<Self> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::invalid_argument(format!("Invalid header name '{}': {}", name, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| Error::invalid_argument(format!("Invalid value for header '{}': {}", name, e)))?;
+            header_map.insert(name, value);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| Error::internal(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            headers: header_map,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpClient for HeaderHttpClient {
+    async fn send(&self, request: Request<Body<'_>>) -> WebDriverResult<Response<Bytes>> {
+        let (parts, body) = request.into_parts();
+
+        let mut req = self.client.request(parts.method, parts.uri.to_string());
+        for (key, value) in parts.headers.iter() {
+            req = req.header(key, value);
+        }
+        // User headers override the defaults set by thirtyfour
+        for (key, value) in self.headers.iter() {
+            req = req.header(key, value);
+        }
+        if let Body::Json(json) = body {
+            req = req.json(json);
+        }
+
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| WebDriverError::HttpError(e.to_string()))?;
+        let status = resp.status();
+        let mut builder = Response::builder().status(status);
+        for (key, value) in resp.headers().iter() {
+            builder = builder.header(key, value);
+        }
+
+        let body = resp
+            .bytes()
+            .await
+            .map_err(|e| WebDriverError::HttpError(e.to_string()))?;
+        let body_str = String::from_utf8_lossy(&body).into_owned();
+        builder
+            .body(body)
+            .map_err(|_| WebDriverError::UnknownResponse(status.as_u16(), body_str))
+    }
+
+    async fn new(&self) -> Arc<dyn HttpClient> {
+        Arc::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_client_rejects_invalid_header() {
+        let mut headers = HashMap::new();
+        headers.insert("bad header".to_string(), "value".to_string());
+        assert!(HeaderHttpClient::new(&headers, Duration::from_secs(5)).is_err());
+    }
+
+    #[test]
+    fn test_header_client_accepts_headers() {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), "Bearer token".to_string());
+        let client = HeaderHttpClient::new(&headers, Duration::from_secs(5)).unwrap();
+        assert_eq!(client.headers["authorization"], "Bearer token");
+    }
+}
@@ -4,8 +4,12 @@
 
 pub mod capabilities;
 pub mod chromedriver_process;
+#[cfg(test)]
+pub(crate) mod fake_webdriver;
+pub mod http_client;
 pub mod webdriver_adapter;
 
 pub use capabilities::*;
 pub use chromedriver_process::*;
+pub use http_client::*;
 pub use webdriver_adapter::*;
@@ -3,16 +3,19 @@
 //! This module provides an abstraction over thirtyfour to adapt it to Playwright's
 //! semantics and API patterns.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use base64::prelude::*;
 use futures::{SinkExt, StreamExt};
 use http::Method;
 use reqwest::Client;
 use serde_json::{json, Value};
 use thirtyfour::common::command::{Command, ExtensionCommand};
+use thirtyfour::common::config::WebDriverConfig;
 use thirtyfour::error::WebDriverErrorInner;
 use thirtyfour::extensions::cdp::ChromeDevTools;
 use thirtyfour::prelude::*;
@@ -21,7 +24,25 @@ use tokio::time::{Instant, Sleep};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use url::Url;
 
-use crate::core::{Error, Result};
+use crate::core::{
+    Error, KeyboardModifier, MouseButton, Position, Result, ScreenshotAnimations, ScreenshotOptions, ScreenshotType, SlowMoConfig, SlowMoScope,
+    WaitForSelectorState,
+};
+use crate::driver::HeaderHttpClient;
+
+/// Distinguishes concurrent accessibility tree lookups' stashes
+static AX_STASH_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Keeps the resolved nodes under a registry symbol until WebDriver takes them
+const STASH_AX_NODES_JS: &str = "function (key, ...nodes) { window[Symbol.for(key)] = nodes; }";
+
+/// Hands the stashed nodes to WebDriver in document order and forgets them
+const TAKE_AX_NODES_JS: &str = r#"
+    const key = Symbol.for(arguments[0]);
+    const nodes = window[key] || [];
+    delete window[key];
+    return nodes.sort((a, b) => (a.compareDocumentPosition(b) & Node.DOCUMENT_POSITION_FOLLOWING ? -1 : 1));
+"#;
 
 /// Adapter wrapping the thirtyfour WebDriver
 ///
@@ -29,12 +50,74 @@ use crate::core::{Error, Result};
 /// handling conversions and adapting behavior where needed.
 pub struct WebDriverAdapter {
     driver: Arc<RwLock<Option<WebDriver>>>,
-    slow_mo: Option<Duration>,
+    slow_mo: Option<SlowMoConfig>,
     cdp: Arc<RwLock<Option<ChromeDevTools>>>,
     requested_capabilities: Option<serde_json::Map<String, serde_json::Value>>,
     session_capabilities: Arc<RwLock<Option<serde_json::Value>>>,
+    /// Which tab the driver is switched to and who is using it
+    windows: Arc<WindowState>,
+    /// Whether the endpoint accepts CDP commands (`goog/cdp/execute`)
+    has_cdp: bool,
+    /// When the driver was last used, for the keepalive's idle check
+    last_activity: std::sync::Mutex<Instant>,
+    /// Endpoint the session was created on, for `reconnect`
+    endpoint: Option<SessionEndpoint>,
+    /// Device metrics override sent to each window, for putting it back after screenshots
+    device_metrics: std::sync::Mutex<HashMap<Option<String>, Value>>,
+}
+
+/// WebDriver server a session lives on and the headers its requests carry
+struct SessionEndpoint {
+    url: String,
+    headers: HashMap<String, String>,
+}
+
+/// Tabs of the session and the one WebDriver commands currently go to
+///
+/// Every page shares the session's current window. Commands for the current
+/// window run side by side; switching to another window waits until they are
+/// done, so a switch can't land between another page's switch and command.
+#[derive(Default)]
+struct WindowState {
+    /// Window handle the driver is currently switched to, if known
+    current: tokio::sync::Mutex<Option<WindowHandle>>,
+    /// Outstanding [`WindowGuard`]s for `current`
+    holders: AtomicUsize,
+    /// Signalled when the last guard is dropped
+    released: tokio::sync::Notify,
+    /// Tabs owned by pages, so input doesn't need to list the session's windows
+    claimed: AtomicUsize,
+    /// Tab last brought to the front with CDP `Page.bringToFront`
+    front: std::sync::Mutex<Option<WindowHandle>>,
+}
+
+/// Keeps the driver on a window until dropped
+///
+/// Returned by [`WebDriverAdapter::activate_window`]; hold it for as long as
+/// commands must reach that window.
+#[must_use = "the window may be switched away as soon as the guard is dropped"]
+pub struct WindowGuard {
+    windows: Arc<WindowState>,
+}
+
+impl Drop for WindowGuard {
+    fn drop(&mut self) {
+        if self.windows.holders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.windows.released.notify_waiters();
+        }
+    }
 }
 
+/// Raw thirtyfour WebDriver handle returned by `Browser::webdriver()`
+pub type WebDriverHandle = WebDriver;
+
+/// Websocket connected directly to a page's DevTools target
+pub(crate) type CdpEventStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Websocket connected to the session's WebDriver BiDi endpoint
+pub(crate) type BidiEventStream = CdpEventStream;
+
 #[derive(Clone, Debug, Default)]
 struct LoadStateSnapshot {
     domcontentloaded: bool,
@@ -43,6 +126,280 @@ struct LoadStateSnapshot {
     commit: bool,
 }
 
+/// Longest single `wait_for_selector` script, below WebDriver's default 30s script timeout
+const WAIT_FOR_SELECTOR_SLICE: Duration = Duration::from_secs(10);
+
+/// Longest `wait_for_selector` script while holding a page's window
+///
+/// The driver runs one command at a time, so other pages' commands only get
+/// a turn between slices.
+const WINDOW_WAIT_FOR_SELECTOR_SLICE: Duration = Duration::from_secs(1);
+
+/// Resolves `{ element }` once the selector `arguments[0]` reaches the state
+/// `arguments[1]`, `false` after `arguments[2]` ms, or `{ error }` for an
+/// invalid selector
+///
+/// The selector comes split into its `>>>` parts, as for `DEEP_QUERY_JS`.
+/// `element` is the matching element for `attached`/`visible` and `null` for
+/// `detached`/`hidden`. Re-checks on every DOM mutation, plus a slow in-page
+/// interval for changes that don't mutate the DOM (e.g. media queries or
+/// transitions). Mutations inside shadow roots are only seen by the interval.
+const WAIT_FOR_SELECTOR_JS: &str = r#"
+    const [parts, state, timeoutMs, done] = arguments;
+    let observer, interval, timer, finished = false;
+    const finish = (result) => {
+        if (finished) return;
+        finished = true;
+        if (observer) observer.disconnect();
+        clearInterval(interval);
+        clearTimeout(timer);
+        done(result);
+    };
+    const isVisible = (el) => {
+        if (!el || !el.isConnected) return false;
+        const style = window.getComputedStyle(el);
+        if (style.visibility === 'hidden' || style.display === 'none') return false;
+        const rect = el.getBoundingClientRect();
+        return rect.width > 0 && rect.height > 0;
+    };
+    const pierce = (root, css) => {
+        const found = root.querySelector(css);
+        if (found) return found;
+        for (const el of root.querySelectorAll('*')) {
+            const inner = el.shadowRoot && pierce(el.shadowRoot, css);
+            if (inner) return inner;
+        }
+        return null;
+    };
+    const query = () => {
+        if (parts[0].startsWith('pierce=')) return pierce(document, parts[0].slice('pierce='.length));
+        if (parts.length === 1) return document.querySelector(parts[0]);
+        let matches = [...document.querySelectorAll(parts[0])];
+        for (const part of parts.slice(1)) {
+            matches = matches.flatMap((el) => el.shadowRoot ? [...el.shadowRoot.querySelectorAll(part)] : []);
+        }
+        return matches[0] || null;
+    };
+    const check = () => {
+        try {
+            const el = query();
+            switch (state) {
+                case 'attached': if (el) finish({ element: el }); break;
+                case 'detached': if (!el) finish({ element: null }); break;
+                case 'hidden': if (!isVisible(el)) finish({ element: null }); break;
+                default: if (isVisible(el)) finish({ element: el });
+            }
+        } catch (e) {
+            finish({ error: String(e && e.message || e) });
+        }
+    };
+    observer = new MutationObserver(check);
+    observer.observe(document, { childList: true, subtree: true, attributes: true, characterData: true });
+    interval = setInterval(check, 250);
+    timer = setTimeout(() => finish(false), timeoutMs);
+    check();
+"#;
+
+/// Message for CDP-only features used on endpoints without CDP
+const CDP_REQUIRED: &str =
+    "CDP required; this WebDriver endpoint does not support Chrome DevTools Protocol commands";
+
+/// Message for BiDi features used on sessions without a BiDi websocket
+const BIDI_REQUIRED: &str =
+    "WebDriver BiDi required; the session has no webSocketUrl (launch with Protocol::Bidi on a BiDi-capable driver)";
+
+/// Largest document (in UTF-16 code units) returned by a single script;
+/// bigger ones are fetched in slices of this size
+const CONTENT_CHUNK: usize = 1 << 20;
+
+/// Serializes the current document, doctype included, the way `page.content()`
+/// does in Playwright. Documents longer than `arguments[0]` are stashed on the
+/// window and only their length is returned.
+const CONTENT_JS: &str = r#"
+    let html = '';
+    if (document.doctype) html = new XMLSerializer().serializeToString(document.doctype);
+    if (document.documentElement) html += document.documentElement.outerHTML;
+    if (html.length <= arguments[0]) return html;
+    window.__sparkleContent = html;
+    return { length: html.length };
+"#;
+
+/// Returns `[slice, end]` of the stashed document starting at `arguments[0]`,
+/// never splitting a surrogate pair, and drops the stash after the last slice
+const CONTENT_SLICE_JS: &str = r#"
+    const html = window.__sparkleContent || '';
+    let end = Math.min(arguments[0] + arguments[1], html.length);
+    const last = html.charCodeAt(end - 1);
+    if (end < html.length && last >= 0xD800 && last <= 0xDBFF) end -= 1;
+    const slice = html.slice(arguments[0], end);
+    if (end >= html.length) delete window.__sparkleContent;
+    return [slice, end];
+"#;
+
+/// Resolves with `{ databases }` describing every IndexedDB database of the
+/// current origin, with its stores, indexes and records, or `{ error }`
+///
+/// All reads are queued before awaiting so the transaction stays active.
+const INDEXED_DB_SNAPSHOT_JS: &str = r#"
+    const done = arguments[arguments.length - 1];
+    const result = (request) => new Promise((resolve, reject) => {
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+    const keyPath = (path) => ({
+        keyPath: typeof path === 'string' ? path : undefined,
+        keyPathArray: Array.isArray(path) ? [...path] : undefined,
+    });
+    (async () => {
+        const databases = [];
+        for (const info of await indexedDB.databases()) {
+            if (!info.name) continue;
+            const db = await result(indexedDB.open(info.name));
+            const names = [...db.objectStoreNames];
+            const stores = [];
+            if (names.length) {
+                const tx = db.transaction(names, 'readonly');
+                const pending = names.map((name) => {
+                    const store = tx.objectStore(name);
+                    return Promise.all([result(store.getAllKeys()), result(store.getAll())]).then(([keys, values]) => ({
+                        name,
+                        autoIncrement: store.autoIncrement,
+                        ...keyPath(store.keyPath),
+                        records: values.map((value, i) => store.keyPath === null ? { key: keys[i], value } : { value }),
+                        indexes: [...store.indexNames].map((indexName) => {
+                            const index = store.index(indexName);
+                            return { name: indexName, ...keyPath(index.keyPath), multiEntry: index.multiEntry, unique: index.unique };
+                        }),
+                    }));
+                });
+                stores.push(...await Promise.all(pending));
+            }
+            databases.push({ name: db.name, version: db.version, stores });
+            db.close();
+        }
+        return databases;
+    })().then((databases) => done({ databases }), (e) => done({ error: String(e && e.message || e) }));
+"#;
+
+/// Recreates the IndexedDB databases in `arguments[0]` for the current
+/// origin, resolving with `null` or an error message
+const INDEXED_DB_RESTORE_JS: &str = r#"
+    const [databases, done] = arguments;
+    const restore = (database) => new Promise((resolve, reject) => {
+        const open = indexedDB.open(database.name, database.version);
+        open.onupgradeneeded = () => {
+            const db = open.result;
+            for (const store of database.stores) {
+                if (db.objectStoreNames.contains(store.name)) continue;
+                const created = db.createObjectStore(store.name, {
+                    keyPath: store.keyPathArray || store.keyPath || undefined,
+                    autoIncrement: store.autoIncrement,
+                });
+                for (const index of store.indexes) {
+                    created.createIndex(index.name, index.keyPathArray || index.keyPath, {
+                        multiEntry: index.multiEntry,
+                        unique: index.unique,
+                    });
+                }
+            }
+        };
+        open.onerror = () => reject(open.error);
+        open.onsuccess = () => {
+            const db = open.result;
+            const names = database.stores.map((store) => store.name).filter((name) => db.objectStoreNames.contains(name));
+            if (!names.length) {
+                db.close();
+                return resolve();
+            }
+            const tx = db.transaction(names, 'readwrite');
+            for (const store of database.stores) {
+                if (!names.includes(store.name)) continue;
+                const target = tx.objectStore(store.name);
+                for (const record of store.records) {
+                    if (target.keyPath === null && record.key !== undefined && record.key !== null) {
+                        target.put(record.value, record.key);
+                    } else {
+                        target.put(record.value);
+                    }
+                }
+            }
+            tx.oncomplete = () => { db.close(); resolve(); };
+            tx.onerror = () => reject(tx.error);
+        };
+    });
+    databases.reduce((chain, database) => chain.then(() => restore(database)), Promise.resolve())
+        .then(() => done(null), (e) => done(String(e && e.message || e)));
+"#;
+
+/// Stabilizes the page for a screenshot: with `arguments[0]` finishes finite
+/// animations, cancels infinite ones and blocks new transitions; covers each
+/// element matching the selectors in `arguments[1]` with a box of color
+/// `arguments[2]`. Undone by `SCREENSHOT_RESTORE_JS`.
+const SCREENSHOT_PREPARE_JS: &str = r#"
+    const [disableAnimations, selectors, color] = arguments;
+    const state = { cancelled: [], overlays: [], style: null };
+    window.__sparkleScreenshot = state;
+    if (disableAnimations) {
+        state.style = document.createElement('style');
+        state.style.textContent = '*, *::before, *::after { transition: none !important; caret-color: transparent !important; }';
+        document.documentElement.appendChild(state.style);
+        for (const animation of document.getAnimations()) {
+            if (animation.playState === 'idle' || animation.playState === 'finished') continue;
+            const timing = animation.effect && animation.effect.getComputedTiming();
+            if (timing && Number.isFinite(timing.endTime)) {
+                animation.finish();
+            } else {
+                animation.cancel();
+                state.cancelled.push(animation);
+            }
+        }
+    }
+    for (const selector of selectors) {
+        for (const el of document.querySelectorAll(selector)) {
+            const rect = el.getBoundingClientRect();
+            const overlay = document.createElement('div');
+            overlay.style.cssText = `position: absolute; left: ${rect.left + scrollX}px; top: ${rect.top + scrollY}px;
+                width: ${rect.width}px; height: ${rect.height}px; background: ${color};
+                z-index: 2147483647; pointer-events: none;`;
+            document.documentElement.appendChild(overlay);
+            state.overlays.push(overlay);
+        }
+    }
+"#;
+
+/// Removes screenshot masks and restarts the animations cancelled by
+/// `SCREENSHOT_PREPARE_JS`
+const SCREENSHOT_RESTORE_JS: &str = r#"
+    const state = window.__sparkleScreenshot;
+    if (!state) return;
+    delete window.__sparkleScreenshot;
+    state.overlays.forEach((overlay) => overlay.remove());
+    if (state.style) state.style.remove();
+    state.cancelled.forEach((animation) => animation.play());
+"#;
+
+/// Finds the elements matching a shadow-piercing selector, split into its
+/// `>>>` parts by `deep_selector_parts`, in `arguments[0]`.
+/// `pierce=css` matches `css` in the document and every shadow root below
+/// it; `host >>> css` matches `css` inside the shadow roots of the elements
+/// matching `host`, and can be repeated to descend through nested components.
+const DEEP_QUERY_JS: &str = r#"
+    const [first, ...rest] = arguments[0];
+    const pierce = (root, css) => {
+        const found = [...root.querySelectorAll(css)];
+        for (const el of root.querySelectorAll('*')) {
+            if (el.shadowRoot) found.push(...pierce(el.shadowRoot, css));
+        }
+        return found;
+    };
+    if (first.startsWith('pierce=')) return pierce(document, first.slice('pierce='.length));
+    let matches = [...document.querySelectorAll(first)];
+    for (const part of rest) {
+        matches = matches.flatMap((el) => el.shadowRoot ? [...el.shadowRoot.querySelectorAll(part)] : []);
+    }
+    return [...new Set(matches)];
+"#;
+
 const W3C_ELEMENT_KEY: &str = "element-6066-11e4-a52e-4f735466cecf";
 const W3C_SHADOW_KEY: &str = "shadow-6066-11e4-a52e-4f735466cecf";
 const LEGACY_ELEMENT_KEY: &str = "ELEMENT";
@@ -75,6 +432,11 @@ impl WebDriverAdapter {
             cdp: Arc::new(RwLock::new(Some(cdp))),
             requested_capabilities: None,
             session_capabilities: Arc::new(RwLock::new(None)),
+            windows: Arc::new(WindowState::default()),
+            has_cdp: true,
+            last_activity: std::sync::Mutex::new(Instant::now()),
+            endpoint: None,
+            device_metrics: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -83,16 +445,21 @@ impl WebDriverAdapter {
         let cdp = ChromeDevTools::new(driver.handle.clone());
         Self {
             driver: Arc::new(RwLock::new(Some(driver))),
-            slow_mo,
+            slow_mo: slow_mo.map(SlowMoConfig::all),
             cdp: Arc::new(RwLock::new(Some(cdp))),
             requested_capabilities: None,
             session_capabilities: Arc::new(RwLock::new(None)),
+            windows: Arc::new(WindowState::default()),
+            has_cdp: true,
+            last_activity: std::sync::Mutex::new(Instant::now()),
+            endpoint: None,
+            device_metrics: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
-    /// Apply slow_mo delay before an operation
-    async fn apply_slow_mo(&self) {
-        if let Some(duration) = self.slow_mo {
+    /// Apply slow_mo delay before an operation in the given category
+    async fn apply_slow_mo(&self, scope: SlowMoScope) {
+        if let Some(duration) = self.slow_mo.and_then(|config| config.delay_for(scope)) {
             tokio::time::sleep(duration).await;
         }
     }
@@ -105,8 +472,27 @@ impl WebDriverAdapter {
     /// * `slow_mo` - Optional delay to slow down operations
     pub async fn create(
         url: &str, 
-        capabilities: std::collections::HashMap<String, serde_json::Value>,
-        slow_mo: Option<Duration>,
+        capabilities: HashMap<String, serde_json::Value>,
+        slow_mo: Option<SlowMoConfig>,
+    ) -> Result<Self> {
+        Self::create_with_headers(url, capabilities, &HashMap::new(), slow_mo).await
+    }
+
+    /// Create a new WebDriver connection that sends extra HTTP headers
+    ///
+    /// The headers are attached to every WebDriver request, which remote
+    /// endpoints such as Selenium Grid or cloud providers may require.
+    ///
+    /// # Arguments
+    /// * `url` - WebDriver server URL
+    /// * `capabilities` - Browser capabilities as a HashMap
+    /// * `headers` - HTTP headers to send with each request
+    /// * `slow_mo` - Optional delay to slow down operations
+    pub async fn create_with_headers(
+        url: &str,
+        capabilities: HashMap<String, serde_json::Value>,
+        headers: &HashMap<String, String>,
+        slow_mo: Option<SlowMoConfig>,
     ) -> Result<Self> {
         tracing::debug!("Creating WebDriver connection to: {}", url);
         tracing::trace!("Capabilities: {:?}", capabilities);
@@ -114,9 +500,16 @@ impl WebDriverAdapter {
         // Convert HashMap to serde_json::Map
         let caps_map: serde_json::Map<String, serde_json::Value> =
             capabilities.into_iter().collect();
-        let caps: Capabilities = caps_map.clone().into();
-        let driver = WebDriver::new(url, caps).await?;
+        let caps: Capabilities = caps_map.clone();
+        let driver = if headers.is_empty() {
+            WebDriver::new(url, caps).await?
+        } else {
+            let config = WebDriverConfig::default();
+            let client = HeaderHttpClient::new(headers, config.reqwest_timeout)?;
+            WebDriver::new_with_config_and_client(url, caps, config, client).await?
+        };
         let cdp = ChromeDevTools::new(driver.handle.clone());
+        let has_cdp = Self::probe_cdp(&cdp).await;
         
         tracing::info!("WebDriver connection established");
         Ok(Self {
@@ -125,13 +518,54 @@ impl WebDriverAdapter {
             cdp: Arc::new(RwLock::new(Some(cdp))),
             requested_capabilities: Some(caps_map),
             session_capabilities: Arc::new(RwLock::new(None)),
+            windows: Arc::new(WindowState::default()),
+            has_cdp,
+            last_activity: std::sync::Mutex::new(Instant::now()),
+            endpoint: Some(SessionEndpoint {
+                url: url.to_string(),
+                headers: headers.clone(),
+            }),
+            device_metrics: std::sync::Mutex::new(HashMap::new()),
         })
     }
 
+    /// Check whether the endpoint accepts CDP commands
+    ///
+    /// Grids and non-Chromium drivers answer the `goog/cdp/execute` route with
+    /// an unknown-command error. Any other failure is not conclusive, so CDP
+    /// is assumed to be available.
+    async fn probe_cdp(cdp: &ChromeDevTools) -> bool {
+        match cdp.execute_cdp("Browser.getVersion").await {
+            Ok(_) => true,
+            Err(error) => match &*error {
+                WebDriverErrorInner::UnknownCommand(_)
+                | WebDriverErrorInner::UnknownMethod(_)
+                | WebDriverErrorInner::UnsupportedOperation(_)
+                | WebDriverErrorInner::NotFound(_, _) => {
+                    tracing::warn!("WebDriver endpoint does not support CDP; CDP-only features are disabled");
+                    false
+                }
+                _ => {
+                    tracing::debug!("CDP probe failed, assuming CDP is available: {}", error);
+                    true
+                }
+            },
+        }
+    }
+
+    /// Whether the session supports Chrome DevTools Protocol commands
+    ///
+    /// Probed when the adapter is created with [`WebDriverAdapter::create`];
+    /// adapters wrapping an existing driver assume CDP is available.
+    pub fn has_cdp(&self) -> bool {
+        self.has_cdp
+    }
+
     /// Get a reference to the underlying WebDriver
     ///
     /// Returns an error if the driver has been closed
     pub async fn driver(&self) -> Result<tokio::sync::RwLockReadGuard<'_, Option<WebDriver>>> {
+        self.touch();
         let guard = self.driver.read().await;
         if guard.is_none() {
             return Err(Error::BrowserClosed);
@@ -143,6 +577,7 @@ impl WebDriverAdapter {
     ///
     /// Returns an error if the driver has been closed
     pub async fn driver_mut(&self) -> Result<tokio::sync::RwLockWriteGuard<'_, Option<WebDriver>>> {
+        self.touch();
         let guard = self.driver.write().await;
         if guard.is_none() {
             return Err(Error::BrowserClosed);
@@ -152,8 +587,13 @@ impl WebDriverAdapter {
 
     /// Get a reference to the Chrome DevTools Protocol interface
     ///
-    /// Returns an error if the driver has been closed
+    /// Returns an error if the driver has been closed, or
+    /// [`Error::NotImplemented`] if the endpoint does not support CDP.
     pub async fn cdp(&self) -> Result<tokio::sync::RwLockReadGuard<'_, Option<ChromeDevTools>>> {
+        if !self.has_cdp {
+            return Err(Error::not_implemented(CDP_REQUIRED));
+        }
+        self.touch();
         let guard = self.cdp.read().await;
         if guard.is_none() {
             return Err(Error::BrowserClosed);
@@ -161,6 +601,48 @@ impl WebDriverAdapter {
         Ok(guard)
     }
 
+    /// Record that the session is in use
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    /// Ping the session whenever it has been idle for `interval`
+    ///
+    /// Remote grids reap sessions that receive no commands for a while
+    /// (Selenium Grid's `--session-timeout`), which would end a session parked
+    /// on a long human-in-the-loop step. The ping is a cheap
+    /// `GET /session/{id}/timeouts`, sent only when nothing else used the
+    /// session during the last `interval`. A ping whose connection dropped
+    /// reconnects like any other command; one that still fails is logged and
+    /// retried one interval later. The task ends when the adapter is closed
+    /// or dropped.
+    pub fn spawn_keepalive(self: &Arc<Self>, interval: Duration) {
+        let adapter = Arc::downgrade(self);
+        tokio::spawn(async move {
+            loop {
+                let Some(idle) = adapter.upgrade().map(|adapter| adapter.last_activity.lock().unwrap().elapsed()) else {
+                    return;
+                };
+                if idle < interval {
+                    tokio::time::sleep(interval - idle).await;
+                    continue;
+                }
+
+                let Some(adapter) = adapter.upgrade() else {
+                    return;
+                };
+                let ping = adapter
+                    .retry_on_reconnect(|driver| async move { Ok(driver.get_timeouts().await.map(|_| ())?) })
+                    .await;
+                match ping {
+                    Ok(()) => tracing::trace!("Keepalive ping sent"),
+                    Err(Error::BrowserClosed) => return,
+                    Err(error) => tracing::warn!("Keepalive ping failed, retrying in {:?}: {}", interval, error),
+                }
+            }
+        });
+    }
+
     /// Execute an async closure with the WebDriver
     ///
     /// This is a convenience method to safely access the driver
@@ -174,6 +656,92 @@ impl WebDriverAdapter {
         f(driver).await
     }
 
+    /// Run a read-only WebDriver command, reconnecting once if the connection dropped
+    ///
+    /// `command` gets its own handle to the driver, so after
+    /// [`WebDriverAdapter::reconnect`] it runs again on the new connection.
+    /// Only for commands that are safe to send twice: the server may have
+    /// handled the first one before its response was lost. Adapters without
+    /// a known endpoint just report the failure.
+    async fn retry_on_reconnect<F, T, Fut>(&self, command: F) -> Result<T>
+    where
+        F: Fn(WebDriver) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let driver = self.driver().await?.as_ref().ok_or(Error::BrowserClosed)?.clone();
+        match command(driver).await {
+            Err(error) if error.is_transport_error() && self.endpoint.is_some() => {
+                tracing::warn!("WebDriver connection failed, reconnecting: {}", error);
+                self.reconnect().await?;
+                let driver = self.driver().await?.as_ref().ok_or(Error::BrowserClosed)?.clone();
+                command(driver).await
+            }
+            result => result,
+        }
+    }
+
+    /// Run a WebDriver command that must not be sent twice, reconnecting if
+    /// the connection dropped
+    ///
+    /// The server may have handled the command before its response was lost,
+    /// so the error is returned rather than repeating a click, keystroke,
+    /// navigation or script; later commands use the new connection.
+    async fn reconnect_on_failure<F, T, Fut>(&self, command: F) -> Result<T>
+    where
+        F: FnOnce(WebDriver) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let driver = self.driver().await?.as_ref().ok_or(Error::BrowserClosed)?.clone();
+        let result = command(driver).await;
+        if let Err(error) = &result {
+            if error.is_transport_error() && self.endpoint.is_some() {
+                tracing::warn!("WebDriver connection failed, reconnecting: {}", error);
+                if let Err(reconnect_error) = self.reconnect().await {
+                    tracing::warn!("Reconnecting failed: {}", reconnect_error);
+                }
+            }
+        }
+        result
+    }
+
+    /// Re-establish the connection to the WebDriver session
+    ///
+    /// Builds a new HTTP client for the same endpoint and session ID, so a
+    /// dropped connection (e.g. a network blip to a remote Grid) doesn't fail
+    /// every later command. The session is reused, not recreated: this fails
+    /// if the endpoint no longer knows it. Elements found before reconnecting
+    /// keep using the old connection and should be looked up again.
+    ///
+    /// Only adapters created with [`WebDriverAdapter::create`] know their
+    /// endpoint; others return [`Error::NotImplemented`].
+    pub async fn reconnect(&self) -> Result<()> {
+        let endpoint = self
+            .endpoint
+            .as_ref()
+            .ok_or_else(|| Error::not_implemented("reconnecting an adapter created from an existing WebDriver"))?;
+
+        let mut guard = self.driver_mut().await?;
+        let session_id = guard.as_ref().ok_or(Error::BrowserClosed)?.handle.session_id().clone();
+        let client = HeaderHttpClient::new(&endpoint.headers, WebDriverConfig::default().reqwest_timeout)?;
+        let handle = thirtyfour::session::handle::SessionHandle::new(Arc::new(client), endpoint.url.as_str(), session_id)?;
+        let driver = WebDriver { handle: Arc::new(handle) };
+
+        // Check the session survived before switching to the new connection
+        if let Err(error) = driver.get_timeouts().await {
+            let _ = driver.leak();
+            return Err(Error::connection_failed(format!("Failed to reconnect to the WebDriver session: {}", error)));
+        }
+
+        let cdp = ChromeDevTools::new(driver.handle.clone());
+        // Dropping the old driver would end the session both connections point at
+        if let Some(old) = guard.replace(driver) {
+            let _ = old.leak();
+        }
+        *self.cdp.write().await = Some(cdp);
+        tracing::info!("Reconnected to WebDriver session");
+        Ok(())
+    }
+
     async fn session_capabilities(&self) -> Result<Option<serde_json::Value>> {
         let mut guard = self.session_capabilities.write().await;
         if let Some(cached) = guard.as_ref() {
@@ -230,7 +798,7 @@ impl WebDriverAdapter {
             })
     }
 
-    fn origin_from_url(url: &Url) -> Option<String> {
+    pub(crate) fn origin_from_url(url: &Url) -> Option<String> {
         let host = url.host_str()?;
         let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
         Some(format!("{}://{}{}", url.scheme(), host, port))
@@ -291,10 +859,7 @@ impl WebDriverAdapter {
     }
 
     fn extract_element_id_for_key(value: &Value, key: &str) -> Option<String> {
-        let map = match value.as_object() {
-            Some(map) => map,
-            None => return None,
-        };
+        let map = value.as_object()?;
 
         let nested = map.get(key)?;
         match nested {
@@ -305,51 +870,79 @@ impl WebDriverAdapter {
     }
 
     async fn find_element_raw(&self, selector: &str) -> Result<WebElement> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let response = match driver
-            .handle
-            .cmd(Command::FindElement(By::Css(selector).into()))
-            .await
-        {
-            Ok(response) => response,
-            Err(error) => {
-                if matches!(&*error, WebDriverErrorInner::NoSuchElement(_)) {
-                    return Err(Error::element_not_found(selector));
+        if is_deep_selector(selector) {
+            return self
+                .find_deep_elements(selector)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| Error::element_not_found(selector));
+        }
+
+        self.retry_on_reconnect(|driver| async move {
+            let response = match driver
+                .handle
+                .cmd(Command::FindElement(By::Css(selector).into()))
+                .await
+            {
+                Ok(response) => response,
+                Err(error) => {
+                    if matches!(&*error, WebDriverErrorInner::NoSuchElement(_)) {
+                        return Err(Error::element_not_found(selector));
+                    }
+                    return Err(Error::from(error));
                 }
-                return Err(Error::from(error));
-            }
-        };
+            };
 
-        let value = response.value_json()?;
-        Self::element_from_value(value, driver.handle.clone())
+            let value = response.value_json()?;
+            Self::element_from_value(value, driver.handle.clone())
+        })
+        .await
     }
 
     async fn find_elements_raw(&self, selector: &str) -> Result<Vec<WebElement>> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let response = match driver
-            .handle
-            .cmd(Command::FindElements(By::Css(selector).into()))
-            .await
-        {
-            Ok(response) => response,
-            Err(error) => {
-                if matches!(&*error, WebDriverErrorInner::NoSuchElement(_))
-                    || matches!(&*error, WebDriverErrorInner::NotFound(_, _))
-                {
-                    return Ok(Vec::new());
+        if is_deep_selector(selector) {
+            return self.find_deep_elements(selector).await;
+        }
+
+        self.retry_on_reconnect(|driver| async move {
+            let response = match driver
+                .handle
+                .cmd(Command::FindElements(By::Css(selector).into()))
+                .await
+            {
+                Ok(response) => response,
+                Err(error) => {
+                    if matches!(&*error, WebDriverErrorInner::NoSuchElement(_))
+                        || matches!(&*error, WebDriverErrorInner::NotFound(_, _))
+                    {
+                        return Ok(Vec::new());
+                    }
+                    return Err(Error::from(error));
                 }
-                return Err(Error::from(error));
+            };
+
+            let values: Vec<Value> = serde_json::from_value(response.value_json()?)?;
+            let mut elements = Vec::with_capacity(values.len());
+            for value in values {
+                elements.push(Self::element_from_value(value, driver.handle.clone())?);
             }
-        };
+            Ok(elements)
+        })
+        .await
+    }
 
-        let values: Vec<Value> = serde_json::from_value(response.value_json()?)?;
-        let mut elements = Vec::with_capacity(values.len());
-        for value in values {
-            elements.push(Self::element_from_value(value, driver.handle.clone())?);
-        }
-        Ok(elements)
+    /// Find elements for a `pierce=` or `>>>` selector by walking shadow roots in script
+    async fn find_deep_elements(&self, selector: &str) -> Result<Vec<WebElement>> {
+        self.retry_on_reconnect(|driver| async move {
+            let result = driver.execute(DEEP_QUERY_JS, vec![json!(deep_selector_parts(selector))]).await?;
+            let values: Vec<Value> = serde_json::from_value(result.json().clone())?;
+            values
+                .into_iter()
+                .map(|value| Self::element_from_value(value, driver.handle.clone()))
+                .collect()
+        })
+        .await
     }
 
     async fn cdp_websocket_url_for_current_page(&self) -> Result<Option<String>> {
@@ -364,6 +957,14 @@ impl WebDriverAdapter {
         };
 
         let current_url = self.current_url().await?;
+        // ChromeDriver window handles carry the DevTools target id
+        let active_target = self
+            .windows
+            .current
+            .lock()
+            .await
+            .as_ref()
+            .map(|handle| handle.to_string().trim_start_matches("CDwindow-").to_uppercase());
         let list_url = format!("http://{}/json/list", debugger_address);
         let client = Client::new();
 
@@ -389,6 +990,7 @@ impl WebDriverAdapter {
         };
 
         let mut fallback: Option<&Value> = None;
+        let mut matched: Option<&Value> = None;
         for target in targets {
             let target_type = target.get("type").and_then(|value| value.as_str());
             if target_type != Some("page") {
@@ -399,14 +1001,19 @@ impl WebDriverAdapter {
                 fallback = Some(target);
             }
 
-            let target_url = target.get("url").and_then(|value| value.as_str());
-            if target_url == Some(current_url.as_str()) {
-                fallback = Some(target);
+            let target_id = target.get("id").and_then(|value| value.as_str());
+            if active_target.is_some() && target_id.map(str::to_uppercase) == active_target {
+                matched = Some(target);
                 break;
             }
+
+            let target_url = target.get("url").and_then(|value| value.as_str());
+            if matched.is_none() && target_url == Some(current_url.as_str()) {
+                matched = Some(target);
+            }
         }
 
-        let target = match fallback {
+        let target = match matched.or(fallback) {
             Some(target) => target,
             None => return Ok(None),
         };
@@ -419,14 +1026,124 @@ impl WebDriverAdapter {
         Ok(ws_url)
     }
 
+    /// Open a CDP websocket to the current page target for listening to events
+    ///
+    /// `commands` (typically domain `enable` calls) are sent in order and
+    /// acknowledged before the stream is returned, so events they enable are
+    /// not missed by actions started afterwards.
+    pub(crate) async fn connect_cdp_events(&self, commands: &[(&str, Value)]) -> Result<CdpEventStream> {
+        if !self.has_cdp {
+            return Err(Error::not_implemented(CDP_REQUIRED));
+        }
+        let ws_url = self.cdp_websocket_url_for_current_page().await?.ok_or_else(|| {
+            Error::connection_failed("CDP websocket is not available for this session (no debuggerAddress)")
+        })?;
+        Self::connect_event_socket(&ws_url, commands, "CDP").await
+    }
+
+    /// WebDriver BiDi websocket URL of the session
+    ///
+    /// `None` unless the session was created with `webSocketUrl: true` and
+    /// the driver supports BiDi.
+    pub(crate) async fn bidi_websocket_url(&self) -> Result<Option<String>> {
+        Ok(self
+            .session_capabilities()
+            .await?
+            .and_then(|capabilities| capabilities.get("webSocketUrl")?.as_str().map(str::to_string)))
+    }
+
+    /// Whether the session has a WebDriver BiDi websocket
+    pub async fn has_bidi(&self) -> bool {
+        matches!(self.bidi_websocket_url().await, Ok(Some(_)))
+    }
+
+    /// Open a WebDriver BiDi websocket subscribed to `events` of the current page
+    ///
+    /// The subscription is limited to the current window's browsing context
+    /// and acknowledged before the stream is returned, like
+    /// [`WebDriverAdapter::connect_cdp_events`].
+    pub(crate) async fn connect_bidi_events(&self, events: &[&str]) -> Result<BidiEventStream> {
+        let ws_url = self.bidi_websocket_url().await?.ok_or_else(|| Error::not_implemented(BIDI_REQUIRED))?;
+        let active = self.windows.current.lock().await.clone();
+        let window = match active {
+            Some(window) => window,
+            None => {
+                let guard = self.driver().await?;
+                guard.as_ref().ok_or(Error::BrowserClosed)?.window().await?
+            }
+        };
+        // ChromeDriver window handles carry the browsing context id
+        let context = window.to_string().trim_start_matches("CDwindow-").to_string();
+        let subscription = json!({ "events": events, "contexts": [context] });
+        Self::connect_event_socket(&ws_url, &[("session.subscribe", subscription)], "BiDi").await
+    }
+
+    /// Connect to an event websocket and send `commands`, waiting for each to
+    /// be acknowledged
+    ///
+    /// CDP and WebDriver BiDi share the `{ id, method, params }` command and
+    /// `{ id, error }` reply shapes, so both protocols go through here.
+    async fn connect_event_socket(ws_url: &str, commands: &[(&str, Value)], protocol: &str) -> Result<CdpEventStream> {
+        let (mut ws_stream, _) = connect_async(ws_url)
+            .await
+            .map_err(|e| Error::connection_failed(format!("Failed to connect to {} websocket: {}", protocol, e)))?;
+
+        for (id, (method, params)) in (1u64..).zip(commands) {
+            let message = json!({ "id": id, "method": method, "params": params });
+            ws_stream
+                .send(Message::Text(serde_json::to_string(&message)?.into()))
+                .await
+                .map_err(|e| Error::ActionFailed(format!("Failed to send {} command: {}", protocol, e)))?;
+        }
+
+        let mut pending = commands.len();
+        let acknowledged = tokio::time::timeout(Duration::from_secs(10), async {
+            while pending > 0 {
+                let message = match ws_stream.next().await {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => {
+                        return Err(Error::connection_failed(format!("{} websocket error: {}", protocol, error)))
+                    }
+                    None => return Err(Error::connection_failed(format!("{} websocket closed", protocol))),
+                };
+                let Ok(response) = serde_json::from_str::<Value>(&message) else { continue };
+                let Some(id) = response.get("id").and_then(Value::as_u64) else { continue };
+                if let Some(error) = response.get("error") {
+                    let method = id
+                        .checked_sub(1)
+                        .and_then(|index| commands.get(index as usize))
+                        .map(|(method, _)| *method)
+                        .unwrap_or_default();
+                    return Err(Error::ActionFailed(format!("{} command {} failed: {}", protocol, method, error)));
+                }
+                pending -= 1;
+            }
+            Ok(())
+        })
+        .await;
+
+        match acknowledged {
+            Ok(result) => result.map(|_| ws_stream),
+            Err(_) => Err(Error::timeout_duration(
+                format!("{} event subscription", protocol),
+                Duration::from_secs(10),
+            )),
+        }
+    }
+
     async fn wait_for_load_state_via_cdp(
         &self,
+        window: Option<&WindowHandle>,
         state: crate::core::WaitUntilState,
         timeout: Duration,
     ) -> Result<Option<()>> {
-        let ws_url = match self.cdp_websocket_url_for_current_page().await? {
-            Some(url) => url,
-            None => return Ok(None),
+        let ws_url = {
+            let _window = self.hold_window(window).await?;
+            match self.cdp_websocket_url_for_current_page().await? {
+                Some(url) => url,
+                None => return Ok(None),
+            }
         };
 
         let (mut ws_stream, _) = match connect_async(&ws_url).await {
@@ -437,25 +1154,21 @@ impl WebDriverAdapter {
             }
         };
 
-        let mut next_id = 1u64;
-
-        for (method, params) in [
+        for (id, (method, params)) in (1u64..).zip([
             ("Page.enable", serde_json::json!({})),
             ("Network.enable", serde_json::json!({})),
             (
                 "Page.setLifecycleEventsEnabled",
                 serde_json::json!({"enabled": true}),
             ),
-        ] {
-            let id = next_id;
-            next_id += 1;
+        ]) {
             let message = serde_json::json!({
                 "id": id,
                 "method": method,
                 "params": params,
             });
             let text = serde_json::to_string(&message)
-                .map_err(|e| Error::Serialization(e))?;
+                .map_err(Error::Serialization)?;
             ws_stream
                 .send(Message::Text(text.into()))
                 .await
@@ -466,6 +1179,24 @@ impl WebDriverAdapter {
         let mut inflight: HashSet<String> = HashSet::new();
         let mut idle_timer: Option<Pin<Box<Sleep>>> = None;
 
+        // Lifecycle events only report changes, so start from the state the
+        // document is already in. After a navigation this is only the new
+        // document once it has committed, which `Page::goto` waits for first
+        let ready_state = self.ready_state(window).await.ok();
+        match ready_state.as_ref().and_then(Value::as_str) {
+            Some("complete") => {
+                snapshot.commit = true;
+                snapshot.domcontentloaded = true;
+                snapshot.load = true;
+                idle_timer = Some(Box::pin(tokio::time::sleep(Duration::from_millis(500))));
+            }
+            Some("interactive") => {
+                snapshot.commit = true;
+                snapshot.domcontentloaded = true;
+            }
+            _ => {}
+        }
+
         let deadline = Instant::now() + timeout;
 
         loop {
@@ -643,12 +1374,35 @@ impl WebDriverAdapter {
 
     /// Navigate to a URL
     pub async fn goto(&self, url: &str) -> Result<()> {
-        self.apply_slow_mo().await;
+        self.apply_slow_mo(SlowMoScope::Navigation).await;
         tracing::debug!("WebDriver: navigating to {}", url);
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        driver.goto(url).await?;
-        Ok(())
+        self.reconnect_on_failure(|driver| async move { Ok(driver.goto(url).await?) }).await
+    }
+
+    /// Navigate to a URL, sending `referrer` as the `Referer` header
+    ///
+    /// WebDriver's navigation can't set headers, so this uses CDP
+    /// `Page.navigate`, whose referrer only applies to this navigation.
+    pub async fn goto_with_referrer(&self, url: &str, referrer: &str) -> Result<()> {
+        if !self.has_cdp {
+            return Err(Error::not_implemented("navigation referer without CDP"));
+        }
+        self.apply_slow_mo(SlowMoScope::Navigation).await;
+        tracing::debug!("CDP: navigating to {} with referrer {}", url, referrer);
+        let result = self
+            .execute_cdp_with_params("Page.navigate", serde_json::json!({ "url": url, "referrer": referrer }))
+            .await?;
+        match result["errorText"].as_str() {
+            Some(error) if !error.is_empty() => Err(Error::ActionFailed(error.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reload the current page
+    pub async fn reload(&self) -> Result<()> {
+        self.apply_slow_mo(SlowMoScope::Navigation).await;
+        tracing::debug!("WebDriver: reloading page");
+        self.reconnect_on_failure(|driver| async move { Ok(driver.refresh().await?) }).await
     }
 
     /// Wait for the page to reach a specific load state
@@ -661,6 +1415,19 @@ impl WebDriverAdapter {
     /// # Returns
     /// Ok(()) if the state is reached, Err if timeout or other error occurs
     pub async fn wait_for_load_state(&self, state: crate::core::WaitUntilState, timeout: Duration) -> Result<()> {
+        self.wait_for_load_state_in(None, state, timeout).await
+    }
+
+    /// Wait for `window` to reach a load state, switching to it only for each command
+    ///
+    /// Other pages' commands can run while this waits on CDP events or
+    /// sleeps between polls.
+    pub(crate) async fn wait_for_load_state_in(
+        &self,
+        window: Option<&WindowHandle>,
+        state: crate::core::WaitUntilState,
+        timeout: Duration,
+    ) -> Result<()> {
         use crate::core::WaitUntilState;
         
         tracing::debug!("Waiting for load state: {:?}", state);
@@ -668,26 +1435,31 @@ impl WebDriverAdapter {
         
         match state {
             WaitUntilState::Load => {
-                let guard = self.driver().await?;
-                let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+                match self.wait_for_load_state_via_cdp(window, state, timeout).await {
+                    Ok(Some(())) => return Ok(()),
+                    Ok(None) => {}
+                    Err(Error::BrowserClosed) => return Err(Error::BrowserClosed),
+                    Err(error) => {
+                        tracing::debug!("CDP load state wait failed, falling back to JS: {}", error);
+                    }
+                }
+
+                loop {
+                    if start.elapsed() >= timeout {
+                        return Err(Error::timeout_duration("wait for load state: load", timeout));
+                    }
 
-                match tokio::time::timeout(timeout, driver.title()).await {
-                    Ok(Ok(_)) => {
-                        tracing::debug!("Load state 'load' reached via WebDriver");
-                        Ok(())
+                    let ready_state = self.ready_state(window).await?;
+                    if ready_state.as_str() == Some("complete") {
+                        tracing::debug!("Load state 'load' reached");
+                        return Ok(());
                     }
-                    Ok(Err(error)) => Err(Error::ActionFailed(format!(
-                        "Failed to wait for load state 'load': {}",
-                        error
-                    ))),
-                    Err(_) => Err(Error::timeout_duration(
-                        "wait for load state: load",
-                        timeout,
-                    )),
+
+                    tokio::time::sleep(Duration::from_millis(100)).await;
                 }
             }
             WaitUntilState::DomContentLoaded => {
-                match self.wait_for_load_state_via_cdp(state, timeout).await {
+                match self.wait_for_load_state_via_cdp(window, state, timeout).await {
                     Ok(Some(())) => return Ok(()),
                     Ok(None) => {}
                     Err(Error::BrowserClosed) => return Err(Error::BrowserClosed),
@@ -705,7 +1477,7 @@ impl WebDriverAdapter {
                         ));
                     }
 
-                    let ready_state = self.execute_script("return document.readyState").await?;
+                    let ready_state = self.ready_state(window).await?;
                     let state_str = ready_state.as_str();
                     if state_str == Some("interactive") || state_str == Some("complete") {
                         tracing::debug!("Load state 'domcontentloaded' reached");
@@ -716,7 +1488,7 @@ impl WebDriverAdapter {
                 }
             }
             WaitUntilState::NetworkIdle => {
-                match self.wait_for_load_state_via_cdp(state, timeout).await {
+                match self.wait_for_load_state_via_cdp(window, state, timeout).await {
                     Ok(Some(())) => return Ok(()),
                     Ok(None) => {}
                     Err(Error::BrowserClosed) => return Err(Error::BrowserClosed),
@@ -734,11 +1506,11 @@ impl WebDriverAdapter {
                         ));
                     }
 
-                    let ready_state = self.execute_script("return document.readyState").await?;
+                    let ready_state = self.ready_state(window).await?;
                     if ready_state.as_str() == Some("complete") {
                         tokio::time::sleep(Duration::from_millis(500)).await;
 
-                        let ready_state = self.execute_script("return document.readyState").await?;
+                        let ready_state = self.ready_state(window).await?;
                         if ready_state.as_str() == Some("complete") {
                             tracing::debug!("Load state 'networkidle' reached");
                             return Ok(());
@@ -749,7 +1521,7 @@ impl WebDriverAdapter {
                 }
             }
             WaitUntilState::Commit => {
-                match self.wait_for_load_state_via_cdp(state, timeout).await {
+                match self.wait_for_load_state_via_cdp(window, state, timeout).await {
                     Ok(Some(())) => return Ok(()),
                     Ok(None) => {}
                     Err(Error::BrowserClosed) => return Err(Error::BrowserClosed),
@@ -764,7 +1536,7 @@ impl WebDriverAdapter {
                         return Err(Error::timeout_duration("wait for load state: commit", timeout));
                     }
 
-                    let ready_state = self.execute_script("return document.readyState").await?;
+                    let ready_state = self.ready_state(window).await?;
                     if ready_state.as_str() != Some("loading") {
                         tracing::debug!("Load state 'commit' reached");
                         return Ok(());
@@ -776,72 +1548,454 @@ impl WebDriverAdapter {
         }
     }
 
+    /// `document.readyState` of `window`, or of the current window
+    async fn ready_state(&self, window: Option<&WindowHandle>) -> Result<Value> {
+        let _window = self.hold_window(window).await?;
+        self.execute_script("return document.readyState").await
+    }
+
     /// Get the current URL
     pub async fn current_url(&self) -> Result<String> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let url = driver.current_url().await?;
-        Ok(url.to_string())
+        self.retry_on_reconnect(|driver| async move { Ok(driver.current_url().await?.to_string()) })
+            .await
     }
 
     /// Get the page title
     pub async fn title(&self) -> Result<String> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let title = driver.title().await?;
-        Ok(title)
+        self.retry_on_reconnect(|driver| async move { Ok(driver.title().await?) }).await
     }
 
     /// Get the current page source as HTML
     pub async fn page_source(&self) -> Result<String> {
+        self.retry_on_reconnect(|driver| async move { Ok(driver.source().await?) }).await
+    }
+
+    /// Serialize the current document, including its doctype
+    ///
+    /// Runs in the current browsing context, so after switching into a frame
+    /// it returns that frame's document. Large documents are read in slices
+    /// so no single script result grows past 1M characters.
+    pub async fn document_content(&self) -> Result<String> {
         let guard = self.driver().await?;
         let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let source = driver.source().await?;
-        Ok(source)
-    }
+        let result = driver.execute(CONTENT_JS, vec![json!(CONTENT_CHUNK)]).await?;
+        let length = match result.json() {
+            Value::String(html) => return Ok(html.clone()),
+            Value::Object(stashed) => stashed.get("length").and_then(Value::as_u64).unwrap_or(0),
+            other => {
+                return Err(Error::internal(format!(
+                    "Unexpected document content result: {}",
+                    other
+                )))
+            }
+        };
 
-    /// Find an element by CSS selector
-    pub async fn find_element(&self, selector: &str) -> Result<WebElement> {
-        self.apply_slow_mo().await;
-        self.find_element_raw(selector).await
+        let mut html = String::new();
+        let mut offset = 0;
+        while offset < length {
+            let result = driver
+                .execute(CONTENT_SLICE_JS, vec![json!(offset), json!(CONTENT_CHUNK)])
+                .await?;
+            let (slice, end) = match result.json() {
+                Value::Array(parts) => (
+                    parts.first().and_then(Value::as_str).unwrap_or_default(),
+                    parts.get(1).and_then(Value::as_u64).unwrap_or(0),
+                ),
+                _ => ("", 0),
+            };
+            if end <= offset {
+                return Err(Error::internal(
+                    "Document changed while its content was being read",
+                ));
+            }
+            html.push_str(slice);
+            offset = end;
+        }
+        Ok(html)
     }
 
-    /// Find all elements matching a CSS selector
-    pub async fn find_elements(&self, selector: &str) -> Result<Vec<WebElement>> {
-        self.find_elements_raw(selector).await
+    /// Serialize the top-level document over CDP `DOM.getOuterHTML`
+    ///
+    /// Not subject to script result limits, but always reads the main frame.
+    pub async fn document_outer_html(&self) -> Result<String> {
+        let document = self
+            .execute_cdp_with_params("DOM.getDocument", json!({ "depth": 0 }))
+            .await?;
+        let node_id = document["root"]["nodeId"]
+            .as_i64()
+            .ok_or_else(|| Error::internal("DOM.getDocument returned no root node"))?;
+        let result = self
+            .execute_cdp_with_params("DOM.getOuterHTML", json!({ "nodeId": node_id }))
+            .await?;
+        result["outerHTML"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| Error::internal("DOM.getOuterHTML returned no HTML"))
     }
 
-    /// Switch to a frame by CSS selector
-    ///
-    /// This method automatically waits for the iframe to appear before switching.
-    ///
-    /// # Arguments
-    /// * `frame_selector` - CSS selector to locate the iframe element
-    pub async fn switch_to_frame_by_selector(&self, frame_selector: &str) -> Result<()> {
-        self.apply_slow_mo().await;
+    /// Click an element while holding modifier keys
+    pub async fn click_with_modifiers(
+        &self,
+        element: &WebElement,
+        modifiers: &[KeyboardModifier],
+    ) -> Result<()> {
+        self.apply_slow_mo(SlowMoScope::Input).await;
+        let keys: Vec<Key> = modifiers.iter().map(modifier_key).collect();
 
-        // Wait for the iframe to appear (with retry logic)
-        let timeout = Duration::from_secs(30);
-        let start = std::time::Instant::now();
-        let target_frame = loop {
-            match self.find_element_raw(frame_selector).await {
-                Ok(frame) => break frame,
-                Err(Error::ElementNotFound { .. }) if start.elapsed() >= timeout => {
-                    return Err(Error::timeout_duration(
-                        &format!("iframe not found: {}", frame_selector),
-                        timeout,
-                    ));
-                }
-                Err(Error::ElementNotFound { .. }) => {
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                }
-                Err(error) => return Err(error),
+        self.reconnect_on_failure(|driver| async move {
+            let mut chain = driver.action_chain();
+            for key in &keys {
+                chain = chain.key_down(key.clone());
             }
+            chain = chain.click_element(element);
+            for key in keys.iter().rev() {
+                chain = chain.key_up(key.clone());
+            }
+            Ok(chain.perform().await?)
+        })
+        .await
+    }
+
+    /// Click an element with any mouse button, `click_count` times
+    ///
+    /// Presses and releases `button` over the element's center, or `position`
+    /// within it, with CDP `Input.dispatchMouseEvent`, so right and middle
+    /// clicks fire `contextmenu` and `auxclick` like real input. `modifiers`
+    /// are reported as held on the events. Without CDP only single right
+    /// clicks are supported, as a WebDriver context click.
+    pub async fn click_with_button(
+        &self,
+        element: &WebElement,
+        button: MouseButton,
+        click_count: u32,
+        position: Option<Position>,
+        modifiers: &[KeyboardModifier],
+    ) -> Result<()> {
+        if !self.has_cdp {
+            if !matches!(button, MouseButton::Right) || click_count > 1 || position.is_some() {
+                return Err(Error::not_implemented(CDP_REQUIRED));
+            }
+            self.apply_slow_mo(SlowMoScope::Input).await;
+            let guard = self.driver().await?;
+            let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+            let keys: Vec<Key> = modifiers.iter().map(modifier_key).collect();
+            let mut chain = driver.action_chain();
+            for key in &keys {
+                chain = chain.key_down(key.clone());
+            }
+            chain = chain.context_click_element(element);
+            for key in keys.iter().rev() {
+                chain = chain.key_up(key.clone());
+            }
+            chain.perform().await?;
+            return Ok(());
+        }
+
+        self.apply_slow_mo(SlowMoScope::Input).await;
+        let rect = self
+            .execute_script_with_args(
+                r#"
+                const el = arguments[0];
+                el.scrollIntoView({ block: 'center', inline: 'center' });
+                const r = el.getBoundingClientRect();
+                return { x: r.left, y: r.top, width: r.width, height: r.height };
+                "#,
+                vec![element.to_json()?],
+            )
+            .await?;
+        let value = |key: &str| rect.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        let (x, y) = match position {
+            Some(position) => (value("x") + position.x, value("y") + position.y),
+            None => (value("x") + value("width") / 2.0, value("y") + value("height") / 2.0),
         };
-        
-        tracing::debug!("Found iframe with selector: {}", frame_selector);
 
-        target_frame.enter_frame().await?;
+        let (name, buttons) = match button {
+            MouseButton::Left => ("left", 1),
+            MouseButton::Right => ("right", 2),
+            MouseButton::Middle => ("middle", 4),
+        };
+        let modifiers: u8 = modifiers
+            .iter()
+            .map(|modifier| match modifier {
+                KeyboardModifier::Alt => 1,
+                KeyboardModifier::Control => 2,
+                KeyboardModifier::Meta => 4,
+                KeyboardModifier::Shift => 8,
+            })
+            .sum();
+
+        self.execute_cdp_with_params(
+            "Input.dispatchMouseEvent",
+            json!({ "type": "mouseMoved", "x": x, "y": y, "modifiers": modifiers }),
+        )
+        .await?;
+        for count in 1..=click_count.max(1) {
+            for (kind, held) in [("mousePressed", buttons), ("mouseReleased", 0)] {
+                self.execute_cdp_with_params(
+                    "Input.dispatchMouseEvent",
+                    json!({
+                        "type": kind,
+                        "x": x,
+                        "y": y,
+                        "button": name,
+                        "buttons": held,
+                        "clickCount": count,
+                        "modifiers": modifiers,
+                    }),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Press a key or combo such as `"Enter"` or `"Control+K"` on the focused element
+    ///
+    /// Modifiers are held down while the final key is pressed and released in
+    /// reverse order afterwards.
+    pub async fn press_key(&self, combo: &str) -> Result<()> {
+        let (modifiers, key) = parse_key_combo(combo)?;
+        self.apply_slow_mo(SlowMoScope::Input).await;
+        let modifier_keys: Vec<Key> = modifiers.iter().map(modifier_key).collect();
+
+        self.reconnect_on_failure(|driver| async move {
+            let mut chain = driver.action_chain();
+            for modifier in &modifier_keys {
+                chain = chain.key_down(modifier.clone());
+            }
+            chain = chain.key_down(key).key_up(key);
+            for modifier in modifier_keys.iter().rev() {
+                chain = chain.key_up(modifier.clone());
+            }
+            Ok(chain.perform().await?)
+        })
+        .await
+    }
+
+    /// Type one character on the focused element with CDP `Input.dispatchKeyEvent`
+    ///
+    /// Unlike `send_keys`, the page sees `keydown`, `keypress`, `input` and
+    /// `keyup` events as for a real key press. `'\n'` presses Enter.
+    pub async fn type_char(&self, ch: char) -> Result<()> {
+        self.apply_slow_mo(SlowMoScope::Input).await;
+        let (key, code, key_code, text) = key_event_fields(ch);
+        self.execute_cdp_with_params(
+            "Input.dispatchKeyEvent",
+            json!({
+                "type": "keyDown",
+                "key": key,
+                "code": code,
+                "windowsVirtualKeyCode": key_code,
+                "text": text,
+                "unmodifiedText": text,
+            }),
+        )
+        .await?;
+        self.execute_cdp_with_params(
+            "Input.dispatchKeyEvent",
+            json!({ "type": "keyUp", "key": key, "code": code, "windowsVirtualKeyCode": key_code }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Find an element by CSS selector
+    ///
+    /// Selectors starting with `pierce=` or containing `>>>` reach into
+    /// shadow roots; see `Page::locator`.
+    pub async fn find_element(&self, selector: &str) -> Result<WebElement> {
+        self.apply_slow_mo(SlowMoScope::Input).await;
+        self.find_element_raw(selector).await
+    }
+
+    /// Find all elements matching a CSS selector
+    pub async fn find_elements(&self, selector: &str) -> Result<Vec<WebElement>> {
+        self.find_elements_raw(selector).await
+    }
+
+    /// Find the element at `index` among those matching a CSS selector
+    ///
+    /// Only that element is sent back, so picking one out of a long list
+    /// doesn't fetch a reference to every match.
+    pub async fn find_nth_element(&self, selector: &str, index: usize) -> Result<Option<WebElement>> {
+        if is_deep_selector(selector) {
+            return Ok(self.find_deep_elements(selector).await?.into_iter().nth(index));
+        }
+
+        self.retry_on_reconnect(|driver| async move {
+            let result = driver
+                .execute(
+                    "return document.querySelectorAll(arguments[0])[arguments[1]] || null;",
+                    vec![json!(selector), json!(index)],
+                )
+                .await?;
+            match result.json() {
+                Value::Null => Ok(None),
+                value => Self::element_from_value(value.clone(), driver.handle.clone()).map(Some),
+            }
+        })
+        .await
+    }
+
+    /// Count elements matching a selector without fetching element handles
+    ///
+    /// CSS selectors use `querySelectorAll`; selectors starting with `//`, `(`
+    /// or `xpath=` are counted with `document.evaluate`. Shadow-piercing
+    /// `pierce=` and `>>>` selectors walk the shadow roots.
+    pub async fn count_elements(&self, selector: &str) -> Result<usize> {
+        if is_deep_selector(selector) {
+            return Ok(self.find_deep_elements(selector).await?.len());
+        }
+
+        let xpath = selector
+            .strip_prefix("xpath=")
+            .or_else(|| (selector.starts_with("//") || selector.starts_with('(')).then_some(selector));
+
+        let result = match xpath {
+            Some(xpath) => {
+                self.execute_script_with_args(
+                    "return document.evaluate(arguments[0], document, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null).snapshotLength;",
+                    vec![json!(xpath)],
+                )
+                .await?
+            }
+            None => {
+                self.execute_script_with_args(
+                    "return document.querySelectorAll(arguments[0]).length;",
+                    vec![json!(selector)],
+                )
+                .await?
+            }
+        };
+
+        result
+            .as_u64()
+            .map(|count| count as usize)
+            .ok_or_else(|| Error::ActionFailed(format!("Unexpected count result for '{}': {}", selector, result)))
+    }
+
+    /// Find elements by computed ARIA role and accessible name
+    ///
+    /// Looks the nodes up in CDP `Accessibility.getFullAXTree`, so implicit
+    /// roles and names from `aria-label`, `aria-labelledby` or `<label>` are
+    /// the browser's own. The nodes are mapped to DOM nodes with
+    /// `DOM.pushNodesByBackendIdsToFrontend`, resolved to JS objects and
+    /// handed to WebDriver through a script, in document order, without
+    /// touching the DOM. `name` matches in full with `exact`, otherwise as a
+    /// case-insensitive substring.
+    pub async fn find_elements_by_accessible_name(&self, role: &str, name: &str, exact: bool) -> Result<Vec<WebElement>> {
+        if !self.has_cdp {
+            return Err(Error::not_implemented("accessibility tree lookup without CDP"));
+        }
+
+        let tree = self.execute_cdp("Accessibility.getFullAXTree").await?;
+        let nodes = tree["nodes"].as_array().map(Vec::as_slice).unwrap_or_default();
+        let backend_ids = crate::core::accessibility::backend_node_ids_by_role(nodes, role, name, exact);
+        if backend_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Backend ids can only be pushed once the document has been requested
+        self.execute_cdp_with_params("DOM.getDocument", json!({ "depth": 0 })).await?;
+        let pushed = self
+            .execute_cdp_with_params("DOM.pushNodesByBackendIdsToFrontend", json!({ "backendNodeIds": backend_ids }))
+            .await?;
+        // 0 means the node is gone from the document
+        let node_ids: Vec<i64> =
+            pushed["nodeIds"].as_array().into_iter().flatten().filter_map(Value::as_i64).filter(|id| *id != 0).collect();
+        if node_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let key = format!("sparkle-ax-{}", AX_STASH_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let stashed = self.stash_ax_nodes(&key, &node_ids).await;
+        // Take the stash and release the objects even when stashing failed halfway
+        let take_args = vec![json!(key)];
+        let taken = self
+            .reconnect_on_failure(|driver| async move {
+                let result = driver.execute(TAKE_AX_NODES_JS, take_args).await?;
+                let values: Vec<Value> = serde_json::from_value(result.json().clone())?;
+                values
+                    .into_iter()
+                    .map(|value| Self::element_from_value(value, driver.handle.clone()))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .await;
+        let released = self.execute_cdp_with_params("Runtime.releaseObjectGroup", json!({ "objectGroup": key })).await;
+        stashed?;
+        let elements = taken?;
+        released?;
+        Ok(elements)
+    }
+
+    /// Resolve DOM nodes to JS objects in the `key` object group and stash
+    /// them on the window for `TAKE_AX_NODES_JS`
+    async fn stash_ax_nodes(&self, key: &str, node_ids: &[i64]) -> Result<()> {
+        let mut object_ids = Vec::with_capacity(node_ids.len());
+        for node_id in node_ids {
+            let resolved = self
+                .execute_cdp_with_params("DOM.resolveNode", json!({ "nodeId": node_id, "objectGroup": key }))
+                .await?;
+            let object_id = resolved["object"]["objectId"]
+                .as_str()
+                .ok_or_else(|| Error::ActionFailed(format!("DOM node {} did not resolve to an object", node_id)))?;
+            object_ids.push(json!({ "objectId": object_id }));
+        }
+
+        let target = object_ids[0]["objectId"].clone();
+        let mut arguments = vec![json!({ "value": key })];
+        arguments.extend(object_ids);
+        self.execute_cdp_with_params(
+            "Runtime.callFunctionOn",
+            json!({ "functionDeclaration": STASH_AX_NODES_JS, "objectId": target, "arguments": arguments }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Switch to a frame by CSS selector
+    ///
+    /// This method automatically waits for the iframe to appear before switching.
+    ///
+    /// # Arguments
+    /// * `frame_selector` - CSS selector to locate the iframe element
+    pub async fn switch_to_frame_by_selector(&self, frame_selector: &str) -> Result<()> {
+        self.apply_slow_mo(SlowMoScope::Input).await;
+
+        // Wait for the iframe to appear (with retry logic)
+        let timeout = Duration::from_secs(30);
+        let start = std::time::Instant::now();
+        let mut retried = false;
+        loop {
+            let target_frame = loop {
+                match self.find_element_raw(frame_selector).await {
+                    Ok(frame) => break frame,
+                    Err(Error::ElementNotFound { .. }) if start.elapsed() >= timeout => {
+                        return Err(Error::timeout_duration(
+                            format!("iframe not found: {}", frame_selector),
+                            timeout,
+                        ));
+                    }
+                    Err(Error::ElementNotFound { .. }) => {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                    Err(error) => return Err(error),
+                }
+            };
+
+            tracing::debug!("Found iframe with selector: {}", frame_selector);
+
+            match target_frame.enter_frame().await {
+                Ok(()) => break,
+                // The frame was replaced between finding and entering it; find it again once
+                Err(error) if !retried && is_frame_detached(&error) && start.elapsed() < timeout => {
+                    tracing::debug!("iframe '{}' detached before switching, retrying", frame_selector);
+                    retried = true;
+                }
+                Err(error) => return Err(Error::from(error)),
+            }
+        }
+
         tracing::debug!("Switched to frame: {}", frame_selector);
         Ok(())
     }
@@ -851,7 +2005,7 @@ impl WebDriverAdapter {
     /// # Arguments
     /// * `frame_element` - The iframe element to switch to (must be obtained in parent context)
     pub async fn switch_to_frame(&self, frame_element: &WebElement) -> Result<()> {
-        self.apply_slow_mo().await;
+        self.apply_slow_mo(SlowMoScope::Input).await;
         let guard = self.driver().await?;
         let _driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
 
@@ -864,7 +2018,7 @@ impl WebDriverAdapter {
     ///
     /// This exits all iframe contexts and returns to the top-level page.
     pub async fn switch_to_default_content(&self) -> Result<()> {
-        self.apply_slow_mo().await;
+        self.apply_slow_mo(SlowMoScope::Input).await;
         let guard = self.driver().await?;
         let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
         driver.enter_default_frame().await?;
@@ -876,7 +2030,7 @@ impl WebDriverAdapter {
     ///
     /// This switches from the current frame to its parent frame.
     pub async fn switch_to_parent_frame(&self) -> Result<()> {
-        self.apply_slow_mo().await;
+        self.apply_slow_mo(SlowMoScope::Input).await;
         let guard = self.driver().await?;
         let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
         driver.enter_parent_frame().await?;
@@ -884,13 +2038,159 @@ impl WebDriverAdapter {
         Ok(())
     }
 
-    /// Execute JavaScript in the browser context
-    pub async fn execute_script(&self, script: &str) -> Result<serde_json::Value> {
-        self.apply_slow_mo().await;
+    /// Lock the current window once no guard holds it, or only guards for `handle`
+    async fn lock_windows(&self, handle: Option<&WindowHandle>) -> tokio::sync::MutexGuard<'_, Option<WindowHandle>> {
+        loop {
+            let released = self.windows.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let current = self.windows.current.lock().await;
+            let shared = handle.is_some() && current.as_ref() == handle;
+            if shared || self.windows.holders.load(Ordering::SeqCst) == 0 {
+                return current;
+            }
+            drop(current);
+            released.await;
+        }
+    }
+
+    /// Hand out a guard for the window `current` was just set to
+    fn window_guard(&self) -> WindowGuard {
+        self.windows.holders.fetch_add(1, Ordering::SeqCst);
+        WindowGuard {
+            windows: Arc::clone(&self.windows),
+        }
+    }
+
+    /// Claim a browser tab for a new page
+    ///
+    /// The first page takes over the session's initial tab; later pages get a
+    /// freshly opened tab. The driver is switched to the returned window and
+    /// stays there while the guard is held.
+    pub async fn claim_window(&self) -> Result<(WindowHandle, WindowGuard)> {
+        let mut current = self.lock_windows(None).await;
+        let guard = self.driver().await?;
+        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+
+        let handle = if self.windows.claimed.load(Ordering::SeqCst) == 0 {
+            let handle = driver.window().await?;
+            if current.as_ref() != Some(&handle) {
+                driver.switch_to_window(handle.clone()).await?;
+            }
+            handle
+        } else {
+            let handle = driver.new_tab().await?;
+            driver.switch_to_window(handle.clone()).await?;
+            tracing::debug!("Opened new tab: {}", handle);
+            handle
+        };
+        self.windows.claimed.fetch_add(1, Ordering::SeqCst);
+
+        *current = Some(handle.clone());
+        Ok((handle, self.window_guard()))
+    }
+
+    /// Release a tab previously returned by `claim_window`
+    ///
+    /// Closes the tab unless it is the last one, which is kept open (and made
+    /// available to the next page) so the session stays alive.
+    pub async fn release_window(&self, handle: &WindowHandle) -> Result<()> {
+        let mut current = self.lock_windows(None).await;
         let guard = self.driver().await?;
         let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let result = driver.execute(script, Vec::new()).await?;
-        Ok(result.json().clone())
+
+        let windows = driver.windows().await?;
+        if windows.len() <= 1 {
+            self.windows.claimed.store(0, Ordering::SeqCst);
+            return Ok(());
+        }
+        let _ = self
+            .windows
+            .claimed
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |claimed| Some(claimed.saturating_sub(1)));
+
+        driver.switch_to_window(handle.clone()).await?;
+        driver.close_window().await?;
+        tracing::debug!("Closed tab: {}", handle);
+
+        {
+            let mut front = self.windows.front.lock().unwrap();
+            if front.as_ref() == Some(handle) {
+                *front = None;
+            }
+        }
+        if let Some(remaining) = windows.into_iter().find(|w| w != handle) {
+            driver.switch_to_window(remaining.clone()).await?;
+            *current = Some(remaining);
+        } else {
+            *current = None;
+        }
+        Ok(())
+    }
+
+    /// Switch the driver to the given window if it isn't already active
+    ///
+    /// The driver stays on the window until the returned guard is dropped.
+    /// Guards for the same window can be held at once; another window waits.
+    pub async fn activate_window(&self, handle: &WindowHandle) -> Result<WindowGuard> {
+        let mut current = self.lock_windows(Some(handle)).await;
+        if current.as_ref() != Some(handle) {
+            let guard = self.driver().await?;
+            let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+            driver.switch_to_window(handle.clone()).await?;
+            *current = Some(handle.clone());
+            tracing::debug!("Switched to window: {}", handle);
+        }
+        Ok(self.window_guard())
+    }
+
+    /// Keep the driver on `window` for the next command, if one is given
+    pub(crate) async fn hold_window(&self, window: Option<&WindowHandle>) -> Result<Option<WindowGuard>> {
+        match window {
+            Some(handle) => Ok(Some(self.activate_window(handle).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Switch to the given window and bring its tab to the foreground
+    ///
+    /// Without CDP this only switches the WebDriver context to the window.
+    pub async fn bring_window_to_front(&self, handle: &WindowHandle) -> Result<WindowGuard> {
+        let guard = self.activate_window(handle).await?;
+        if self.has_cdp {
+            self.execute_cdp("Page.bringToFront").await?;
+            *self.windows.front.lock().unwrap() = Some(handle.clone());
+        }
+        Ok(guard)
+    }
+
+    /// Bring the window to the foreground before input or screenshots when several tabs are open
+    ///
+    /// Background tabs don't render or receive focus, so actions on them can
+    /// misbehave. The tab is only brought to the front when another page's
+    /// tab was last.
+    pub async fn focus_window_for_input(&self, handle: &WindowHandle) -> Result<WindowGuard> {
+        let in_front = self.windows.front.lock().unwrap().as_ref() == Some(handle);
+        if self.windows.claimed.load(Ordering::SeqCst) > 1 && !in_front {
+            self.bring_window_to_front(handle).await
+        } else {
+            self.activate_window(handle).await
+        }
+    }
+
+    /// Number of open windows/tabs in the session
+    pub async fn window_count(&self) -> Result<usize> {
+        let guard = self.driver().await?;
+        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+        Ok(driver.windows().await?.len())
+    }
+
+    /// Execute JavaScript in the browser context
+    pub async fn execute_script(&self, script: &str) -> Result<serde_json::Value> {
+        self.apply_slow_mo(SlowMoScope::Script).await;
+        self.reconnect_on_failure(|driver| async move { Ok(driver.execute(script, Vec::new()).await?.json().clone()) })
+            .await
     }
 
     /// Execute JavaScript with arguments
@@ -899,18 +2199,273 @@ impl WebDriverAdapter {
         script: &str,
         args: Vec<serde_json::Value>,
     ) -> Result<serde_json::Value> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let result = driver.execute(script, args).await?;
-        Ok(result.json().clone())
+        self.reconnect_on_failure(|driver| async move { Ok(driver.execute(script, args).await?.json().clone()) })
+        .await
+    }
+
+    /// Execute asynchronous JavaScript that reports its result through the
+    /// callback passed as the last argument
+    pub async fn execute_async_script(
+        &self,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        self.reconnect_on_failure(|driver| async move { Ok(driver.execute_async(script, args).await?.json().clone()) })
+        .await
+    }
+
+    /// Wait until the element matching `selector` reaches `state`
+    ///
+    /// A `MutationObserver` injected with one async script resolves as soon as
+    /// the DOM changes make the selector reach the state, instead of polling
+    /// over the wire. Long waits are split into slices that stay below the
+    /// session's script timeout. Returns the element for `Attached` and
+    /// `Visible`, and `None` for `Detached` and `Hidden`.
+    pub async fn wait_for_selector(
+        &self,
+        selector: &str,
+        state: WaitForSelectorState,
+        timeout: Duration,
+    ) -> Result<Option<WebElement>> {
+        self.wait_for_selector_in(None, selector, state, timeout).await
+    }
+
+    /// Wait for `selector` in `window`, switching to it only for each slice of the wait
+    pub(crate) async fn wait_for_selector_in(
+        &self,
+        window: Option<&WindowHandle>,
+        selector: &str,
+        state: WaitForSelectorState,
+        timeout: Duration,
+    ) -> Result<Option<WebElement>> {
+        let deadline = Instant::now() + timeout;
+        let max_slice = if window.is_some() { WINDOW_WAIT_FOR_SELECTOR_SLICE } else { WAIT_FOR_SELECTOR_SLICE };
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(Error::timeout_duration(
+                    format!("Element '{}' not {}", selector, state.as_str()),
+                    timeout,
+                ));
+            }
+
+            let slice = remaining.min(max_slice);
+            let window_guard = self.hold_window(window).await?;
+            let result = self
+                .execute_async_script(
+                    WAIT_FOR_SELECTOR_JS,
+                    vec![
+                        json!(deep_selector_parts(selector)),
+                        json!(state.as_str()),
+                        json!(slice.as_millis() as u64),
+                    ],
+                )
+                .await;
+
+            match result {
+                Ok(Value::Object(mut result)) => {
+                    if let Some(message) = result.get("error") {
+                        return Err(Error::invalid_argument(format!(
+                            "Invalid selector '{}': {}",
+                            selector,
+                            message.as_str().unwrap_or_default()
+                        )));
+                    }
+                    return match result.remove("element") {
+                        Some(Value::Null) | None => Ok(None),
+                        Some(element) => {
+                            let guard = self.driver().await?;
+                            let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+                            Self::element_from_value(element, driver.handle.clone()).map(Some)
+                        }
+                    };
+                }
+                Ok(_) => {}
+                Err(Error::BrowserClosed) => return Err(Error::BrowserClosed),
+                // The document was replaced mid-wait (navigation); observe the new one
+                Err(error) => {
+                    tracing::debug!("wait_for_selector observer interrupted: {}", error);
+                    drop(window_guard);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
     }
 
     /// Take a screenshot of the current page
     pub async fn screenshot(&self) -> Result<Vec<u8>> {
-        let guard = self.driver().await?;
-        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
-        let screenshot = driver.screenshot_as_png().await?;
-        Ok(screenshot)
+        self.retry_on_reconnect(|driver| async move { Ok(driver.screenshot_as_png().await?) }).await
+    }
+
+    /// Capture a screenshot of the current page with `options`
+    ///
+    /// See `Page::screenshot_with_options`. Animations are disabled and masks
+    /// drawn just for the capture, and removed again even if it fails.
+    pub async fn screenshot_with_options(&self, options: &ScreenshotOptions) -> Result<Vec<u8>> {
+        let data = self.capture_screenshot_data(options).await?;
+        let bytes = decode_screenshot(&data)?;
+        if let Some(path) = &options.path {
+            write_screenshot(path, &bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Capture a screenshot of the current page with `options` as base64
+    ///
+    /// The image data from CDP is returned as is; it is only decoded when
+    /// `options.path` asks for a file.
+    pub async fn screenshot_base64_with_options(&self, options: &ScreenshotOptions) -> Result<String> {
+        let data = self.capture_screenshot_data(options).await?;
+        if let Some(path) = &options.path {
+            write_screenshot(path, &decode_screenshot(&data)?)?;
+        }
+        Ok(data)
+    }
+
+    /// Capture a screenshot with `options` applied, returning the base64 image data
+    async fn capture_screenshot_data(&self, options: &ScreenshotOptions) -> Result<String> {
+        if options.clip.is_some() && options.full_page == Some(true) {
+            return Err(Error::invalid_argument("clip and full_page cannot be used together"));
+        }
+
+        let format = options.r#type.unwrap_or(ScreenshotType::Png);
+        let mut params = json!({ "format": format.as_str() });
+        if let (ScreenshotType::Jpeg, Some(quality)) = (format, options.quality) {
+            params["quality"] = json!(quality.min(100));
+        }
+
+        // Viewport and device metrics override before capturing beyond the
+        // viewport, to put back if the capture resized it
+        let mut viewport = None;
+        let device_metrics = self.device_metrics_override().await;
+        if let Some(clip) = options.clip {
+            if clip.width <= 0.0 || clip.height <= 0.0 {
+                return Err(Error::invalid_argument("clip width and height must be positive"));
+            }
+            viewport = Some(layout_viewport(&self.execute_cdp("Page.getLayoutMetrics").await?));
+            // Regions outside the viewport are rendered rather than left blank
+            params["captureBeyondViewport"] = json!(true);
+            params["clip"] = json!({
+                "x": clip.x,
+                "y": clip.y,
+                "width": clip.width,
+                "height": clip.height,
+                "scale": 1,
+            });
+        } else if options.full_page == Some(true) {
+            let metrics = self.execute_cdp("Page.getLayoutMetrics").await?;
+            viewport = Some(layout_viewport(&metrics));
+            let content = &metrics["cssContentSize"];
+            params["captureBeyondViewport"] = json!(true);
+            params["clip"] = json!({
+                "x": 0,
+                "y": 0,
+                "width": content["width"].as_f64().unwrap_or(0.0).ceil(),
+                "height": content["height"].as_f64().unwrap_or(0.0).ceil(),
+                "scale": 1,
+            });
+        }
+
+        let disable_animations = options.animations == Some(ScreenshotAnimations::Disabled);
+        let mask = options.mask.as_deref().unwrap_or_default();
+        let stabilize = disable_animations || !mask.is_empty();
+        if stabilize {
+            let args = vec![
+                json!(disable_animations),
+                json!(mask),
+                json!(options.mask_color.as_deref().unwrap_or("#FF00FF")),
+            ];
+            if let Err(error) = self.execute_script_with_args(SCREENSHOT_PREPARE_JS, args).await {
+                let _ = self.execute_script(SCREENSHOT_RESTORE_JS).await;
+                return Err(error);
+            }
+        }
+        // Pausing the document timeline also holds animations started after the
+        // script ran; the script alone is enough if the endpoint refuses
+        let paused = disable_animations
+            && match self.set_animation_playback_rate(0.0).await {
+                Ok(()) => true,
+                Err(error) => {
+                    tracing::debug!("Could not pause the animation timeline: {}", error);
+                    false
+                }
+            };
+
+        // A transparent default background only shows through in PNGs
+        let omit_background = options.omit_background == Some(true) && matches!(format, ScreenshotType::Png);
+        if omit_background {
+            self.execute_cdp_with_params(
+                "Emulation.setDefaultBackgroundColorOverride",
+                json!({ "color": { "r": 0, "g": 0, "b": 0, "a": 0 } }),
+            )
+            .await?;
+        }
+
+        let captured = self.capture_screenshot_base64(params).await;
+
+        // Undo every change even if one of the steps fails, then report the first error
+        let mut restored = Ok(());
+        if paused {
+            restored = restored.and(self.set_animation_playback_rate(1.0).await);
+        }
+        if let Some(viewport) = viewport {
+            restored = restored.and(self.restore_viewport(viewport, device_metrics).await);
+        }
+        if stabilize {
+            restored = restored.and(self.execute_script(SCREENSHOT_RESTORE_JS).await.map(|_| ()));
+        }
+        if omit_background {
+            restored = restored.and(
+                self.execute_cdp_with_params("Emulation.setDefaultBackgroundColorOverride", json!({}))
+                    .await
+                    .map(|_| ()),
+            );
+        }
+        let data = captured?;
+        restored?;
+        Ok(data)
+    }
+
+    /// Set the playback rate of the page's animation timeline (`Animation.setPlaybackRate`)
+    async fn set_animation_playback_rate(&self, rate: f64) -> Result<()> {
+        self.execute_cdp_with_params("Animation.setPlaybackRate", json!({ "playbackRate": rate }))
+            .await
+            .map(|_| ())
+    }
+
+    /// Put the layout viewport back to `size` if capturing beyond it left it resized
+    ///
+    /// The capture's override is cleared and `device_metrics`, the override
+    /// the window had before the capture, is applied again if there was one.
+    async fn restore_viewport(&self, size: (i64, i64), device_metrics: Option<Value>) -> Result<()> {
+        if layout_viewport(&self.execute_cdp("Page.getLayoutMetrics").await?) == size {
+            return Ok(());
+        }
+        tracing::debug!("Screenshot resized the viewport, restoring {}x{}", size.0, size.1);
+        self.execute_cdp("Emulation.clearDeviceMetricsOverride").await?;
+        if let Some(device_metrics) = device_metrics {
+            self.execute_cdp_with_params("Emulation.setDeviceMetricsOverride", device_metrics)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Capture a screenshot via CDP `Page.captureScreenshot` and decode the image bytes
+    pub async fn capture_screenshot(&self, params: serde_json::Value) -> Result<Vec<u8>> {
+        decode_screenshot(&self.capture_screenshot_base64(params).await?)
+    }
+
+    /// Capture a screenshot via CDP `Page.captureScreenshot`, returning the base64 image data
+    pub async fn capture_screenshot_base64(&self, params: serde_json::Value) -> Result<String> {
+        let result = self
+            .execute_cdp_with_params("Page.captureScreenshot", params)
+            .await
+            .map_err(|e| Error::ScreenshotFailed(e.to_string()))?;
+        result["data"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| Error::ScreenshotFailed("Page.captureScreenshot returned no data".to_string()))
     }
 
     /// Close the browser and clean up
@@ -931,6 +2486,12 @@ impl WebDriverAdapter {
         Ok(())
     }
 
+    /// Get a cloneable handle to the underlying WebDriver session
+    pub async fn webdriver_handle(&self) -> Result<WebDriverHandle> {
+        let guard = self.driver().await?;
+        guard.as_ref().cloned().ok_or(Error::BrowserClosed)
+    }
+
     /// Check if the driver is still active
     pub async fn is_closed(&self) -> bool {
         self.driver.read().await.is_none()
@@ -954,13 +2515,7 @@ impl WebDriverAdapter {
     /// # }
     /// ```
     pub async fn execute_cdp(&self, command: &str) -> Result<serde_json::Value> {
-        let cdp_guard = self.cdp().await?;
-        let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
-        
-        let result = dev_tools.execute_cdp(command).await
-            .map_err(|e| Error::ActionFailed(format!("CDP command failed: {}", e)))?;
-        
-        Ok(result)
+        self.execute_cdp_with_params(command, json!({})).await
     }
 
     /// Execute a Chrome DevTools Protocol command with parameters
@@ -987,13 +2542,70 @@ impl WebDriverAdapter {
         command: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value> {
-        let cdp_guard = self.cdp().await?;
-        let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
-        
-        let result = dev_tools.execute_cdp_with_params(command, params).await
-            .map_err(|e| Error::ActionFailed(format!("CDP command failed: {}", e)))?;
-        
-        Ok(result)
+        let device_metrics = match command {
+            "Emulation.setDeviceMetricsOverride" => Some(Some(params.clone())),
+            "Emulation.clearDeviceMetricsOverride" => Some(None),
+            _ => None,
+        };
+        let result = self
+            .reconnect_on_failure(|driver| async move {
+                Ok(ChromeDevTools::new(driver.handle.clone()).execute_cdp_with_params(command, params).await?)
+            })
+            .await
+            .map_err(|e| match e {
+                Error::BrowserClosed => Error::BrowserClosed,
+                e => Error::ActionFailed(format!("CDP command failed: {}", e)),
+            });
+        if let (Some(metrics), Ok(_)) = (device_metrics, &result) {
+            let window = self.current_window_key().await;
+            let mut overrides = self.device_metrics.lock().unwrap_or_else(|e| e.into_inner());
+            match metrics {
+                Some(metrics) => overrides.insert(window, metrics),
+                None => overrides.remove(&window),
+            };
+        }
+        result
+    }
+
+    /// Key of the window commands currently go to, for per-window state
+    async fn current_window_key(&self) -> Option<String> {
+        self.windows.current.lock().await.as_ref().map(ToString::to_string)
+    }
+
+    /// Device metrics override last sent to the current window, if it has one
+    async fn device_metrics_override(&self) -> Option<Value> {
+        let window = self.current_window_key().await;
+        self.device_metrics.lock().unwrap_or_else(|e| e.into_inner()).get(&window).cloned()
+    }
+
+    /// Get the WebDriver session id
+    ///
+    /// Useful for correlating driver logs or attaching to the session from a
+    /// remote debugging tool.
+    pub async fn session_id(&self) -> Result<String> {
+        let guard = self.driver().await?;
+        let driver = guard.as_ref().ok_or(Error::BrowserClosed)?;
+        Ok(driver.handle.session_id().to_string())
+    }
+
+    /// Forget the cached session capabilities
+    ///
+    /// The capabilities are fetched once and reused; after this the next
+    /// lookup (e.g. [`WebDriverAdapter::browser_version`]) asks the driver again.
+    pub async fn refresh_session_capabilities(&self) {
+        *self.session_capabilities.write().await = None;
+    }
+
+    /// Download directory the session was launched with, if any
+    ///
+    /// Set from `LaunchOptions::downloads_path` as the
+    /// `download.default_directory` preference.
+    pub(crate) fn downloads_path(&self) -> Option<std::path::PathBuf> {
+        let requested = self.requested_capabilities.as_ref()?;
+        ["goog:chromeOptions", "ms:edgeOptions"]
+            .iter()
+            .find_map(|key| requested.get(*key)?["prefs"]["download.default_directory"].as_str())
+            .map(std::path::PathBuf::from)
     }
 
     /// Get the browser version
@@ -1016,20 +2628,21 @@ impl WebDriverAdapter {
             }
         }
 
-        let cdp_guard = self.cdp().await?;
-        let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
+        if self.has_cdp {
+            let cdp_guard = self.cdp().await?;
+            let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
 
-        if let Ok(version_info) = dev_tools.execute_cdp("Browser.getVersion").await {
-            if let Some(product) = version_info.get("product") {
-                if let Some(product_str) = product.as_str() {
-                    if let Some(version) = product_str.split('/').nth(1) {
-                        return Ok(version.to_string());
+            if let Ok(version_info) = dev_tools.execute_cdp("Browser.getVersion").await {
+                if let Some(product) = version_info.get("product") {
+                    if let Some(product_str) = product.as_str() {
+                        if let Some(version) = product_str.split('/').nth(1) {
+                            return Ok(version.to_string());
+                        }
                     }
                 }
             }
         }
 
-        drop(cdp_guard);
         let result = self.execute_script("return navigator.userAgent").await?;
         if let Some(ua) = result.as_str() {
             if let Some(start) = ua.find("Chrome/") {
@@ -1058,7 +2671,7 @@ impl WebDriverAdapter {
     /// Returns all cookies for all origins in the browser context.
     /// This is Chromium-only (uses CDP).
     pub async fn get_cookies(&self) -> Result<Vec<crate::core::storage::CookieState>> {
-        use crate::core::storage::{CookieState, SameSite};
+        use crate::core::storage::{CookiePriority, CookieState, SameSite};
         
         let cdp_guard = self.cdp().await?;
         let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
@@ -1109,6 +2722,20 @@ impl WebDriverAdapter {
                 "None" => SameSite::None,
                 _ => SameSite::Lax,
             };
+
+            let priority = match cookie.get("priority").and_then(|v| v.as_str()) {
+                Some("Low") => Some(CookiePriority::Low),
+                Some("Medium") => Some(CookiePriority::Medium),
+                Some("High") => Some(CookiePriority::High),
+                _ => None,
+            };
+
+            // Newer Chromium reports an object, older versions the top-level site string
+            let partition_key = match cookie.get("partitionKey") {
+                Some(serde_json::Value::String(site)) => Some(site.clone()),
+                Some(key) => key.get("topLevelSite").and_then(|v| v.as_str()).map(String::from),
+                None => None,
+            };
             
             cookies.push(CookieState {
                 name,
@@ -1119,6 +2746,10 @@ impl WebDriverAdapter {
                 http_only,
                 secure,
                 same_site,
+                priority,
+                partition_key,
+                same_party: cookie.get("sameParty").and_then(|v| v.as_bool()),
+                url: None,
             });
         }
         
@@ -1127,36 +2758,22 @@ impl WebDriverAdapter {
 
     /// Set cookies via CDP
     ///
-    /// Sets cookies in the browser context.
+    /// Sets cookies in the browser context. Each cookie needs either a `url`
+    /// or both `domain` and `path`; see [`CookieState::url`](crate::core::storage::CookieState::url).
     /// This is Chromium-only (uses CDP).
     pub async fn set_cookies(&self, cookies: &[crate::core::storage::CookieState]) -> Result<()> {
         use serde_json::json;
+
+        // Validate everything up front so a bad cookie doesn't leave the rest half-applied
+        let params = cookies
+            .iter()
+            .map(Self::cookie_params)
+            .collect::<Result<Vec<_>>>()?;
         
         let cdp_guard = self.cdp().await?;
         let dev_tools = cdp_guard.as_ref().ok_or(Error::BrowserClosed)?;
         
-        for cookie in cookies {
-            let same_site_str = match cookie.same_site {
-                crate::core::storage::SameSite::Strict => "Strict",
-                crate::core::storage::SameSite::Lax => "Lax",
-                crate::core::storage::SameSite::None => "None",
-            };
-            
-            let mut cookie_params = json!({
-                "name": cookie.name,
-                "value": cookie.value,
-                "domain": cookie.domain,
-                "path": cookie.path,
-                "httpOnly": cookie.http_only,
-                "secure": cookie.secure,
-                "sameSite": same_site_str,
-            });
-            
-            // Only include expires if it's not a session cookie (-1)
-            if cookie.expires >= 0.0 {
-                cookie_params["expires"] = json!(cookie.expires);
-            }
-            
+        for (cookie, cookie_params) in cookies.iter().zip(params) {
             let params = json!({
                 "cookies": [cookie_params]
             });
@@ -1168,6 +2785,91 @@ impl WebDriverAdapter {
         Ok(())
     }
 
+    /// Build the `Network.CookieParam` for a cookie
+    ///
+    /// URL-scoped cookies are sent with `url` and no `domain`, so they end up
+    /// host-only for the URL's host. Their path defaults to the URL's
+    /// directory and `https` URLs make them secure.
+    fn cookie_params(cookie: &crate::core::storage::CookieState) -> Result<serde_json::Value> {
+        use serde_json::json;
+
+        let same_site_str = match cookie.same_site {
+            crate::core::storage::SameSite::Strict => "Strict",
+            crate::core::storage::SameSite::Lax => "Lax",
+            crate::core::storage::SameSite::None => "None",
+        };
+
+        let mut cookie_params = json!({
+            "name": cookie.name,
+            "value": cookie.value,
+            "httpOnly": cookie.http_only,
+            "secure": cookie.secure,
+            "sameSite": same_site_str,
+        });
+
+        match &cookie.url {
+            Some(url) => {
+                if !cookie.domain.is_empty() {
+                    return Err(Error::invalid_argument(format!(
+                        "Cookie '{}' should have either url or domain, not both",
+                        cookie.name
+                    )));
+                }
+                let parsed = Url::parse(url)
+                    .ok()
+                    .filter(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some())
+                    .ok_or_else(|| Error::invalid_argument(format!(
+                        "Cookie '{}' has invalid url '{}': expected an http(s) URL with a host",
+                        cookie.name, url
+                    )))?;
+                let path = if cookie.path.is_empty() {
+                    // RFC 6265 default-path: everything up to the last '/'
+                    match parsed.path().rfind('/') {
+                        Some(0) | None => "/".to_string(),
+                        Some(end) => parsed.path()[..end].to_string(),
+                    }
+                } else {
+                    cookie.path.clone()
+                };
+                cookie_params["url"] = json!(url);
+                cookie_params["path"] = json!(path);
+                cookie_params["secure"] = json!(cookie.secure || parsed.scheme() == "https");
+            }
+            None => {
+                if cookie.domain.is_empty() || cookie.path.is_empty() {
+                    return Err(Error::invalid_argument(format!(
+                        "Cookie '{}' should have a url or a domain/path pair",
+                        cookie.name
+                    )));
+                }
+                cookie_params["domain"] = json!(cookie.domain);
+                cookie_params["path"] = json!(cookie.path);
+            }
+        }
+
+        // Only include expires if it's not a session cookie (-1)
+        if cookie.expires >= 0.0 {
+            cookie_params["expires"] = json!(cookie.expires);
+        }
+
+        if let Some(priority) = cookie.priority {
+            cookie_params["priority"] = json!(priority.as_str());
+        }
+
+        if let Some(same_party) = cookie.same_party {
+            cookie_params["sameParty"] = json!(same_party);
+        }
+
+        if let Some(partition_key) = &cookie.partition_key {
+            cookie_params["partitionKey"] = json!({
+                "topLevelSite": partition_key,
+                "hasCrossSiteAncestor": false,
+            });
+        }
+
+        Ok(cookie_params)
+    }
+
     async fn get_storage_for_origin_via_cdp(
         &self,
         origin: &str,
@@ -1285,6 +2987,29 @@ impl WebDriverAdapter {
         Ok(())
     }
 
+    /// Capture the IndexedDB databases of the current window's origin
+    pub async fn get_indexed_db(&self) -> Result<Vec<crate::core::storage::IndexedDbDatabase>> {
+        let result = self.execute_async_script(INDEXED_DB_SNAPSHOT_JS, Vec::new()).await?;
+        if let Some(error) = result.get("error").and_then(Value::as_str) {
+            return Err(Error::ActionFailed(format!("Failed to read IndexedDB: {}", error)));
+        }
+        Ok(serde_json::from_value(result["databases"].clone())?)
+    }
+
+    /// Recreate IndexedDB databases in the current window's origin
+    ///
+    /// Missing stores and indexes are created; records are written with `put`,
+    /// replacing existing records with the same key.
+    pub async fn set_indexed_db(&self, databases: &[crate::core::storage::IndexedDbDatabase]) -> Result<()> {
+        let result = self
+            .execute_async_script(INDEXED_DB_RESTORE_JS, vec![serde_json::to_value(databases)?])
+            .await?;
+        if let Some(error) = result.as_str() {
+            return Err(Error::ActionFailed(format!("Failed to restore IndexedDB: {}", error)));
+        }
+        Ok(())
+    }
+
     /// Get localStorage and sessionStorage for a given origin
     ///
     /// Requires an open page at the origin.
@@ -1380,6 +3105,175 @@ impl WebDriverAdapter {
     }
 }
 
+/// Whether a selector pierces shadow roots (`pierce=css` or `host >>> css`)
+fn is_deep_selector(selector: &str) -> bool {
+    selector.starts_with("pierce=") || deep_selector_parts(selector).len() > 1
+}
+
+/// Split a selector at the `>>>` combinators that descend into shadow roots
+///
+/// `>>>` inside quotes, brackets or parentheses (e.g. `[title=">>>"]`) is
+/// part of the selector. A `pierce=` selector is a single part.
+fn deep_selector_parts(selector: &str) -> Vec<&str> {
+    if selector.starts_with("pierce=") {
+        return vec![selector];
+    }
+    let mut parts = Vec::new();
+    let (mut quote, mut depth, mut escaped, mut start) = (None, 0usize, false, 0);
+    let bytes = selector.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if escaped {
+            escaped = false;
+        } else if byte == b'\\' {
+            escaped = true;
+        } else if let Some(open) = quote {
+            if byte == open {
+                quote = None;
+            }
+        } else {
+            match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'[' | b'(' => depth += 1,
+                b']' | b')' => depth = depth.saturating_sub(1),
+                b'>' if depth == 0 && bytes[i..].starts_with(b">>>") => {
+                    parts.push(selector[start..i].trim());
+                    i += 3;
+                    start = i;
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    parts.push(selector[start..].trim());
+    parts
+}
+
+/// Decode base64 screenshot data from `Page.captureScreenshot`
+fn decode_screenshot(data: &str) -> Result<Vec<u8>> {
+    BASE64_STANDARD
+        .decode(data)
+        .map_err(|e| Error::ScreenshotFailed(format!("Invalid screenshot data: {}", e)))
+}
+
+/// Write screenshot bytes to `path`, creating its directory
+fn write_screenshot(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Whether a frame switch failed because the iframe element went away
+fn is_frame_detached(error: &WebDriverErrorInner) -> bool {
+    matches!(
+        error,
+        WebDriverErrorInner::StaleElementReference(_) | WebDriverErrorInner::NoSuchFrame(_)
+    )
+}
+
+/// WebDriver key for a keyboard modifier
+fn modifier_key(modifier: &KeyboardModifier) -> Key {
+    match modifier {
+        KeyboardModifier::Alt => Key::Alt,
+        KeyboardModifier::Control => Key::Control,
+        KeyboardModifier::Meta => Key::Meta,
+        KeyboardModifier::Shift => Key::Shift,
+    }
+}
+
+/// Split a combo like `"Control+Shift+K"` into its modifiers and the key to press
+///
+/// The final key is a single character or a named key (`Enter`, `Tab`,
+/// `Escape`, `ArrowUp`, `F5`, ...); `"Control++"` presses `+`.
+fn parse_key_combo(combo: &str) -> Result<(Vec<KeyboardModifier>, char)> {
+    let (modifiers, key) = match combo.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => match combo.rsplit_once('+') {
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", combo),
+        },
+    };
+
+    let mut chars = key.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) => c,
+        _ => named_key(key)
+            .ok_or_else(|| Error::invalid_argument(format!("Unknown key '{}' in '{}'", key, combo)))?
+            .value(),
+    };
+    Ok((KeyboardModifier::parse_list(modifiers)?, key))
+}
+
+/// Width and height of the layout viewport in a `Page.getLayoutMetrics` result
+fn layout_viewport(metrics: &Value) -> (i64, i64) {
+    let viewport = &metrics["cssLayoutViewport"];
+    (
+        viewport["clientWidth"].as_i64().unwrap_or(0),
+        viewport["clientHeight"].as_i64().unwrap_or(0),
+    )
+}
+
+/// `key`, `code`, virtual key code and inserted text of the US layout key that types `ch`
+///
+/// Characters without a key of their own get an empty `code` and key code 0;
+/// Chrome still inserts their text.
+fn key_event_fields(ch: char) -> (String, String, u32, String) {
+    match ch {
+        '\n' | '\r' => ("Enter".to_string(), "Enter".to_string(), 13, "\r".to_string()),
+        ' ' => (" ".to_string(), "Space".to_string(), 32, " ".to_string()),
+        'a'..='z' | 'A'..='Z' => {
+            let upper = ch.to_ascii_uppercase();
+            (ch.to_string(), format!("Key{}", upper), upper as u32, ch.to_string())
+        }
+        '0'..='9' => (ch.to_string(), format!("Digit{}", ch), ch as u32, ch.to_string()),
+        _ => (ch.to_string(), String::new(), 0, ch.to_string()),
+    }
+}
+
+/// WebDriver key for a named key, using the DOM `KeyboardEvent.key` names
+fn named_key(name: &str) -> Option<Key> {
+    let key = match name.to_ascii_lowercase().as_str() {
+        "enter" => Key::Enter,
+        "tab" => Key::Tab,
+        "escape" | "esc" => Key::Escape,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "insert" => Key::Insert,
+        "space" => Key::Space,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "arrowup" => Key::Up,
+        "arrowdown" => Key::Down,
+        "arrowleft" => Key::Left,
+        "arrowright" => Key::Right,
+        "shift" => Key::Shift,
+        "control" => Key::Control,
+        "alt" => Key::Alt,
+        "meta" => Key::Meta,
+        "f1" => Key::F1,
+        "f2" => Key::F2,
+        "f3" => Key::F3,
+        "f4" => Key::F4,
+        "f5" => Key::F5,
+        "f6" => Key::F6,
+        "f7" => Key::F7,
+        "f8" => Key::F8,
+        "f9" => Key::F9,
+        "f10" => Key::F10,
+        "f11" => Key::F11,
+        "f12" => Key::F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
 impl Drop for WebDriverAdapter {
     fn drop(&mut self) {
         // Note: We can't await in Drop, so we just mark it for cleanup
@@ -1391,6 +3285,679 @@ impl Drop for WebDriverAdapter {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_key_combo() {
+        let (modifiers, key) = parse_key_combo("Control+K").unwrap();
+        assert_eq!(modifiers, vec![KeyboardModifier::Control]);
+        assert_eq!(key, 'K');
+
+        let (modifiers, key) = parse_key_combo("Shift+ArrowDown").unwrap();
+        assert_eq!(modifiers, vec![KeyboardModifier::Shift]);
+        assert_eq!(key, Key::Down.value());
+
+        let (modifiers, key) = parse_key_combo("Enter").unwrap();
+        assert!(modifiers.is_empty());
+        assert_eq!(key, Key::Enter.value());
+
+        let (modifiers, key) = parse_key_combo("Control++").unwrap();
+        assert_eq!(modifiers, vec![KeyboardModifier::Control]);
+        assert_eq!(key, '+');
+
+        assert!(parse_key_combo("Control+Bogus").is_err());
+        assert!(parse_key_combo("Hyper+K").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_commands_reconnect_after_dropped_connection() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+        use std::sync::atomic::AtomicBool;
+
+        // The first title request loses its connection
+        let dropped = AtomicBool::new(false);
+        let (adapter, commands) = fake_adapter(move |command, _| match command {
+            "GET /session/fake/title" if !dropped.swap(true, Ordering::SeqCst) => Some(FakeResponse::dropped()),
+            "GET /session/fake/title" => Some(FakeResponse::ok(json!("Reconnected"))),
+            "GET /session/fake/timeouts" => {
+                Some(FakeResponse::ok(json!({ "script": 30000, "pageLoad": 300000, "implicit": 0 })))
+            }
+            _ => None,
+        })
+        .await;
+
+        let title = adapter.title().await.expect("the command should be retried on a new connection");
+        assert_eq!(title, "Reconnected");
+
+        let commands: Vec<_> = commands.lock().unwrap().iter().map(|(_, command, _)| command.clone()).collect();
+        assert_eq!(
+            commands,
+            vec!["GET /session/fake/title", "GET /session/fake/timeouts", "GET /session/fake/title"],
+            "the session should be checked and reused, not recreated"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scripts_are_not_resent_after_dropped_connection() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+        use std::sync::atomic::AtomicBool;
+
+        // The script ran, but its response was lost
+        let dropped = AtomicBool::new(false);
+        let (adapter, commands) = fake_adapter(move |command, _| match command {
+            "POST /session/fake/execute/sync" if !dropped.swap(true, Ordering::SeqCst) => {
+                Some(FakeResponse::dropped())
+            }
+            "GET /session/fake/timeouts" => {
+                Some(FakeResponse::ok(json!({ "script": 30000, "pageLoad": 300000, "implicit": 0 })))
+            }
+            "GET /session/fake/title" => Some(FakeResponse::ok(json!("Reconnected"))),
+            _ => None,
+        })
+        .await;
+
+        let error = adapter.execute_script("document.forms[0].submit()").await.unwrap_err();
+        assert!(error.is_transport_error(), "{}", error);
+        assert_eq!(adapter.title().await.unwrap(), "Reconnected");
+
+        let commands: Vec<_> = commands.lock().unwrap().iter().map(|(_, command, _)| command.clone()).collect();
+        assert_eq!(
+            commands,
+            vec!["POST /session/fake/execute/sync", "GET /session/fake/timeouts", "GET /session/fake/title"],
+            "the script should not run twice, and later commands use the new connection"
+        );
+    }
+
+    #[test]
+    fn test_key_event_fields() {
+        let fields = |key: &str, code: &str, key_code, text: &str| {
+            (key.to_string(), code.to_string(), key_code, text.to_string())
+        };
+        assert_eq!(key_event_fields('a'), fields("a", "KeyA", 65, "a"));
+        assert_eq!(key_event_fields('Z'), fields("Z", "KeyZ", 90, "Z"));
+        assert_eq!(key_event_fields('7'), fields("7", "Digit7", 55, "7"));
+        assert_eq!(key_event_fields('\n'), fields("Enter", "Enter", 13, "\r"));
+        assert_eq!(key_event_fields('é'), fields("é", "", 0, "é"));
+    }
+
+    #[test]
+    fn test_is_frame_detached() {
+        use thirtyfour::error::WebDriverErrorInfo;
+
+        let info = || WebDriverErrorInfo::new("stale element reference".to_string());
+        assert!(is_frame_detached(&WebDriverErrorInner::StaleElementReference(info())));
+        assert!(is_frame_detached(&WebDriverErrorInner::NoSuchFrame(info())));
+        assert!(!is_frame_detached(&WebDriverErrorInner::NoSuchElement(info())));
+    }
+
+    #[test]
+    fn test_is_deep_selector() {
+        assert!(is_deep_selector("pierce=button"));
+        assert!(is_deep_selector("my-app >>> button.primary"));
+        assert!(!is_deep_selector("div > button"));
+        assert!(!is_deep_selector("button[data-pierce='1']"));
+        assert!(!is_deep_selector("button[title='a >>> b']"));
+        assert!(!is_deep_selector("a:not([href=\">>>\"])"));
+    }
+
+    #[test]
+    fn test_deep_selector_splits_only_outside_quotes_and_brackets() {
+        assert_eq!(deep_selector_parts("my-app >>> x-list >>> li"), ["my-app", "x-list", "li"]);
+        assert_eq!(
+            deep_selector_parts(r#"my-app >>> button[title=">>>"]"#),
+            ["my-app", r#"button[title=">>>"]"#]
+        );
+        assert_eq!(deep_selector_parts(r"my-app[data-x='it\'s >>> ok']"), [r"my-app[data-x='it\'s >>> ok']"]);
+        assert_eq!(deep_selector_parts("pierce=a >>> b"), ["pierce=a >>> b"]);
+        assert_eq!(deep_selector_parts("#banner"), ["#banner"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_element_pierces_shadow_roots_in_script() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/execute/sync") => Some(FakeResponse::ok(json!([{ W3C_ELEMENT_KEY: "shadow-button" }]))),
+            _ => None,
+        })
+        .await;
+
+        let element = adapter.find_element("my-app >>> button").await.unwrap();
+        assert_eq!(element.element_id().to_string(), "shadow-button");
+        assert_eq!(adapter.count_elements("my-app >>> button").await.unwrap(), 1);
+
+        let commands = commands.lock().unwrap();
+        assert!(commands.iter().all(|(_, command, _)| command == "POST /session/fake/execute/sync"));
+        assert_eq!(commands[0].2["script"], DEEP_QUERY_JS);
+        assert_eq!(commands[0].2["args"], json!([["my-app", "button"]]));
+    }
+
+    #[tokio::test]
+    async fn test_full_page_screenshot_restores_resized_viewport() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use std::sync::atomic::AtomicBool;
+
+        // Capturing stretches the viewport to the content height until the
+        // override is cleared
+        let stretched = AtomicBool::new(false);
+        let (adapter, commands) = fake_adapter(move |command, body| match command {
+            c if c.ends_with("/goog/cdp/execute") => {
+                match body["cmd"].as_str() {
+                    Some("Page.captureScreenshot") => stretched.store(true, Ordering::SeqCst),
+                    Some("Emulation.clearDeviceMetricsOverride") => stretched.store(false, Ordering::SeqCst),
+                    _ => {}
+                }
+                let height = if stretched.load(Ordering::SeqCst) { 2000 } else { 600 };
+                Some(FakeResponse::ok(json!({
+                    "data": "iVBORw0KGgo=",
+                    "cssContentSize": { "width": 800, "height": 2000 },
+                    "cssLayoutViewport": { "clientWidth": 800, "clientHeight": height },
+                })))
+            }
+            _ => None,
+        })
+        .await;
+
+        let options = ScreenshotOptions { full_page: Some(true), ..Default::default() };
+        adapter.screenshot_base64_with_options(&options).await.unwrap();
+
+        let sent: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command.ends_with("/goog/cdp/execute"))
+            .map(|(_, _, body)| body["cmd"].as_str().unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(
+            sent,
+            vec![
+                "Page.getLayoutMetrics",
+                "Page.captureScreenshot",
+                "Page.getLayoutMetrics",
+                "Emulation.clearDeviceMetricsOverride",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_page_screenshot_restores_previous_device_metrics() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use std::sync::atomic::AtomicBool;
+
+        let stretched = AtomicBool::new(false);
+        let (adapter, commands) = fake_adapter(move |command, body| match command {
+            c if c.ends_with("/goog/cdp/execute") => {
+                if body["cmd"] == "Page.captureScreenshot" {
+                    stretched.store(true, Ordering::SeqCst);
+                }
+                let height = if stretched.load(Ordering::SeqCst) { 2000 } else { 844 };
+                Some(FakeResponse::ok(json!({
+                    "data": "iVBORw0KGgo=",
+                    "cssContentSize": { "width": 390, "height": 2000 },
+                    "cssLayoutViewport": { "clientWidth": 390, "clientHeight": height },
+                })))
+            }
+            _ => None,
+        })
+        .await;
+
+        // A mobile override set before the capture comes back unchanged
+        let mobile = json!({ "width": 390, "height": 844, "deviceScaleFactor": 3, "mobile": true });
+        adapter
+            .execute_cdp_with_params("Emulation.setDeviceMetricsOverride", mobile.clone())
+            .await
+            .unwrap();
+        commands.lock().unwrap().clear();
+
+        let options = ScreenshotOptions { full_page: Some(true), ..Default::default() };
+        adapter.screenshot_base64_with_options(&options).await.unwrap();
+
+        let sent: Vec<_> = commands.lock().unwrap().iter().map(|(_, _, body)| body.clone()).collect();
+        let cleared = sent
+            .iter()
+            .position(|body| body["cmd"] == "Emulation.clearDeviceMetricsOverride")
+            .expect("the capture's override is cleared");
+        assert_eq!(
+            sent[cleared + 1..],
+            [json!({ "cmd": "Emulation.setDeviceMetricsOverride", "params": mobile })]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_pauses_animation_timeline() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/goog/cdp/execute") => Some(FakeResponse::ok(json!({ "data": "/9j/4AAQ" }))),
+            _ => None,
+        })
+        .await;
+
+        let options = ScreenshotOptions {
+            animations: Some(ScreenshotAnimations::Disabled),
+            r#type: Some(ScreenshotType::Jpeg),
+            ..Default::default()
+        };
+        adapter.screenshot_base64_with_options(&options).await.unwrap();
+
+        let sent: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command.ends_with("/goog/cdp/execute"))
+            .map(|(_, _, body)| body.clone())
+            .collect();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0], json!({ "cmd": "Animation.setPlaybackRate", "params": { "playbackRate": 0.0 } }));
+        assert_eq!(sent[1]["cmd"], "Page.captureScreenshot");
+        assert_eq!(sent[1]["params"]["format"], "jpeg");
+        assert_eq!(sent[2], json!({ "cmd": "Animation.setPlaybackRate", "params": { "playbackRate": 1.0 } }));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_selector_uses_single_observer_command() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        // The async script answers after 300ms, standing in for an element
+        // that appears while the observer is waiting
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/execute/async") => Some(
+                FakeResponse::ok(json!({ "element": { W3C_ELEMENT_KEY: "late" } })).after(Duration::from_millis(300)),
+            ),
+            _ => None,
+        })
+        .await;
+
+        let start = std::time::Instant::now();
+        adapter
+            .wait_for_selector("#late", WaitForSelectorState::Visible, Duration::from_secs(5))
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        assert!(elapsed >= Duration::from_millis(300), "returned before the element appeared");
+        assert!(elapsed < Duration::from_secs(2), "detection took {:?}", elapsed);
+        let commands: Vec<_> = commands.lock().unwrap().iter().map(|(_, c, _)| c.clone()).collect();
+        assert_eq!(
+            commands,
+            vec!["POST /session/fake/execute/async".to_string()],
+            "the wait should be one async script, not repeated polling"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_selector_visible_returns_element() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use std::sync::atomic::AtomicBool;
+
+        // The banner shows up after the first slice has timed out
+        let shown = AtomicBool::new(false);
+        let (adapter, commands) = fake_adapter(move |command, _| match command {
+            c if c.ends_with("/execute/async") && !shown.swap(true, Ordering::SeqCst) => {
+                Some(FakeResponse::ok(json!(false)))
+            }
+            c if c.ends_with("/execute/async") => {
+                Some(FakeResponse::ok(json!({ "element": { W3C_ELEMENT_KEY: "banner" } })))
+            }
+            _ => None,
+        })
+        .await;
+
+        let element = adapter
+            .wait_for_selector("#banner", WaitForSelectorState::Visible, Duration::from_secs(5))
+            .await
+            .unwrap()
+            .expect("visible state should return the element");
+        assert_eq!(element.element_id().to_string(), "banner");
+
+        let commands = commands.lock().unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[1].2["args"][0], json!(["#banner"]));
+        assert_eq!(commands[1].2["args"][1], "visible");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_selector_detached_returns_none() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/execute/async") => Some(FakeResponse::ok(json!({ "element": null }))),
+            _ => None,
+        })
+        .await;
+
+        let element = adapter
+            .wait_for_selector(".loading", WaitForSelectorState::Detached, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(element.is_none());
+        assert_eq!(commands.lock().unwrap()[0].2["args"][1], "detached");
+
+        // An element that never detaches times out with the state in the message
+        let error = adapter
+            .wait_for_selector(".loading", WaitForSelectorState::Detached, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(matches!(&error, Error::Timeout { message, .. } if message.contains("not detached")), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn test_document_content_reassembles_large_documents() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        // The document is too large for one reply, so it is stashed and read in slices
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            c if c.ends_with("/execute/sync") && body["script"] == CONTENT_JS => {
+                Some(FakeResponse::ok(json!({ "length": 28 })))
+            }
+            c if c.ends_with("/execute/sync") => Some(FakeResponse::ok(match body["args"][0].as_u64() {
+                Some(0) => json!(["<!DOCTYPE html>", 15]),
+                _ => json!(["<html></html>", 28]),
+            })),
+            _ => None,
+        })
+        .await;
+
+        let html = adapter.document_content().await.unwrap();
+        assert_eq!(html, "<!DOCTYPE html><html></html>");
+        assert_eq!(commands.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_cdp_only_methods_report_missing_cdp() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        // A grid node that doesn't route the chromedriver CDP extension
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/goog/cdp/execute") => {
+                Some(FakeResponse::error(404, "unknown command", "Unable to find command"))
+            }
+            _ => None,
+        })
+        .await;
+        assert!(!adapter.has_cdp());
+
+        let error = adapter.get_cookies().await.unwrap_err();
+        assert!(matches!(error, Error::NotImplemented(_)), "unexpected error: {:?}", error);
+        assert!(error.to_string().contains("CDP required"));
+        // Reported up front, without sending the command to the endpoint
+        assert!(commands.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_stabilization_is_undone_when_capture_fails() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            c if c.ends_with("/goog/cdp/execute") => {
+                Some(FakeResponse::error(500, "unknown error", "Unable to capture screenshot"))
+            }
+            _ => None,
+        })
+        .await;
+
+        let options = crate::core::ScreenshotOptionsBuilder::default()
+            .animations(ScreenshotAnimations::Disabled)
+            .mask(vec![".ad".to_string()])
+            .build()
+            .unwrap();
+        let error = adapter.screenshot_with_options(&options).await.unwrap_err();
+        assert!(matches!(error, Error::ScreenshotFailed(_)), "unexpected error: {:?}", error);
+
+        let sent: Vec<_> = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, command, body)| (command.clone(), body.clone()))
+            .collect();
+        assert_eq!(sent.len(), 4);
+        assert_eq!(sent[0].1["script"], SCREENSHOT_PREPARE_JS);
+        assert_eq!(sent[0].1["args"], json!([true, [".ad"], "#FF00FF"]));
+        assert_eq!(sent[1].1["cmd"], "Animation.setPlaybackRate");
+        assert_eq!(sent[2].1["cmd"], "Page.captureScreenshot");
+        assert_eq!(sent[3].1["script"], SCREENSHOT_RESTORE_JS);
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_restores_everything_when_a_restore_step_fails() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        // Resuming the animation timeline fails; the later steps must still run
+        let (adapter, commands) = fake_adapter(|command, body| match command {
+            c if c.ends_with("/goog/cdp/execute") => {
+                if body["cmd"] == "Animation.setPlaybackRate" && body["params"]["playbackRate"] == 1.0 {
+                    Some(FakeResponse::error(500, "unknown error", "target crashed"))
+                } else {
+                    Some(FakeResponse::ok(json!({ "data": "iVBORw0KGgo=" })))
+                }
+            }
+            _ => None,
+        })
+        .await;
+
+        let options = crate::core::ScreenshotOptionsBuilder::default()
+            .animations(ScreenshotAnimations::Disabled)
+            .omit_background(true)
+            .build()
+            .unwrap();
+        let error = adapter.screenshot_with_options(&options).await.unwrap_err();
+        assert!(error.to_string().contains("target crashed"), "unexpected error: {:?}", error);
+
+        let sent: Vec<_> = commands.lock().unwrap().iter().map(|(_, _, body)| body.clone()).collect();
+        let restore = sent.iter().position(|body| body["script"] == SCREENSHOT_RESTORE_JS);
+        assert!(restore.is_some(), "stabilization was not undone: {:?}", sent);
+        assert_eq!(
+            sent.last().unwrap(),
+            &json!({ "cmd": "Emulation.setDefaultBackgroundColorOverride", "params": {} })
+        );
+    }
+
+    /// Drive `future` to completion on a paused clock without letting tokio
+    /// auto-advance time while it waits on the fake server's real sockets
+    async fn without_auto_advance<T>(future: impl std::future::Future<Output = T>) -> T {
+        tokio::pin!(future);
+        loop {
+            if let std::task::Poll::Ready(value) = futures::poll!(&mut future) {
+                return value;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keepalive_pings_idle_session_at_interval() {
+        use crate::driver::fake_webdriver::{fake_adapter, CommandLog, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, _| match command {
+            "GET /session/fake/timeouts" => {
+                Some(FakeResponse::ok(json!({ "script": 30000, "pageLoad": 300000, "implicit": 0 })))
+            }
+            _ => None,
+        })
+        .await;
+        let pings = |commands: &CommandLog| {
+            commands.lock().unwrap().iter().filter(|(_, command, _)| command == "GET /session/fake/timeouts").count()
+        };
+        // Let any ping that is due reach the fake server before counting
+        let settle = || {
+            without_auto_advance(async {
+                let deadline = std::time::Instant::now() + Duration::from_millis(50);
+                while std::time::Instant::now() < deadline {
+                    tokio::task::yield_now().await;
+                }
+            })
+        };
+
+        tokio::time::pause();
+        without_auto_advance(adapter.execute_script("return 1;")).await.unwrap();
+        commands.lock().unwrap().clear();
+        let interval = Duration::from_millis(200);
+        adapter.spawn_keepalive(interval);
+
+        for expected in 1..=2 {
+            tokio::time::advance(interval - Duration::from_millis(10)).await;
+            settle().await;
+            assert_eq!(pings(&commands), expected - 1, "pinged before the session was idle for {:?}", interval);
+            // Timer deadlines round up to the next millisecond
+            tokio::time::advance(Duration::from_millis(20)).await;
+            settle().await;
+            assert_eq!(pings(&commands), expected, "expected a ping every {:?}", interval);
+        }
+
+        // Regular commands keep the session alive on their own
+        commands.lock().unwrap().clear();
+        for _ in 0..8 {
+            without_auto_advance(adapter.execute_script("return 1;")).await.unwrap();
+            tokio::time::advance(Duration::from_millis(60)).await;
+            settle().await;
+        }
+        assert_eq!(pings(&commands), 0, "a busy session should not be pinged");
+
+        // Closing the session stops the keepalive
+        without_auto_advance(adapter.close()).await.unwrap();
+        commands.lock().unwrap().clear();
+        for _ in 0..3 {
+            tokio::time::advance(interval).await;
+            settle().await;
+        }
+        assert!(commands.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_accessible_name_lookup_cleans_up_when_resolving_fails() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+
+        let (adapter, commands) = fake_adapter(|command, body| match (command, body["cmd"].as_str()) {
+            (_, Some("Accessibility.getFullAXTree")) => Some(FakeResponse::ok(json!({ "nodes": [
+                { "nodeId": "1", "role": { "value": "button" }, "name": { "value": "Save" }, "backendDOMNodeId": 10 },
+                { "nodeId": "2", "role": { "value": "button" }, "name": { "value": "Save" }, "backendDOMNodeId": 11 }
+            ] }))),
+            (_, Some("DOM.pushNodesByBackendIdsToFrontend")) => Some(FakeResponse::ok(json!({ "nodeIds": [7, 8] }))),
+            (_, Some("DOM.resolveNode")) if body["params"]["nodeId"] == 8 => {
+                Some(FakeResponse::error(500, "unknown error", "No node with given id found"))
+            }
+            (_, Some("DOM.resolveNode")) => Some(FakeResponse::ok(json!({ "object": { "objectId": "node-7" } }))),
+            ("POST /session/fake/execute/sync", _) => Some(FakeResponse::ok(json!([]))),
+            _ => None,
+        })
+        .await;
+
+        assert!(adapter.find_elements_by_accessible_name("button", "Save", true).await.is_err());
+        let commands = commands.lock().unwrap();
+        let take = commands.iter().position(|(_, command, _)| command == "POST /session/fake/execute/sync");
+        let release = commands.iter().position(|(_, _, body)| body["cmd"] == "Runtime.releaseObjectGroup");
+        assert!(take.is_some(), "the stash should be taken back: {:?}", commands);
+        assert!(release > take, "the object group should be released: {:?}", commands);
+        assert!(commands.iter().all(|(_, _, body)| body["cmd"] != "Runtime.callFunctionOn"));
+    }
+
+    #[test]
+    fn test_cookie_params_derive_scope_from_url() {
+        use crate::core::storage::CookieState;
+
+        let cookie = CookieState {
+            name: "session".to_string(),
+            value: "abc".to_string(),
+            url: Some("https://app.example.com/docs/index.html".to_string()),
+            same_party: Some(true),
+            partition_key: Some("https://example.org".to_string()),
+            ..Default::default()
+        };
+        let params = WebDriverAdapter::cookie_params(&cookie).unwrap();
+        assert_eq!(params["url"], "https://app.example.com/docs/index.html");
+        assert!(params.get("domain").is_none(), "url cookies should be host-only");
+        assert_eq!(params["path"], "/docs");
+        assert_eq!(params["secure"], true);
+        assert_eq!(params["sameParty"], true);
+        assert_eq!(params["partitionKey"]["topLevelSite"], "https://example.org");
+        assert!(params.get("expires").is_none());
+
+        let cookie = CookieState {
+            name: "plain".to_string(),
+            url: Some("http://example.com".to_string()),
+            path: "/app".to_string(),
+            ..Default::default()
+        };
+        let params = WebDriverAdapter::cookie_params(&cookie).unwrap();
+        assert_eq!(params["path"], "/app");
+        assert_eq!(params["secure"], false);
+        assert!(params.get("sameParty").is_none());
+    }
+
+    #[test]
+    fn test_cookie_params_require_url_or_domain_and_path() {
+        use crate::core::storage::CookieState;
+
+        let invalid = [
+            CookieState { name: "none".to_string(), ..Default::default() },
+            CookieState {
+                name: "no-path".to_string(),
+                domain: "example.com".to_string(),
+                ..Default::default()
+            },
+            CookieState {
+                name: "both".to_string(),
+                url: Some("https://example.com/".to_string()),
+                domain: "example.com".to_string(),
+                ..Default::default()
+            },
+            CookieState {
+                name: "data".to_string(),
+                url: Some("data:text/html,hi".to_string()),
+                ..Default::default()
+            },
+        ];
+        for cookie in &invalid {
+            let result = WebDriverAdapter::cookie_params(cookie);
+            assert!(matches!(result, Err(Error::InvalidArgument(_))), "{} should be rejected", cookie.name);
+        }
+
+        let cookie = CookieState {
+            name: "scoped".to_string(),
+            domain: ".example.com".to_string(),
+            path: "/".to_string(),
+            ..Default::default()
+        };
+        let params = WebDriverAdapter::cookie_params(&cookie).unwrap();
+        assert_eq!(params["domain"], ".example.com");
+        assert!(params.get("url").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_id_and_capability_refresh() {
+        use crate::driver::fake_webdriver::{fake_adapter, FakeResponse};
+        use serde_json::json;
+        use std::sync::atomic::AtomicBool;
+
+        let updated = Arc::new(AtomicBool::new(false));
+        let browser = Arc::clone(&updated);
+        let (adapter, commands) = fake_adapter(move |command, _| match command {
+            "GET /session/fake" => {
+                let version = if browser.load(Ordering::SeqCst) { "145.0.2" } else { "144.0.1" };
+                Some(FakeResponse::ok(json!({ "capabilities": { "browserVersion": version } })))
+            }
+            _ => None,
+        })
+        .await;
+
+        assert_eq!(adapter.session_id().await.unwrap(), "fake");
+
+        assert_eq!(adapter.browser_version().await.unwrap(), "144.0.1");
+        // The browser is updated in place; the cached capabilities go stale
+        updated.store(true, Ordering::SeqCst);
+        assert_eq!(adapter.browser_version().await.unwrap(), "144.0.1", "capabilities are cached");
+        adapter.refresh_session_capabilities().await;
+        assert_eq!(adapter.browser_version().await.unwrap(), "145.0.2");
+
+        let lookups = commands
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, command, _)| command == "GET /session/fake")
+            .count();
+        assert_eq!(lookups, 2);
+    }
+
     #[tokio::test]
     async fn test_adapter_closed_error() {
         // Create a mock adapter (this would need a real WebDriver in practice)
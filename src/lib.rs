@@ -33,7 +33,7 @@ pub mod core;
 pub mod driver;
 
 // Re-export commonly used types for convenience
-pub use async_api::{Browser, BrowserContext, BrowserType, ElementHandle, ElementInFrame, FrameLocator, Locator, Mouse, MouseClickOptions, MouseTarget, MoveOptions, Page, Playwright};
+pub use async_api::{Browser, BrowserContext, BrowserType, ConsoleMessage, Download, ElementHandle, ElementInFrame, FileChooser, FrameLocator, Locator, Mouse, MouseClickOptions, MouseTarget, MoveOptions, Page, Playwright, Request, Response, Touchscreen, WebSocket, WebSocketFrame};
 pub use core::{init_logging, init_logging_with_level, Error, Result};
 
 /// Prelude module for convenient imports
@@ -43,22 +43,20 @@ pub use core::{init_logging, init_logging_with_level, Error, Result};
 /// use sparkle::prelude::*;
 /// ```
 pub mod prelude {
-    pub use crate::async_api::{Browser, BrowserContext, BrowserType, ElementHandle, ElementInFrame, FrameLocator, Locator, Mouse, MouseClickOptions, MouseTarget, MoveOptions, Page, Playwright};
+    pub use crate::async_api::{Browser, BrowserContext, BrowserType, ConsoleMessage, Download, ElementHandle, ElementInFrame, FilterOptions, FilterOptionsBuilder, FrameLocator, Locator, Mouse, MouseClickOptions, MouseTarget, MoveOptions, Page, Playwright, Request, Response, Touchscreen, WebSocket, WebSocketFrame};
     pub use crate::core::{
         init_logging, init_logging_with_level,
-        BrowserContextOptions, BrowserContextOptionsBuilder, ClickOptions, ClickOptionsBuilder,
+        AddTagOptions, AddTagOptionsBuilder, BrowserContextOptions, BrowserContextOptionsBuilder, AxNode, ClickOptions, ClickOptionsBuilder, Clip,
         ConnectOptions, ConnectOptionsBuilder, ConnectOverCdpOptions, ConnectOverCdpOptionsBuilder,
-        CookieState, Error, LaunchOptions, LaunchOptionsBuilder, NameValue, NavigationOptions, 
-        NavigationOptionsBuilder, OriginState, ProxySettings, Result, SameSite, ScreenshotOptions, 
-        ScreenshotOptionsBuilder, StorageState, StorageStateSource, TypeOptions, TypeOptionsBuilder,
-        WaitUntilState,
+        ColorScheme, CookiePriority, CookieState, EmulateMediaOptions, EmulateMediaOptionsBuilder, Error, FormValue, GetByRoleOptions, GetByRoleOptionsBuilder, HeadlessMode, KeyboardModifier, LaunchOptions, LaunchOptionsBuilder, Media, NameValue, NavigationOptions, NotActionableReason, 
+        NavigationOptionsBuilder, OriginState, Protocol, ProxySettings, ReducedMotion, Result, RetryOptions, RetryOptionsBuilder, SameSite, ScreenshotAnimations, ScreenshotImage, ScreenshotOptions, ScreenshotType, 
+        ScreenshotOptionsBuilder, SlowMoConfig, SlowMoScope, StorageState, StorageStateOptions, StorageStateOptionsBuilder, StorageStateSource, TapOptions, TapOptionsBuilder, TypeOptions, TypeOptionsBuilder,
+        WaitForSelectorOptions, WaitForSelectorOptionsBuilder, WaitForSelectorState, WaitUntilState,
     };
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn it_works() {
         // Basic compilation test